@@ -0,0 +1,48 @@
+use std::collections::HashSet;
+use std::time::Duration;
+
+use spacetimedb::{Identity, ReducerContext, Table};
+
+use crate::services::stats;
+use crate::tables::{BuffExpiryAgentSchedule, BuffState};
+use crate::tables::agent_schedule::buff_expiry_agent_schedule;
+use crate::tables::buff::buff_state;
+
+const TICK_INTERVAL: Duration = Duration::from_secs(2);
+
+pub(crate) fn init(ctx: &ReducerContext) {
+    if ctx.db.buff_expiry_agent_schedule().iter().next().is_none() {
+        ctx.db.buff_expiry_agent_schedule().insert(BuffExpiryAgentSchedule {
+            scheduled_id: 0,
+            scheduled_at: TICK_INTERVAL.into(),
+        });
+    }
+}
+
+/// Deletes expired buffs and recomputes stats for everyone who lost one.
+#[spacetimedb::reducer]
+pub fn buff_expiry_agent(ctx: &ReducerContext, _timer: BuffExpiryAgentSchedule) -> Result<(), String> {
+    if ctx.sender != ctx.identity() {
+        return Err("buff_expiry_agent is scheduler-only".to_string());
+    }
+
+    let expired: Vec<BuffState> = ctx
+        .db
+        .buff_state()
+        .iter()
+        .filter(|b| b.expires_at.is_some_and(|t| t <= ctx.timestamp))
+        .collect();
+    if expired.is_empty() {
+        return Ok(());
+    }
+
+    let mut affected: HashSet<Identity> = HashSet::new();
+    for buff in expired {
+        affected.insert(buff.identity);
+        ctx.db.buff_state().buff_key().delete(buff.buff_key);
+    }
+    for identity in affected {
+        stats::recompute(ctx, identity);
+    }
+    Ok(())
+}
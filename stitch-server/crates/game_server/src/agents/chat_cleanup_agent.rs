@@ -0,0 +1,42 @@
+use std::time::Duration;
+
+use spacetimedb::{ReducerContext, Table};
+
+use crate::services::balance;
+use crate::tables::ChatCleanupAgentSchedule;
+use crate::tables::agent_schedule::chat_cleanup_agent_schedule;
+use crate::tables::chat::chat_message;
+
+const TICK_INTERVAL: Duration = Duration::from_secs(3600);
+
+pub(crate) fn init(ctx: &ReducerContext) {
+    if ctx.db.chat_cleanup_agent_schedule().iter().next().is_none() {
+        ctx.db.chat_cleanup_agent_schedule().insert(ChatCleanupAgentSchedule {
+            scheduled_id: 0,
+            scheduled_at: TICK_INTERVAL.into(),
+        });
+    }
+}
+
+/// Deletes chat messages older than `chat.retention_hours`.
+#[spacetimedb::reducer]
+pub fn chat_cleanup_agent(ctx: &ReducerContext, _timer: ChatCleanupAgentSchedule) -> Result<(), String> {
+    if ctx.sender != ctx.identity() {
+        return Err("chat_cleanup_agent is scheduler-only".to_string());
+    }
+    let retention = Duration::from_secs(balance::get_u64(ctx, balance::CHAT_RETENTION_HOURS, 48) * 3600);
+    let expired: Vec<u64> = ctx
+        .db
+        .chat_message()
+        .iter()
+        .filter(|m| ctx.timestamp.duration_since(m.sent_at).is_some_and(|age| age >= retention))
+        .map(|m| m.message_id)
+        .collect();
+    if !expired.is_empty() {
+        log::info!("chat cleanup: deleted {} messages", expired.len());
+    }
+    for message_id in expired {
+        ctx.db.chat_message().message_id().delete(message_id);
+    }
+    Ok(())
+}
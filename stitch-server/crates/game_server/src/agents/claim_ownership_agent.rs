@@ -0,0 +1,97 @@
+use std::time::Duration;
+
+use spacetimedb::{ReducerContext, Table};
+
+use crate::services::claims;
+use crate::tables::{ClaimMemberState, ClaimOwnershipAgentSchedule};
+use crate::tables::agent_schedule::claim_ownership_agent_schedule;
+use crate::tables::claim_member_state::claim_member_state;
+use crate::tables::claim_state::claim_state;
+use crate::tables::session_state::session_state;
+
+const TICK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+pub(crate) fn init(ctx: &ReducerContext) {
+    if ctx.db.claim_ownership_agent_schedule().iter().next().is_none() {
+        ctx.db.claim_ownership_agent_schedule().insert(ClaimOwnershipAgentSchedule {
+            scheduled_id: 0,
+            scheduled_at: TICK_INTERVAL.into(),
+        });
+    }
+}
+
+/// Owner inactivity after which a member of the given rank may inherit the claim.
+/// Higher ranks take over sooner.
+fn takeover_after(rank: u8) -> Option<Duration> {
+    match rank {
+        claims::RANK_CO_OWNER => Some(DAY * 7),
+        claims::RANK_OFFICER => Some(DAY * 14),
+        claims::RANK_MEMBER => Some(DAY * 21),
+        _ => None,
+    }
+}
+
+#[spacetimedb::reducer]
+pub fn claim_ownership_agent(ctx: &ReducerContext, _timer: ClaimOwnershipAgentSchedule) -> Result<(), String> {
+    if ctx.sender != ctx.identity() {
+        return Err("claim_ownership_agent is scheduler-only".to_string());
+    }
+
+    let claims_snapshot: Vec<_> = ctx.db.claim_state().iter().collect();
+    for claim in claims_snapshot {
+        if ctx.db.session_state().identity().find(claim.owner_identity).is_some() {
+            continue;
+        }
+
+        let owner_last_active = ctx
+            .db
+            .claim_member_state()
+            .member_key()
+            .find(claims::member_key(claim.claim_id, claim.owner_identity))
+            .map(|m| m.last_active_at)
+            .unwrap_or(claim.updated_at);
+        let Some(inactive_for) = ctx.timestamp.duration_since(owner_last_active) else {
+            continue;
+        };
+
+        let successor = ctx
+            .db
+            .claim_member_state()
+            .iter()
+            .filter(|m| {
+                m.claim_id == claim.claim_id
+                    && m.status == claims::MEMBER_STATUS_ACTIVE
+                    && m.member_identity != claim.owner_identity
+                    && m.last_active_at > owner_last_active
+                    && takeover_after(m.rank).is_some_and(|after| inactive_for >= after)
+            })
+            .max_by_key(|m: &ClaimMemberState| (m.rank, m.last_active_at));
+
+        if let Some(successor) = successor {
+            claims::transfer_ownership(ctx, claim, successor.member_identity);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn higher_ranks_take_over_sooner() {
+        let co_owner = takeover_after(claims::RANK_CO_OWNER).unwrap();
+        let officer = takeover_after(claims::RANK_OFFICER).unwrap();
+        let member = takeover_after(claims::RANK_MEMBER).unwrap();
+        assert!(co_owner < officer && officer < member);
+        assert_eq!(co_owner, DAY * 7);
+    }
+
+    #[test]
+    fn guests_and_owners_never_take_over() {
+        assert_eq!(takeover_after(claims::RANK_GUEST), None);
+        assert_eq!(takeover_after(claims::RANK_OWNER), None);
+    }
+}
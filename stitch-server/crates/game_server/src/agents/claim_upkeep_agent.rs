@@ -0,0 +1,73 @@
+use std::time::Duration;
+
+use spacetimedb::{ReducerContext, Table};
+
+use crate::services::claims;
+use crate::tables::ClaimUpkeepAgentSchedule;
+use crate::tables::agent_schedule::claim_upkeep_agent_schedule;
+use crate::tables::claim_local_state::claim_local_state;
+use crate::tables::claim_state::claim_state;
+
+const TICK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+const BASE_UPKEEP: u32 = 1;
+const TILES_PER_SUPPLY: u32 = 20;
+const MEMBERS_PER_SUPPLY: u32 = 5;
+
+pub(crate) fn init(ctx: &ReducerContext) {
+    if ctx.db.claim_upkeep_agent_schedule().iter().next().is_none() {
+        ctx.db.claim_upkeep_agent_schedule().insert(ClaimUpkeepAgentSchedule {
+            scheduled_id: 0,
+            scheduled_at: TICK_INTERVAL.into(),
+        });
+    }
+}
+
+pub fn upkeep_cost(num_tiles: u32, population: u32) -> u32 {
+    BASE_UPKEEP
+        .saturating_add(num_tiles / TILES_PER_SUPPLY)
+        .saturating_add(population / MEMBERS_PER_SUPPLY)
+}
+
+#[spacetimedb::reducer]
+pub fn claim_upkeep_agent(ctx: &ReducerContext, _timer: ClaimUpkeepAgentSchedule) -> Result<(), String> {
+    if ctx.sender != ctx.identity() {
+        return Err("claim_upkeep_agent is scheduler-only".to_string());
+    }
+
+    let claims_snapshot: Vec<_> = ctx.db.claim_state().iter().collect();
+    for claim in claims_snapshot {
+        let mut local = claims::ensure_local_state(ctx, &claim);
+        local.population = claims::active_member_count(ctx, claim.claim_id).max(1);
+        local.num_tiles = claims::hex_tile_count(claim.radius);
+
+        let cost = upkeep_cost(local.num_tiles, local.population);
+        local.supplies = local.supplies.saturating_sub(cost);
+        if local.supplies == 0 && local.protected {
+            local.protected = false;
+            log::info!("claim lost protection: claim_id={} (supplies depleted)", claim.claim_id);
+        }
+        local.last_upkeep_at = ctx.timestamp;
+        local.updated_at = ctx.timestamp;
+        ctx.db.claim_local_state().claim_id().update(local);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::upkeep_cost;
+
+    #[test]
+    fn upkeep_cost_adds_tiles_and_population_steps_to_the_base() {
+        assert_eq!(upkeep_cost(0, 0), 1);
+        assert_eq!(upkeep_cost(19, 4), 1);
+        assert_eq!(upkeep_cost(20, 5), 3);
+        assert_eq!(upkeep_cost(127, 12), 1 + 6 + 2);
+    }
+
+    #[test]
+    fn upkeep_cost_does_not_overflow_on_huge_claims() {
+        assert_eq!(upkeep_cost(u32::MAX, u32::MAX), 1 + u32::MAX / 20 + u32::MAX / 5);
+    }
+}
@@ -0,0 +1,49 @@
+use std::time::Duration;
+
+use spacetimedb::{ReducerContext, Table};
+
+use crate::services::day_night;
+use crate::tables::DayNightAgentSchedule;
+use crate::tables::agent_schedule::day_night_agent_schedule;
+use crate::tables::day_night::day_night_state;
+
+const TICK_INTERVAL: Duration = Duration::from_secs(10);
+
+pub(crate) fn init(ctx: &ReducerContext) {
+    day_night::ensure_state(ctx);
+    if ctx.db.day_night_agent_schedule().iter().next().is_none() {
+        ctx.db.day_night_agent_schedule().insert(DayNightAgentSchedule {
+            scheduled_id: 0,
+            scheduled_at: TICK_INTERVAL.into(),
+        });
+    }
+}
+
+/// Advances the world clock hour and flips between day and night once a
+/// phase has run for `day_night.day_secs` / `day_night.night_secs`.
+#[spacetimedb::reducer]
+pub fn day_night_agent(ctx: &ReducerContext, _timer: DayNightAgentSchedule) -> Result<(), String> {
+    if ctx.sender != ctx.identity() {
+        return Err("day_night_agent is scheduler-only".to_string());
+    }
+
+    let Some(mut state) = day_night::state(ctx) else {
+        day_night::ensure_state(ctx);
+        return Ok(());
+    };
+    let mut elapsed = ctx.timestamp.duration_since(state.phase_started_at).unwrap_or_default();
+    if elapsed >= day_night::phase_duration(ctx, state.is_day) {
+        state.is_day = !state.is_day;
+        if state.is_day {
+            state.cycle_number += 1;
+        }
+        state.phase_started_at = ctx.timestamp;
+        elapsed = Duration::ZERO;
+        log::info!("day_night: cycle={} is_day={}", state.cycle_number, state.is_day);
+    }
+    let duration = day_night::phase_duration(ctx, state.is_day);
+    state.hour = day_night::hour_in_phase(state.is_day, elapsed, duration);
+    state.updated_at = ctx.timestamp;
+    ctx.db.day_night_state().id().update(state);
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use spacetimedb::ReducerContext;
+
+use crate::services::empires;
+use crate::tables::EmpireSiegeTimer;
+use crate::tables::empire::{empire_node_siege_state, empire_node_state, empire_siege_timer, empire_state};
+
+const SIEGE_DRAIN_PER_TICK: u32 = 10;
+
+/// Siege countdown: every tick burns attacker supplies and node energy in equal
+/// measure until one side runs dry.
+#[spacetimedb::reducer]
+pub fn empire_siege_tick(ctx: &ReducerContext, timer: EmpireSiegeTimer) -> Result<(), String> {
+    if ctx.sender != ctx.identity() {
+        return Err("empire_siege_tick is scheduler-only".to_string());
+    }
+
+    let Some(mut siege) = ctx.db.empire_node_siege_state().siege_id().find(timer.siege_id.clone()) else {
+        ctx.db.empire_siege_timer().scheduled_id().delete(timer.scheduled_id);
+        return Ok(());
+    };
+    if siege.phase != 0 {
+        ctx.db.empire_siege_timer().scheduled_id().delete(timer.scheduled_id);
+        return Ok(());
+    }
+
+    let Some(mut node) = ctx.db.empire_node_state().node_id().find(siege.node_id) else {
+        siege.phase = 2;
+        siege.updated_at = ctx.timestamp;
+        ctx.db.empire_node_siege_state().siege_id().update(siege);
+        ctx.db.empire_siege_timer().scheduled_id().delete(timer.scheduled_id);
+        return Ok(());
+    };
+
+    siege.attacker_supplies = siege.attacker_supplies.saturating_sub(SIEGE_DRAIN_PER_TICK);
+    node.energy = node.energy.saturating_sub(SIEGE_DRAIN_PER_TICK);
+    siege.updated_at = ctx.timestamp;
+    node.updated_at = ctx.timestamp;
+
+    if node.energy == 0 {
+        siege.phase = 1;
+        let attacker_emperor = ctx
+            .db
+            .empire_state()
+            .empire_id()
+            .find(siege.attacker_empire_id)
+            .map(|e| e.emperor_identity);
+        match attacker_emperor {
+            Some(emperor) => empires::transfer_node(ctx, node, siege.attacker_empire_id, emperor),
+            None => {
+                node.active = false;
+                ctx.db.empire_node_state().node_id().update(node);
+            }
+        }
+    } else {
+        if siege.attacker_supplies == 0 {
+            siege.phase = 2;
+        }
+        ctx.db.empire_node_state().node_id().update(node);
+    }
+
+    if siege.phase != 0 {
+        log::info!("siege resolved: siege_id={} phase={}", siege.siege_id, siege.phase);
+        ctx.db.empire_siege_timer().scheduled_id().delete(timer.scheduled_id);
+    }
+    ctx.db.empire_node_siege_state().siege_id().update(siege);
+
+    Ok(())
+}
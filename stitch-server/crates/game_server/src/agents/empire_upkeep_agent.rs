@@ -0,0 +1,44 @@
+use std::time::Duration;
+
+use spacetimedb::{ReducerContext, Table};
+
+use crate::tables::EmpireUpkeepAgentSchedule;
+use crate::tables::agent_schedule::empire_upkeep_agent_schedule;
+use crate::tables::empire::{empire_node_siege_state, empire_node_state};
+
+const TICK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+pub(crate) fn init(ctx: &ReducerContext) {
+    if ctx.db.empire_upkeep_agent_schedule().iter().next().is_none() {
+        ctx.db.empire_upkeep_agent_schedule().insert(EmpireUpkeepAgentSchedule {
+            scheduled_id: 0,
+            scheduled_at: TICK_INTERVAL.into(),
+        });
+    }
+}
+
+#[spacetimedb::reducer]
+pub fn empire_upkeep_agent(ctx: &ReducerContext, _timer: EmpireUpkeepAgentSchedule) -> Result<(), String> {
+    if ctx.sender != ctx.identity() {
+        return Err("empire_upkeep_agent is scheduler-only".to_string());
+    }
+
+    let nodes: Vec<_> = ctx.db.empire_node_state().iter().collect();
+    for mut node in nodes {
+        // Sieges drain energy on their own timer.
+        if ctx
+            .db
+            .empire_node_siege_state()
+            .iter()
+            .any(|s| s.node_id == node.node_id && s.phase == 0)
+        {
+            continue;
+        }
+        node.energy = node.energy.saturating_sub(node.upkeep);
+        node.active = node.energy > 0;
+        node.updated_at = ctx.timestamp;
+        ctx.db.empire_node_state().node_id().update(node);
+    }
+
+    Ok(())
+}
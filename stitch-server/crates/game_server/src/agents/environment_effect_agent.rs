@@ -0,0 +1,84 @@
+use std::time::Duration;
+
+use spacetimedb::{ReducerContext, Table};
+
+use crate::services::{buffs, environment, health, stats, terrain};
+use crate::tables::{EnvironmentEffectAgentSchedule, EnvironmentEffectDef, SessionState};
+use crate::tables::agent_schedule::environment_effect_agent_schedule;
+use crate::tables::buff::{buff_state, environment_effect_def};
+use crate::tables::session_state::session_state;
+use crate::tables::transform_state::transform_state;
+
+const TICK_INTERVAL: Duration = Duration::from_secs(5);
+
+pub(crate) fn init(ctx: &ReducerContext) {
+    if ctx.db.environment_effect_agent_schedule().iter().next().is_none() {
+        ctx.db.environment_effect_agent_schedule().insert(EnvironmentEffectAgentSchedule {
+            scheduled_id: 0,
+            scheduled_at: TICK_INTERVAL.into(),
+        });
+    }
+}
+
+/// Checks every online player's biome against hazard defs. Unresisted hazards
+/// keep their debuff applied and deal damage each tick; leaving the biome or
+/// gaining enough resistance lifts the environment-sourced debuff.
+#[spacetimedb::reducer]
+pub fn environment_effect_agent(
+    ctx: &ReducerContext,
+    _timer: EnvironmentEffectAgentSchedule,
+) -> Result<(), String> {
+    if ctx.sender != ctx.identity() {
+        return Err("environment_effect_agent is scheduler-only".to_string());
+    }
+
+    let defs: Vec<EnvironmentEffectDef> = ctx.db.environment_effect_def().iter().collect();
+    if defs.is_empty() {
+        return Ok(());
+    }
+    let sessions: Vec<SessionState> = ctx.db.session_state().iter().collect();
+
+    for session in sessions {
+        let identity = session.identity;
+        if health::is_downed(ctx, identity) {
+            continue;
+        }
+        let Some(transform) = ctx.db.transform_state().entity_id().find(identity) else {
+            continue;
+        };
+        let hex = terrain::hex_at(transform.position[0], transform.position[2]);
+        let hazards = terrain::TerrainSampler::for_region(ctx, session.region_id)
+            .cell(hex)
+            .map(|cell| environment::biome_hazards(cell.biome_id))
+            .unwrap_or(&[]);
+        let current = stats::get(ctx, identity);
+
+        for def in &defs {
+            let resistance = stats::stat_value(&current, def.resistance_stat);
+            let resisted = resistance >= def.resistance_threshold as f32;
+            if !hazards.contains(&def.hazard_tag) || resisted {
+                let from_environment = ctx
+                    .db
+                    .buff_state()
+                    .buff_key()
+                    .find(buffs::buff_key(identity, def.effect_id))
+                    .is_some_and(|b| b.source == buffs::SOURCE_ENVIRONMENT);
+                if from_environment {
+                    buffs::remove(ctx, identity, def.effect_id);
+                }
+                continue;
+            }
+
+            if let Err(err) = buffs::apply(ctx, identity, def.effect_id, buffs::SOURCE_ENVIRONMENT) {
+                log::debug!(
+                    "environment effect skipped: identity={identity} effect={} reason={err}",
+                    def.name
+                );
+            }
+            if def.damage_per_tick > 0 {
+                health::damage(ctx, identity, def.damage_per_tick);
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,104 @@
+use std::time::Duration;
+
+use spacetimedb::{ReducerContext, Table};
+
+use crate::services::housing;
+use crate::tables::{HousingCollapseAgentSchedule, HousingRegenTimer};
+use crate::tables::agent_schedule::housing_collapse_agent_schedule;
+use crate::tables::building_state::building_state;
+use crate::tables::housing::{dimension_desc, dimension_network, housing_regen_timer, housing_state};
+
+const TICK_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+const ABANDONED_AFTER: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+const REGEN_DELAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+pub(crate) fn init(ctx: &ReducerContext) {
+    if ctx.db.housing_collapse_agent_schedule().iter().next().is_none() {
+        ctx.db.housing_collapse_agent_schedule().insert(HousingCollapseAgentSchedule {
+            scheduled_id: 0,
+            scheduled_at: TICK_INTERVAL.into(),
+        });
+    }
+}
+
+/// Collapses interiors whose owner has not visited for `ABANDONED_AFTER` and
+/// schedules their regeneration.
+#[spacetimedb::reducer]
+pub fn housing_collapse_agent(ctx: &ReducerContext, _timer: HousingCollapseAgentSchedule) -> Result<(), String> {
+    if ctx.sender != ctx.identity() {
+        return Err("housing_collapse_agent is scheduler-only".to_string());
+    }
+
+    let houses: Vec<_> = ctx.db.housing_state().iter().collect();
+    for house in houses {
+        let abandoned = ctx
+            .timestamp
+            .duration_since(house.last_visited_at)
+            .is_some_and(|idle| idle >= ABANDONED_AFTER);
+        if !abandoned || housing::occupant_count(ctx, house.dimension_id) > 0 {
+            continue;
+        }
+
+        let Some(mut dimension) = ctx.db.dimension_desc().dimension_id().find(house.dimension_id) else {
+            continue;
+        };
+        if dimension.collapse_at.is_some() {
+            continue;
+        }
+        dimension.collapse_at = Some(ctx.timestamp);
+        ctx.db.dimension_desc().dimension_id().update(dimension);
+
+        let interior_region = housing::interior_region_id(house.dimension_id);
+        let interior_buildings: Vec<_> = ctx
+            .db
+            .building_state()
+            .iter()
+            .filter(|b| b.region_id == interior_region && b.state != 2)
+            .collect();
+        for mut building in interior_buildings {
+            building.state = 2;
+            building.updated_at = ctx.timestamp;
+            ctx.db.building_state().entity_id().update(building);
+        }
+
+        let respawn_at = ctx.timestamp + REGEN_DELAY;
+        if let Some(mut network) = ctx.db.dimension_network().network_id().find(house.network_id) {
+            network.collapse_respawn_at = Some(respawn_at);
+            ctx.db.dimension_network().network_id().update(network);
+        }
+        ctx.db.housing_regen_timer().insert(HousingRegenTimer {
+            scheduled_id: 0,
+            scheduled_at: respawn_at.into(),
+            network_id: house.network_id,
+        });
+        log::info!("housing interior collapsed: owner={} dimension_id={}", house.owner_identity, house.dimension_id);
+    }
+
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn housing_regen(ctx: &ReducerContext, timer: HousingRegenTimer) -> Result<(), String> {
+    if ctx.sender != ctx.identity() {
+        return Err("housing_regen is scheduler-only".to_string());
+    }
+
+    let Some(mut network) = ctx.db.dimension_network().network_id().find(timer.network_id) else {
+        return Ok(());
+    };
+    let Some(mut house) = ctx.db.housing_state().iter().find(|h| h.network_id == timer.network_id) else {
+        return Ok(());
+    };
+
+    ctx.db.dimension_desc().dimension_id().delete(house.dimension_id);
+    house.dimension_id = housing::spawn_dimension(ctx, network.network_id);
+    house.is_empty = true;
+    house.last_visited_at = ctx.timestamp;
+    house.updated_at = ctx.timestamp;
+    ctx.db.housing_state().owner_identity().update(house);
+
+    network.collapse_respawn_at = None;
+    ctx.db.dimension_network().network_id().update(network);
+
+    Ok(())
+}
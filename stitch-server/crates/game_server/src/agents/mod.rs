@@ -1 +1,38 @@
 //! Scheduled reducers and background agents live here.
+
+use spacetimedb::ReducerContext;
+
+pub mod buff_expiry_agent;
+pub mod chat_cleanup_agent;
+pub mod claim_ownership_agent;
+pub mod claim_upkeep_agent;
+pub mod day_night_agent;
+pub mod empire_siege_agent;
+pub mod empire_upkeep_agent;
+pub mod environment_effect_agent;
+pub mod housing_collapse_agent;
+pub mod npc_ai_agent;
+pub mod npc_conversation_agent;
+pub mod npc_lease_agent;
+pub mod npc_mover_agent;
+pub mod npc_restock_agent;
+pub mod player_regen_agent;
+pub mod resource_regen_agent;
+
+pub(crate) fn init_agents(ctx: &ReducerContext) {
+    buff_expiry_agent::init(ctx);
+    chat_cleanup_agent::init(ctx);
+    claim_ownership_agent::init(ctx);
+    claim_upkeep_agent::init(ctx);
+    day_night_agent::init(ctx);
+    empire_upkeep_agent::init(ctx);
+    environment_effect_agent::init(ctx);
+    housing_collapse_agent::init(ctx);
+    npc_ai_agent::init(ctx);
+    npc_conversation_agent::init(ctx);
+    npc_lease_agent::init(ctx);
+    npc_mover_agent::init(ctx);
+    npc_restock_agent::init(ctx);
+    player_regen_agent::init(ctx);
+    resource_regen_agent::init(ctx);
+}
@@ -0,0 +1,40 @@
+use std::time::Duration;
+
+use spacetimedb::{ReducerContext, Table};
+
+use crate::services::{day_night, npc_schedule};
+use crate::tables::{NpcAiAgentSchedule, NpcState};
+use crate::tables::agent_schedule::npc_ai_agent_schedule;
+use crate::tables::npc_quest::npc_state;
+
+const TICK_INTERVAL: Duration = Duration::from_secs(15);
+
+pub(crate) fn init(ctx: &ReducerContext) {
+    if ctx.db.npc_ai_agent_schedule().iter().next().is_none() {
+        ctx.db.npc_ai_agent_schedule().insert(NpcAiAgentSchedule {
+            scheduled_id: 0,
+            scheduled_at: TICK_INTERVAL.into(),
+        });
+    }
+}
+
+/// Moves routine npcs onto the `npc_action_schedule` entry for the current
+/// clock hour; `npc_mover_agent` walks the resulting paths.
+#[spacetimedb::reducer]
+pub fn npc_ai_agent(ctx: &ReducerContext, _timer: NpcAiAgentSchedule) -> Result<(), String> {
+    if ctx.sender != ctx.identity() {
+        return Err("npc_ai_agent is scheduler-only".to_string());
+    }
+
+    let hour = day_night::current_hour(ctx);
+    let routine: Vec<NpcState> = ctx
+        .db
+        .npc_state()
+        .iter()
+        .filter(|n| n.schedule_kind == npc_schedule::SCHEDULE_ROUTINE)
+        .collect();
+    for npc in routine {
+        npc_schedule::advance(ctx, npc, hour);
+    }
+    Ok(())
+}
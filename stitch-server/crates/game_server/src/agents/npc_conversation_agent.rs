@@ -0,0 +1,44 @@
+use std::time::Duration;
+
+use spacetimedb::{ReducerContext, Table};
+
+use crate::services::npc_memory;
+use crate::tables::{NpcConversationAgentSchedule, NpcConversationSession};
+use crate::tables::agent_schedule::npc_conversation_agent_schedule;
+use crate::tables::npc_conversation::npc_conversation_session;
+
+const TICK_INTERVAL: Duration = Duration::from_secs(30);
+
+pub(crate) fn init(ctx: &ReducerContext) {
+    if ctx.db.npc_conversation_agent_schedule().iter().next().is_none() {
+        ctx.db.npc_conversation_agent_schedule().insert(NpcConversationAgentSchedule {
+            scheduled_id: 0,
+            scheduled_at: TICK_INTERVAL.into(),
+        });
+    }
+}
+
+/// Times out conversations idle for `SESSION_IDLE_SECS`, folding their turns
+/// into the npc's memory of the player.
+#[spacetimedb::reducer]
+pub fn npc_conversation_agent(
+    ctx: &ReducerContext,
+    _timer: NpcConversationAgentSchedule,
+) -> Result<(), String> {
+    if ctx.sender != ctx.identity() {
+        return Err("npc_conversation_agent is scheduler-only".to_string());
+    }
+
+    let idle = Duration::from_secs(npc_memory::SESSION_IDLE_SECS);
+    let stale: Vec<NpcConversationSession> = ctx
+        .db
+        .npc_conversation_session()
+        .iter()
+        .filter(|s| s.status == npc_memory::SESSION_ACTIVE)
+        .filter(|s| ctx.timestamp.duration_since(s.last_active_at).is_some_and(|d| d >= idle))
+        .collect();
+    for session in stale {
+        npc_memory::close_session(ctx, session, npc_memory::SESSION_TIMED_OUT);
+    }
+    Ok(())
+}
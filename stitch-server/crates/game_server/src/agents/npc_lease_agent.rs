@@ -0,0 +1,82 @@
+use std::time::Duration;
+
+use spacetimedb::{ReducerContext, Table};
+
+use crate::services::{balance, npc_agent, npc_cost};
+use crate::tables::{NpcActionRequest, NpcLeaseAgentSchedule};
+use crate::tables::agent_schedule::npc_lease_agent_schedule;
+use crate::tables::npc_action::{npc_action_request, npc_action_result};
+
+const TICK_INTERVAL: Duration = Duration::from_secs(5);
+
+pub(crate) fn init(ctx: &ReducerContext) {
+    if ctx.db.npc_lease_agent_schedule().iter().next().is_none() {
+        ctx.db.npc_lease_agent_schedule().insert(NpcLeaseAgentSchedule {
+            scheduled_id: 0,
+            scheduled_at: TICK_INTERVAL.into(),
+        });
+    }
+}
+
+/// Returns requests whose worker lease ran out to the queue, failing them
+/// with the scripted fallback after the last attempt, fails requests no
+/// worker picked up in time, and drops expired response cache entries and
+/// finished requests past their retention.
+#[spacetimedb::reducer]
+pub fn npc_lease_agent(ctx: &ReducerContext, _timer: NpcLeaseAgentSchedule) -> Result<(), String> {
+    if ctx.sender != ctx.identity() {
+        return Err("npc_lease_agent is scheduler-only".to_string());
+    }
+
+    let expired: Vec<NpcActionRequest> = ctx
+        .db
+        .npc_action_request()
+        .status()
+        .filter(npc_agent::REQUEST_LEASED)
+        .filter(|r| r.lease_expires_at.is_some_and(|t| t <= ctx.timestamp))
+        .collect();
+    for request in expired {
+        npc_agent::release(ctx, request, "lease expired");
+    }
+    fail_unclaimed(ctx);
+    npc_cost::purge_expired_cache(ctx);
+    purge_finished(ctx);
+    Ok(())
+}
+
+/// Fails pending requests that waited longer than `npc.pending_ttl_secs`
+/// without a worker claiming them, so the player still gets the fallback
+/// reply when no worker is online.
+fn fail_unclaimed(ctx: &ReducerContext) {
+    let ttl = Duration::from_secs(balance::get_u64(ctx, balance::NPC_PENDING_TTL_SECS, 60));
+    let stale: Vec<NpcActionRequest> = ctx
+        .db
+        .npc_action_request()
+        .status()
+        .filter(npc_agent::REQUEST_PENDING)
+        .filter(|r| ctx.timestamp.duration_since(r.updated_at).is_some_and(|age| age >= ttl))
+        .collect();
+    for request in stale {
+        log::info!("npc action failed: request_id={} reason=no worker claimed it", request.request_id);
+        let reply = npc_agent::FALLBACK_REPLY.to_string();
+        let detail = "no worker claimed the request".to_string();
+        npc_agent::finish(ctx, request, npc_agent::RESULT_FAILED, reply, detail);
+    }
+}
+
+/// Deletes done and failed requests, with their results, once they are older
+/// than `npc.result_retention_hours`.
+fn purge_finished(ctx: &ReducerContext) {
+    let hours = balance::get_u64(ctx, balance::NPC_RESULT_RETENTION_HOURS, 24);
+    let retention = Duration::from_secs(hours * 3600);
+    let stale: Vec<u64> = [npc_agent::REQUEST_DONE, npc_agent::REQUEST_FAILED]
+        .into_iter()
+        .flat_map(|status| ctx.db.npc_action_request().status().filter(status))
+        .filter(|r| ctx.timestamp.duration_since(r.updated_at).is_some_and(|age| age >= retention))
+        .map(|r| r.request_id)
+        .collect();
+    for request_id in stale {
+        ctx.db.npc_action_result().request_id().delete(request_id);
+        ctx.db.npc_action_request().request_id().delete(request_id);
+    }
+}
@@ -0,0 +1,109 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use spacetimedb::{ReducerContext, Table};
+
+use crate::services::pathfinding::{
+    self, NavGrid, PATH_STATUS_ARRIVED, PATH_STATUS_FAILED, PATH_STATUS_MOVING,
+};
+use crate::services::terrain;
+use crate::tables::{NavPath, NavWaypoint, NpcMoverAgentSchedule, NpcState};
+use crate::tables::agent_schedule::npc_mover_agent_schedule;
+use crate::tables::navigation::{nav_path, nav_waypoint};
+use crate::tables::npc_quest::npc_state;
+use crate::utils::hex::HexCoordinates;
+
+const TICK_INTERVAL: Duration = Duration::from_secs(1);
+const STEPS_PER_TICK: u32 = 2;
+
+pub(crate) fn init(ctx: &ReducerContext) {
+    if ctx.db.npc_mover_agent_schedule().iter().next().is_none() {
+        ctx.db.npc_mover_agent_schedule().insert(NpcMoverAgentSchedule {
+            scheduled_id: 0,
+            scheduled_at: TICK_INTERVAL.into(),
+        });
+    }
+}
+
+#[spacetimedb::reducer]
+pub fn npc_mover_agent(ctx: &ReducerContext, _timer: NpcMoverAgentSchedule) -> Result<(), String> {
+    if ctx.sender != ctx.identity() {
+        return Err("npc_mover_agent is scheduler-only".to_string());
+    }
+
+    let moving: Vec<NavPath> = ctx
+        .db
+        .nav_path()
+        .iter()
+        .filter(|p| p.status == PATH_STATUS_MOVING)
+        .collect();
+    // One nav grid per region per tick; NPC moves do not change what it caches.
+    let mut grids: HashMap<u64, NavGrid> = HashMap::new();
+    for path in moving {
+        let grid = grids.entry(path.region_id).or_insert_with(|| NavGrid::load(ctx, path.region_id));
+        advance(ctx, grid, path);
+    }
+    Ok(())
+}
+
+fn advance(ctx: &ReducerContext, grid: &mut NavGrid, mut path: NavPath) {
+    let Some(npc) = ctx.db.npc_state().npc_id().find(path.npc_id) else {
+        pathfinding::clear_npc_path(ctx, path.npc_id);
+        return;
+    };
+
+    let mut waypoints: Vec<NavWaypoint> = ctx
+        .db
+        .nav_waypoint()
+        .path_id()
+        .filter(path.path_id)
+        .filter(|w| w.seq >= path.next_seq)
+        .collect();
+    waypoints.sort_by_key(|w| w.seq);
+
+    let mut current = terrain::hex_at(npc.pos_x, npc.pos_z);
+    let mut steps = 0;
+    for waypoint in waypoints.iter().take(STEPS_PER_TICK as usize) {
+        let next = HexCoordinates::new(waypoint.hex_x, waypoint.hex_z);
+        if grid.step_cost(current, next).is_none() {
+            // The world changed under the path (new building/obstacle): re-plan once.
+            let goal = HexCoordinates::new(path.target_x, path.target_z);
+            let limit = pathfinding::DEFAULT_NODE_LIMIT;
+            match pathfinding::find_region_path(ctx, path.region_id, current, goal, limit) {
+                Ok(replanned) => {
+                    pathfinding::store_npc_path(ctx, path.npc_id, path.region_id, &replanned);
+                }
+                Err(reason) => {
+                    log::warn!("npc path failed: npc_id={} reason={reason}", path.npc_id);
+                    pathfinding::clear_waypoints(ctx, path.path_id);
+                    path.status = PATH_STATUS_FAILED;
+                    path.updated_at = ctx.timestamp;
+                    ctx.db.nav_path().path_id().update(path);
+                }
+            }
+            save_position(ctx, npc, current, steps);
+            return;
+        }
+        current = next;
+        steps += 1;
+    }
+
+    save_position(ctx, npc, current, steps);
+    path.next_seq += steps;
+    path.updated_at = ctx.timestamp;
+    if path.next_seq >= path.waypoint_count {
+        pathfinding::clear_waypoints(ctx, path.path_id);
+        path.status = PATH_STATUS_ARRIVED;
+    }
+    ctx.db.nav_path().path_id().update(path);
+}
+
+fn save_position(ctx: &ReducerContext, mut npc: NpcState, hex: HexCoordinates, steps: u32) {
+    if steps == 0 {
+        return;
+    }
+    npc.pos_x = hex.x as f32;
+    npc.pos_z = hex.z as f32;
+    npc.updated_at = ctx.timestamp;
+    ctx.db.npc_state().npc_id().update(npc);
+}
@@ -0,0 +1,27 @@
+use std::time::Duration;
+
+use spacetimedb::{ReducerContext, Table};
+
+use crate::services::npc_shop;
+use crate::tables::NpcRestockAgentSchedule;
+use crate::tables::agent_schedule::npc_restock_agent_schedule;
+
+const TICK_INTERVAL: Duration = Duration::from_secs(300);
+
+pub(crate) fn init(ctx: &ReducerContext) {
+    if ctx.db.npc_restock_agent_schedule().iter().next().is_none() {
+        ctx.db.npc_restock_agent_schedule().insert(NpcRestockAgentSchedule {
+            scheduled_id: 0,
+            scheduled_at: TICK_INTERVAL.into(),
+        });
+    }
+}
+
+#[spacetimedb::reducer]
+pub fn npc_restock_agent(ctx: &ReducerContext, _timer: NpcRestockAgentSchedule) -> Result<(), String> {
+    if ctx.sender != ctx.identity() {
+        return Err("npc_restock_agent is scheduler-only".to_string());
+    }
+    npc_shop::restock(ctx);
+    Ok(())
+}
@@ -0,0 +1,126 @@
+use std::time::Duration;
+
+use spacetimedb::{ReducerContext, Table, Timestamp};
+
+use crate::services::{balance, buffs, health, stats};
+use crate::tables::{PlayerRegenAgentSchedule, SessionState};
+use crate::tables::agent_schedule::player_regen_agent_schedule;
+use crate::tables::buff::buff_state;
+use crate::tables::combat::combat_state;
+use crate::tables::resource_state::resource_state;
+use crate::tables::session_state::session_state;
+
+const TICK_INTERVAL: Duration = Duration::from_secs(1);
+
+pub(crate) fn init(ctx: &ReducerContext) {
+    if ctx.db.player_regen_agent_schedule().iter().next().is_none() {
+        ctx.db.player_regen_agent_schedule().insert(PlayerRegenAgentSchedule {
+            scheduled_id: 0,
+            scheduled_at: TICK_INTERVAL.into(),
+        });
+    }
+}
+
+struct RegenParams {
+    passive_hp: i32,
+    passive_stamina: i32,
+    passive_delay: Duration,
+    satiation_decay: i32,
+    starving_damage: i32,
+    combat_exit: Duration,
+    respawn_after: Duration,
+}
+
+impl RegenParams {
+    fn load(ctx: &ReducerContext) -> Self {
+        Self {
+            passive_hp: balance::get_u64(ctx, balance::PASSIVE_HP_REGEN, 3) as i32,
+            passive_stamina: balance::get_u64(ctx, balance::PASSIVE_STAMINA_REGEN, 5) as i32,
+            passive_delay: Duration::from_secs(balance::get_u64(ctx, balance::PASSIVE_REGEN_DELAY_SECS, 10)),
+            satiation_decay: balance::get_u64(ctx, balance::SATIATION_DECAY, 1) as i32,
+            starving_damage: balance::get_u64(ctx, balance::STARVING_DAMAGE, 1) as i32,
+            combat_exit: Duration::from_secs(balance::get_u64(ctx, balance::COMBAT_EXIT_SECS, 15)),
+            respawn_after: Duration::from_secs(balance::get_u64(ctx, balance::RESPAWN_SECS, 60)),
+        }
+    }
+}
+
+fn elapsed_at_least(ctx: &ReducerContext, since: Timestamp, wait: Duration) -> bool {
+    ctx.timestamp.duration_since(since).is_some_and(|d| d >= wait)
+}
+
+/// One batched pass over online players: regenerates hp and stamina (passive
+/// bonuses only after the delay, and hp only out of combat), drains satiation,
+/// and keeps the starving debuff in sync with an empty stomach. Starvation
+/// wears hp down to 1 but never downs anyone; players downed in combat are
+/// respawned once `regen.respawn_secs` have passed.
+#[spacetimedb::reducer]
+pub fn player_regen_agent(ctx: &ReducerContext, _timer: PlayerRegenAgentSchedule) -> Result<(), String> {
+    if ctx.sender != ctx.identity() {
+        return Err("player_regen_agent is scheduler-only".to_string());
+    }
+
+    let params = RegenParams::load(ctx);
+    let sessions: Vec<SessionState> = ctx.db.session_state().iter().collect();
+    for session in sessions {
+        let identity = session.identity;
+        let mut state = health::ensure(ctx, identity);
+        if state.hp == 0 {
+            if elapsed_at_least(ctx, state.last_damage_at, params.respawn_after) {
+                if let Err(err) = health::respawn(ctx, identity) {
+                    log::warn!("respawn failed: identity={identity} reason={err}");
+                }
+            }
+            continue;
+        }
+        let current = stats::get(ctx, identity);
+
+        let mut in_combat = false;
+        if let Some(mut combat) = ctx.db.combat_state().identity().find(identity) {
+            if combat.in_combat && elapsed_at_least(ctx, combat.updated_at, params.combat_exit) {
+                combat.in_combat = false;
+                combat.updated_at = ctx.timestamp;
+                ctx.db.combat_state().identity().update(combat);
+            } else {
+                in_combat = combat.in_combat;
+            }
+        }
+
+        let starving = state.satiation == 0;
+        if starving {
+            state.hp = (state.hp - params.starving_damage).max(1);
+        } else {
+            let mut hp_regen = current.hp_regen;
+            if !in_combat && elapsed_at_least(ctx, state.last_damage_at, params.passive_delay) {
+                hp_regen += params.passive_hp;
+            }
+            state.hp = (state.hp + hp_regen).min(current.max_hp);
+        }
+
+        let mut stamina_regen = current.stamina_regen;
+        if elapsed_at_least(ctx, state.last_stamina_use_at, params.passive_delay) {
+            stamina_regen += params.passive_stamina;
+        }
+        state.stamina = (state.stamina + stamina_regen).min(current.max_stamina);
+        state.satiation = (state.satiation - params.satiation_decay).max(0);
+        state.updated_at = ctx.timestamp;
+
+        let now_starving = state.satiation == 0;
+        ctx.db.resource_state().identity().update(state);
+
+        let has_debuff = ctx
+            .db
+            .buff_state()
+            .buff_key()
+            .find(buffs::buff_key(identity, health::STARVING_EFFECT_ID))
+            .is_some();
+        if now_starving && !has_debuff {
+            if let Err(err) = buffs::apply(ctx, identity, health::STARVING_EFFECT_ID, buffs::SOURCE_HUNGER) {
+                log::warn!("starving debuff not applied: identity={identity} reason={err}");
+            }
+        } else if !now_starving && has_debuff {
+            buffs::remove(ctx, identity, health::STARVING_EFFECT_ID);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use std::time::Duration;
+
+use spacetimedb::{ReducerContext, Table};
+
+use crate::services::resources;
+use crate::tables::{ResourceNode, ResourceRegenAgentSchedule};
+use crate::tables::agent_schedule::resource_regen_agent_schedule;
+use crate::tables::resource_node::{resource_def, resource_node};
+
+const TICK_INTERVAL: Duration = Duration::from_secs(60);
+
+pub(crate) fn init(ctx: &ReducerContext) {
+    if ctx.db.resource_regen_agent_schedule().iter().next().is_none() {
+        ctx.db.resource_regen_agent_schedule().insert(ResourceRegenAgentSchedule {
+            scheduled_id: 0,
+            scheduled_at: TICK_INTERVAL.into(),
+        });
+    }
+}
+
+/// Respawns depleted nodes once their timer elapses, unless the chunk is
+/// already at the def's density cap, in which case the node waits another cycle.
+#[spacetimedb::reducer]
+pub fn resource_regen_agent(ctx: &ReducerContext, _timer: ResourceRegenAgentSchedule) -> Result<(), String> {
+    if ctx.sender != ctx.identity() {
+        return Err("resource_regen_agent is scheduler-only".to_string());
+    }
+
+    let due: Vec<ResourceNode> = ctx
+        .db
+        .resource_node()
+        .iter()
+        .filter(|n| resources::is_depleted(n) && n.respawn_at.is_some_and(|t| t <= ctx.timestamp))
+        .collect();
+
+    let mut respawned = 0u32;
+    for mut node in due {
+        let Some(def) = ctx.db.resource_def().resource_def_id().find(node.resource_def_id) else {
+            ctx.db.resource_node().entity_id().delete(node.entity_id);
+            continue;
+        };
+        if resources::active_nodes_in_chunk(ctx, node.chunk_id, def.resource_def_id) >= def.max_per_chunk {
+            node.respawn_at = Some(ctx.timestamp + Duration::from_secs(def.respawn_secs));
+        } else {
+            node.health = def.max_health.max(1);
+            node.depleted_at = None;
+            node.respawn_at = None;
+            respawned += 1;
+        }
+        ctx.db.resource_node().entity_id().update(node);
+    }
+
+    if respawned > 0 {
+        log::info!("resource_regen_agent respawned {respawned} nodes");
+    }
+    Ok(())
+}
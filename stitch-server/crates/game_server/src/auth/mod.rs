@@ -1,5 +1,6 @@
 use spacetimedb::{ReducerContext, Table};
 
+use crate::services::terrain;
 use crate::tables::{Account, PlayerState, TransformState};
 use crate::tables::account::account;
 use crate::tables::player_state::player_state;
@@ -34,7 +35,7 @@ pub(crate) fn ensure_transform_exists(ctx: &ReducerContext, region_id: u64) {
         ctx.db.transform_state().insert(TransformState {
             entity_id: ctx.sender,
             region_id,
-            position: vec![0.0, 0.0, 0.0],
+            position: terrain::spawn_position(ctx, region_id),
             rotation: vec![0.0, 0.0, 0.0, 1.0],
             updated_at: ctx.timestamp,
         });
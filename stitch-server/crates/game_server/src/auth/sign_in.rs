@@ -7,6 +7,7 @@ use crate::tables::session_state::session_state;
 #[spacetimedb::reducer]
 pub fn sign_in(ctx: &ReducerContext, region_id: u64) -> Result<(), String> {
     super::ensure_account_exists(ctx);
+    crate::services::moderation::lift_expired_ban(ctx, ctx.sender);
 
     let account = ctx
         .db
@@ -34,5 +35,6 @@ pub fn sign_in(ctx: &ReducerContext, region_id: u64) -> Result<(), String> {
 
     super::ensure_player_state_exists(ctx, "new-player".to_string());
     super::ensure_transform_exists(ctx, region_id);
+    crate::services::claims::touch_member_activity(ctx, ctx.sender);
     Ok(())
 }
@@ -16,5 +16,6 @@ pub fn sign_out(ctx: &ReducerContext) -> Result<(), String> {
     }
 
     ctx.db.session_state().identity().delete(ctx.sender);
+    crate::services::claims::touch_member_activity(ctx, ctx.sender);
     Ok(())
 }
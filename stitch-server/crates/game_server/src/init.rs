@@ -1,6 +1,7 @@
 use spacetimedb::ReducerContext;
 
 #[spacetimedb::reducer(init)]
-pub fn init(_ctx: &ReducerContext) {
+pub fn init(ctx: &ReducerContext) {
+    crate::agents::init_agents(ctx);
     log::info!("stitch-server module initialized");
 }
@@ -13,34 +13,619 @@ pub mod tables;
 pub mod utils;
 pub mod validation;
 
-use tables::ItemDef;
-use tables::item_def::item_def;
+use services::{abilities, achievements, environment, equipment, npc_policy, npc_schedule, quests, stats};
+use services::world_gen::{BIOME_DESERT, BIOME_FOREST, BIOME_GRASSLAND, BIOME_MOUNTAIN, BIOME_SWAMP};
+use tables::{
+    AbilityDef, AchievementDef, AchievementRequirement, AchievementReward, BuildingDef, ClaimTechDef,
+    EnvironmentEffectDef, EquipmentDef, FoodDef, ItemDef, ItemListDef, ItemListEntry, NpcRoleItemRule,
+    NpcState, QuestChainDef, QuestCondition, QuestReward, QuestStageDef, RecipeDef, RecipeItem, ResourceDef,
+    SkillDef, StatModifier, StatusEffect, ToolDef,
+};
+use tables::ability::ability_def;
+use tables::achievement::achievement_def;
+use tables::buff::{environment_effect_def, status_effect};
+use tables::building_state::building_def;
+use tables::claim_tech::claim_tech_def;
+use tables::crafting::recipe_def;
+use tables::equipment::equipment_def;
+use tables::item_def::{food_def, item_def, tool_def};
+use tables::item_list::item_list_def;
+use tables::npc_policy::npc_role_item_rule;
+use tables::npc_quest::{npc_state, quest_chain_def, quest_stage_def};
+use tables::resource_node::resource_def;
+use tables::skill::skill_def;
 
 #[spacetimedb::reducer]
 pub fn seed_data(ctx: &ReducerContext) {
-    if ctx.db.item_def().item_def_id().find(1).is_none() {
-        ctx.db.item_def().insert(ItemDef {
-            item_def_id: 1,
-            category: 1,
-            rarity: 1,
-            max_stack: 200,
-            volume: 1,
-        });
-    }
-
-    if ctx.db.item_def().item_def_id().find(2).is_none() {
-        ctx.db.item_def().insert(ItemDef {
-            item_def_id: 2,
-            category: 2,
-            rarity: 1,
-            max_stack: 200,
-            volume: 2,
-        });
+    // (item_def_id, category, rarity, max_stack, volume)
+    let items: [(u64, u8, u8, u32, i32); 15] = [
+        (1, 1, 1, 200, 1), // wood
+        (2, 2, 1, 200, 2), // stone
+        (3, 2, 1, 200, 2), // ore
+        (4, 1, 1, 200, 1), // plant fiber
+        (5, 1, 1, 100, 2), // plank
+        (6, 2, 1, 100, 2), // ingot
+        (10, 3, 1, 1, 5),  // hatchet
+        (11, 3, 1, 1, 5),  // pickaxe
+        (12, 3, 1, 1, 3),  // sickle
+        (20, 4, 1, 1, 6),  // iron sword
+        (21, 5, 1, 1, 8),  // leather jerkin
+        (22, 5, 1, 1, 4),  // traveler boots
+        (23, 5, 1, 1, 6),  // frame pack
+        (30, 6, 1, 50, 1), // wild berries
+        (31, 6, 1, 20, 1), // bread
+    ];
+    for (item_def_id, category, rarity, max_stack, volume) in items {
+        if ctx.db.item_def().item_def_id().find(item_def_id).is_none() {
+            ctx.db.item_def().insert(ItemDef {
+                item_def_id,
+                category,
+                rarity,
+                max_stack,
+                volume,
+            });
+        }
     }
 
+    seed_skills(ctx);
+    seed_buildings(ctx);
+    seed_claim_tech(ctx);
+    seed_resources(ctx);
+    seed_recipes(ctx);
+    seed_equipment(ctx);
+    seed_status_effects(ctx);
+    seed_food(ctx);
+    seed_abilities(ctx);
+    seed_quests(ctx);
+    seed_achievements(ctx);
+    seed_npc_role_items(ctx);
+    seed_npcs(ctx);
+
     log::info!("seed_data complete");
 }
 
+fn seed_skills(ctx: &ReducerContext) {
+    let skills: [(u32, &str, &str, u32); 7] = [
+        (1, "Forestry", "gathering", 100),
+        (2, "Mining", "gathering", 100),
+        (3, "Foraging", "gathering", 100),
+        (4, "Carpentry", "crafting", 100),
+        (5, "Smithing", "crafting", 100),
+        (6, "Construction", "building", 100),
+        (7, "Combat", "combat", 100),
+    ];
+    for (skill_id, name, category, max_level) in skills {
+        if ctx.db.skill_def().skill_id().find(skill_id).is_none() {
+            ctx.db.skill_def().insert(SkillDef {
+                skill_id,
+                name: name.to_string(),
+                category: category.to_string(),
+                max_level,
+            });
+        }
+    }
+}
+
+fn seed_buildings(ctx: &ReducerContext) {
+    // (building_type, name, skill_id, skill_level, required_item_def_id, required_item_qty,
+    //  build_required)
+    let buildings: [(u32, &str, u32, u32, u64, u32, u32); 4] = [
+        (1, "Carpentry Bench", 6, 0, 1, 20, 10),
+        (2, "Smelter", 6, 3, 2, 30, 20),
+        (10, "Watchtower", 6, 5, 5, 40, 30),
+        (20, "Town Hall", 6, 10, 6, 50, 60),
+    ];
+    for (building_type, name, skill_id, skill_level, item_def_id, item_qty, build_required) in buildings {
+        if ctx.db.building_def().building_type().find(building_type).is_none() {
+            ctx.db.building_def().insert(BuildingDef {
+                building_type,
+                name: name.to_string(),
+                skill_id,
+                skill_level,
+                required_item_def_id: item_def_id,
+                required_item_qty: item_qty,
+                build_required,
+            });
+        }
+    }
+}
+
+fn seed_claim_tech(ctx: &ReducerContext) {
+    // (tech_id, tier, required_tech_id, supply_cost, max_tiles, unlocks_building_type)
+    let defs: [(u64, u32, u64, u32, u32, u32); 4] = [
+        (1, 2, 0, 200, 217, 0),
+        (2, 2, 1, 300, 217, 10),
+        (3, 3, 1, 800, 397, 0),
+        (4, 4, 3, 2000, 631, 20),
+    ];
+    for (tech_id, tier, required_tech_id, supply_cost, max_tiles, unlocks_building_type) in defs {
+        if ctx.db.claim_tech_def().tech_id().find(tech_id).is_none() {
+            ctx.db.claim_tech_def().insert(ClaimTechDef {
+                tech_id,
+                tier,
+                required_tech_id,
+                supply_cost,
+                max_tiles,
+                unlocks_building_type,
+            });
+        }
+    }
+}
+
+fn seed_resources(ctx: &ReducerContext) {
+    // (item_def_id, tool_type, tier, power)
+    let tools: [(u64, u8, u8, u32); 3] = [(10, 1, 1, 25), (11, 2, 1, 25), (12, 3, 1, 10)];
+    for (item_def_id, tool_type, tier, power) in tools {
+        if ctx.db.tool_def().item_def_id().find(item_def_id).is_none() {
+            ctx.db.tool_def().insert(ToolDef {
+                item_def_id,
+                tool_type,
+                tier,
+                power,
+            });
+        }
+    }
+
+    // (item_list_id, item_def_id, min_qty, max_qty, probability)
+    let entries: [(u64, u64, u32, u32, f32); 5] = [
+        (1, 1, 2, 4, 1.0),
+        (2, 2, 1, 3, 1.0),
+        (2, 3, 1, 1, 0.25),
+        (3, 4, 1, 2, 1.0),
+        (3, 30, 1, 3, 0.3),
+    ];
+    for item_list_id in [1u64, 2, 3] {
+        if ctx.db.item_list_def().item_list_id().find(item_list_id).is_none() {
+            ctx.db.item_list_def().insert(ItemListDef {
+                item_list_id,
+                entries: entries
+                    .iter()
+                    .filter(|e| e.0 == item_list_id)
+                    .map(|&(_, item_def_id, min_qty, max_qty, probability)| ItemListEntry {
+                        item_def_id,
+                        min_qty,
+                        max_qty,
+                        probability,
+                    })
+                    .collect(),
+            });
+        }
+    }
+
+    // (id, name, biome, tool_type, tool_tier, skill_id, skill_level, health,
+    //  duration_ms, item_list_id, respawn_secs, spawn_per_mille, max_per_chunk)
+    #[allow(clippy::type_complexity)]
+    let defs: [(u64, &str, u16, u8, u8, u32, u32, u32, u64, u64, u64, u16, u32); 5] = [
+        (1, "Oak Tree", BIOME_FOREST, 1, 1, 1, 0, 100, 3000, 1, 600, 60, 40),
+        (2, "Lone Pine", BIOME_GRASSLAND, 1, 1, 1, 0, 100, 3000, 1, 600, 15, 12),
+        (3, "Granite Outcrop", BIOME_MOUNTAIN, 2, 1, 2, 0, 150, 4000, 2, 900, 80, 40),
+        (4, "Sandstone Boulder", BIOME_DESERT, 2, 1, 2, 0, 100, 4000, 2, 900, 20, 16),
+        (5, "Marsh Reeds", BIOME_SWAMP, 0, 0, 3, 0, 20, 1500, 3, 300, 50, 30),
+    ];
+    for (
+        resource_def_id,
+        name,
+        biome_id,
+        tool_type,
+        tool_tier,
+        skill_id,
+        skill_level,
+        max_health,
+        gather_duration_ms,
+        item_list_id,
+        respawn_secs,
+        spawn_per_mille,
+        max_per_chunk,
+    ) in defs
+    {
+        if ctx.db.resource_def().resource_def_id().find(resource_def_id).is_none() {
+            ctx.db.resource_def().insert(ResourceDef {
+                resource_def_id,
+                name: name.to_string(),
+                biome_id,
+                tool_type,
+                tool_tier,
+                skill_id,
+                skill_level,
+                max_health,
+                gather_duration_ms,
+                item_list_id,
+                respawn_secs,
+                spawn_per_mille,
+                max_per_chunk,
+            });
+        }
+    }
+}
+
+fn seed_recipes(ctx: &ReducerContext) {
+    // (recipe_id, name, station_building_type, skill_id, skill_level, duration_ms, xp_reward)
+    let recipes: [(u64, &str, u32, u32, u32, u64, u64); 3] = [
+        (1, "Saw Planks", 1, 4, 0, 5000, 10),
+        (2, "Smelt Ingot", 2, 5, 0, 8000, 15),
+        (3, "Bind Sickle", 0, 3, 0, 3000, 5),
+    ];
+    // (recipe_id, is_output, item_def_id, quantity)
+    let items: [(u64, bool, u64, u32); 7] = [
+        (1, false, 1, 4),
+        (1, true, 5, 2),
+        (2, false, 3, 3),
+        (2, true, 6, 1),
+        (3, false, 4, 3),
+        (3, false, 1, 2),
+        (3, true, 12, 1),
+    ];
+    let stacks = |recipe_id: u64, output: bool| -> Vec<RecipeItem> {
+        items
+            .iter()
+            .filter(|e| e.0 == recipe_id && e.1 == output)
+            .map(|&(_, _, item_def_id, quantity)| RecipeItem { item_def_id, quantity })
+            .collect()
+    };
+    for (recipe_id, name, station_building_type, skill_id, skill_level, duration_ms, xp_reward) in recipes {
+        if ctx.db.recipe_def().recipe_id().find(recipe_id).is_none() {
+            ctx.db.recipe_def().insert(RecipeDef {
+                recipe_id,
+                name: name.to_string(),
+                inputs: stacks(recipe_id, false),
+                outputs: stacks(recipe_id, true),
+                station_building_type,
+                skill_id,
+                skill_level,
+                duration_ms,
+                xp_reward,
+            });
+        }
+    }
+}
+
+fn seed_equipment(ctx: &ReducerContext) {
+    // (item_def_id, slot, weapon_type, skill_id, skill_level)
+    let defs: [(u64, u8, u8, u32, u32); 4] = [
+        (20, equipment::SLOT_MAIN_HAND, 1, 7, 1),
+        (21, equipment::SLOT_CHEST, 0, 0, 0),
+        (22, equipment::SLOT_FEET, 0, 0, 0),
+        (23, equipment::SLOT_BACK, 0, 0, 0),
+    ];
+    // (item_def_id, stat, value, is_percentage)
+    let modifiers: [(u64, u8, f32, bool); 6] = [
+        (20, stats::STAT_ATTACK_POWER, 8.0, false),
+        (21, stats::STAT_ARMOR, 5.0, false),
+        (21, stats::STAT_MAX_HP, 10.0, false),
+        (21, stats::STAT_COLD_RESIST, 10.0, false),
+        (22, stats::STAT_MOVE_SPEED, 0.05, true),
+        (23, stats::STAT_CARRY_VOLUME, 500.0, false),
+    ];
+    for (item_def_id, slot, weapon_type, skill_id, skill_level) in defs {
+        if ctx.db.equipment_def().item_def_id().find(item_def_id).is_none() {
+            ctx.db.equipment_def().insert(EquipmentDef {
+                item_def_id,
+                slot,
+                weapon_type,
+                skill_id,
+                skill_level,
+                modifiers: modifiers
+                    .iter()
+                    .filter(|m| m.0 == item_def_id)
+                    .map(|&(_, stat, value, is_percentage)| StatModifier {
+                        stat,
+                        value,
+                        is_percentage,
+                    })
+                    .collect(),
+            });
+        }
+    }
+}
+
+fn seed_status_effects(ctx: &ReducerContext) {
+    // (effect_id, name, category, priority, duration_ms, max_stacks, is_debuff)
+    #[allow(clippy::type_complexity)]
+    let effects: [(u32, &str, u16, i32, u64, u32, bool); 6] = [
+        (1, "Well Fed", 1, 1, 300_000, 1, false),
+        (2, "Chilled", 2, 1, 10_000, 1, true),
+        (3, "Overheated", 3, 1, 10_000, 1, true),
+        (4, "Poisoned", 4, 1, 10_000, 3, true),
+        (5, "Haste", 5, 1, 15_000, 1, false),
+        (6, "Starving", 6, 1, 0, 1, true),
+    ];
+    // (effect_id, stat, value per stack, is_percentage)
+    let modifiers: [(u32, u8, f32, bool); 7] = [
+        (1, stats::STAT_MAX_HP, 10.0, false),
+        (1, stats::STAT_HP_REGEN, 1.0, false),
+        (2, stats::STAT_MOVE_SPEED, -0.15, true),
+        (3, stats::STAT_ATTACK_POWER, -0.10, true),
+        (4, stats::STAT_MAX_HP, -0.05, true),
+        (5, stats::STAT_MOVE_SPEED, 0.20, true),
+        (6, stats::STAT_STAMINA_REGEN, -1.0, true),
+    ];
+    for (effect_id, name, category, priority, duration_ms, max_stacks, is_debuff) in effects {
+        if ctx.db.status_effect().effect_id().find(effect_id).is_none() {
+            ctx.db.status_effect().insert(StatusEffect {
+                effect_id,
+                name: name.to_string(),
+                category,
+                priority,
+                duration_ms,
+                max_stacks,
+                is_debuff,
+                modifiers: modifiers
+                    .iter()
+                    .filter(|m| m.0 == effect_id)
+                    .map(|&(_, stat, value, is_percentage)| StatModifier {
+                        stat,
+                        value,
+                        is_percentage,
+                    })
+                    .collect(),
+            });
+        }
+    }
+
+    // (env_effect_id, name, hazard_tag, effect_id, damage_per_tick, resistance_stat, threshold)
+    #[allow(clippy::type_complexity)]
+    let hazards: [(u32, &str, u8, u32, i32, u8, i32); 3] = [
+        (1, "Frostbite", environment::HAZARD_COLD, 2, 2, stats::STAT_COLD_RESIST, 10),
+        (2, "Heatstroke", environment::HAZARD_HEAT, 3, 1, stats::STAT_HEAT_RESIST, 10),
+        (3, "Swamp Miasma", environment::HAZARD_POISON, 4, 1, stats::STAT_POISON_RESIST, 10),
+    ];
+    for (
+        env_effect_id,
+        name,
+        hazard_tag,
+        effect_id,
+        damage_per_tick,
+        resistance_stat,
+        resistance_threshold,
+    ) in hazards
+    {
+        if ctx.db.environment_effect_def().env_effect_id().find(env_effect_id).is_none() {
+            ctx.db.environment_effect_def().insert(EnvironmentEffectDef {
+                env_effect_id,
+                name: name.to_string(),
+                hazard_tag,
+                effect_id,
+                damage_per_tick,
+                resistance_stat,
+                resistance_threshold,
+            });
+        }
+    }
+}
+
+fn seed_food(ctx: &ReducerContext) {
+    // (item_def_id, hp, stamina, satiation, consumable_in_combat, effect_id)
+    let foods: [(u64, i32, i32, i32, bool, u32); 2] = [(30, 5, 10, 80, true, 0), (31, 10, 20, 250, false, 1)];
+    for (item_def_id, hp_restore, stamina_restore, satiation_restore, consumable_in_combat, effect_id) in foods
+    {
+        if ctx.db.food_def().item_def_id().find(item_def_id).is_none() {
+            ctx.db.food_def().insert(FoodDef {
+                item_def_id,
+                hp_restore,
+                stamina_restore,
+                satiation_restore,
+                consumable_in_combat,
+                effect_id,
+            });
+        }
+    }
+}
+
+fn seed_abilities(ctx: &ReducerContext) {
+    // (id, name, kind, cooldown_ms, stamina_cost, range, power,
+    //  effect_id, skill_id, skill_level, weapon_type)
+    #[allow(clippy::type_complexity)]
+    let defs: [(u32, &str, u8, u64, i32, f32, i32, u32, u32, u32, u8); 4] = [
+        (1, "Basic Attack", abilities::KIND_ATTACK, 1200, 10, 8.0, 0, 0, 0, 0, 0),
+        (2, "Second Wind", abilities::KIND_HEAL, 30_000, 20, 0.0, 25, 0, 0, 0, 0),
+        (3, "Sprint", abilities::KIND_BUFF, 45_000, 15, 0.0, 0, 5, 0, 0, 0),
+        (4, "Cleave", abilities::KIND_ATTACK, 6000, 25, 3.0, 12, 0, 7, 2, 1),
+    ];
+    for (
+        ability_def_id,
+        name,
+        kind,
+        cooldown_ms,
+        stamina_cost,
+        range,
+        power,
+        effect_id,
+        skill_id,
+        skill_level,
+        weapon_type,
+    ) in defs
+    {
+        if ctx.db.ability_def().ability_def_id().find(ability_def_id).is_none() {
+            ctx.db.ability_def().insert(AbilityDef {
+                ability_def_id,
+                name: name.to_string(),
+                kind,
+                cooldown_ms,
+                stamina_cost,
+                range,
+                power,
+                effect_id,
+                skill_id,
+                skill_level,
+                weapon_type,
+            });
+        }
+    }
+}
+
+fn seed_quests(ctx: &ReducerContext) {
+    // (chain_id, name, prerequisite_chain_ids, skill_id, skill_level, stage_ids)
+    #[allow(clippy::type_complexity)]
+    let chains: [(u64, &str, &[u64], u32, u32, &[u64]); 2] = [
+        (1, "Settling In", &[], 0, 0, &[1, 2, 3]),
+        (2, "Trial by Combat", &[1], 0, 0, &[4, 5]),
+    ];
+    // (chain_id, kind, target_id, amount)
+    let rewards: [(u64, u8, u64, u64); 4] = [
+        (1, quests::REWARD_ITEM, 31, 3),
+        (1, quests::REWARD_XP, 4, 50),
+        (2, quests::REWARD_ITEM, 20, 1),
+        (2, quests::REWARD_XP, 7, 100),
+    ];
+    // (stage_id, chain_id, name)
+    let stages: [(u64, u64, &str); 5] = [
+        (1, 1, "Meet the Warden"),
+        (2, 1, "Gather Timber"),
+        (3, 1, "Saw Some Planks"),
+        (4, 2, "First Blood"),
+        (5, 2, "Return to the Waystone"),
+    ];
+    // (stage_id, kind, target_id, quantity, consume, pos_x, pos_z, radius)
+    #[allow(clippy::type_complexity)]
+    let conditions: [(u64, u8, u64, u32, bool, f32, f32, f32); 5] = [
+        (1, quests::COND_TALK, 1, 1, false, 0.0, 0.0, 0.0),
+        (2, quests::COND_COLLECT, 1, 10, true, 0.0, 0.0, 0.0),
+        (3, quests::COND_CRAFT, 1, 1, false, 0.0, 0.0, 0.0),
+        (4, quests::COND_KILL, 0, 1, false, 0.0, 0.0, 0.0),
+        (5, quests::COND_REACH, 0, 1, false, 0.0, 0.0, 5.0),
+    ];
+    for (chain_id, name, prerequisite_chain_ids, skill_id, skill_level, stage_ids) in chains {
+        if ctx.db.quest_chain_def().chain_id().find(chain_id).is_none() {
+            ctx.db.quest_chain_def().insert(QuestChainDef {
+                chain_id,
+                name: name.to_string(),
+                prerequisite_chain_ids: prerequisite_chain_ids.to_vec(),
+                skill_id,
+                skill_level,
+                stage_ids: stage_ids.to_vec(),
+                rewards: rewards
+                    .iter()
+                    .filter(|r| r.0 == chain_id)
+                    .map(|&(_, kind, target_id, amount)| QuestReward { kind, target_id, amount })
+                    .collect(),
+            });
+        }
+    }
+    for (stage_id, chain_id, name) in stages {
+        if ctx.db.quest_stage_def().stage_id().find(stage_id).is_none() {
+            ctx.db.quest_stage_def().insert(QuestStageDef {
+                stage_id,
+                chain_id,
+                name: name.to_string(),
+                conditions: conditions
+                    .iter()
+                    .filter(|c| c.0 == stage_id)
+                    .map(|&(_, kind, target_id, quantity, consume, pos_x, pos_z, radius)| QuestCondition {
+                        kind,
+                        target_id,
+                        quantity,
+                        consume,
+                        pos_x,
+                        pos_z,
+                        radius,
+                    })
+                    .collect(),
+            });
+        }
+    }
+}
+
+fn seed_achievements(ctx: &ReducerContext) {
+    // (achievement_id, name, prerequisite_ids, skill_id, skill_level)
+    #[allow(clippy::type_complexity)]
+    let defs: [(u64, &str, &[u64], u32, u32); 5] = [
+        (1, "Woodsman", &[], 0, 0),
+        (2, "Wanderer", &[], 0, 0),
+        (3, "Artisan", &[1], 4, 2),
+        (4, "Settled", &[], 0, 0),
+        (5, "Friend of the Folk", &[4], 0, 0),
+    ];
+    // (achievement_id, kind, target_id, count)
+    let requirements: [(u64, u8, u64, u32); 5] = [
+        (1, achievements::KNOWLEDGE_ITEM, 1, 1),
+        (2, achievements::KNOWLEDGE_BIOME, 0, 3),
+        (3, achievements::KNOWLEDGE_RECIPE, 0, 3),
+        (4, achievements::REQ_QUEST_CHAIN, 1, 1),
+        (5, achievements::KNOWLEDGE_NPC, 0, 3),
+    ];
+    // (achievement_id, kind, reward_id, label)
+    let rewards: [(u64, u8, u64, &str); 5] = [
+        (1, achievements::REWARD_TITLE, 1, "Woodsman"),
+        (2, achievements::REWARD_TITLE, 2, "Wanderer"),
+        (3, achievements::REWARD_COSMETIC, 1, "Artisan's Apron"),
+        (4, achievements::REWARD_TITLE, 3, "Settler"),
+        (5, achievements::REWARD_COSMETIC, 2, "Village Scarf"),
+    ];
+    for (achievement_id, name, prerequisite_ids, skill_id, skill_level) in defs {
+        if ctx.db.achievement_def().achievement_id().find(achievement_id).is_none() {
+            ctx.db.achievement_def().insert(AchievementDef {
+                achievement_id,
+                name: name.to_string(),
+                prerequisite_ids: prerequisite_ids.to_vec(),
+                skill_id,
+                skill_level,
+                requirements: requirements
+                    .iter()
+                    .filter(|r| r.0 == achievement_id)
+                    .map(|&(_, kind, target_id, count)| AchievementRequirement { kind, target_id, count })
+                    .collect(),
+                rewards: rewards
+                    .iter()
+                    .filter(|r| r.0 == achievement_id)
+                    .map(|&(_, kind, reward_id, label)| AchievementReward {
+                        kind,
+                        reward_id,
+                        label: label.to_string(),
+                    })
+                    .collect(),
+            });
+        }
+    }
+}
+
+fn seed_npc_role_items(ctx: &ReducerContext) {
+    // (role, item_def_id, max_quantity); guards hand out nothing
+    let rules: [(u8, u64, u32); 6] = [
+        (npc_policy::ROLE_VILLAGER, 30, 3),
+        (npc_policy::ROLE_VILLAGER, 31, 1),
+        (npc_policy::ROLE_MERCHANT, 31, 1),
+        (npc_policy::ROLE_QUEST_GIVER, 1, 5),
+        (npc_policy::ROLE_QUEST_GIVER, 4, 5),
+        (npc_policy::ROLE_QUEST_GIVER, 31, 2),
+    ];
+    for (role, item_def_id, max_quantity) in rules {
+        let rule_key = npc_policy::rule_key(role, item_def_id);
+        if ctx.db.npc_role_item_rule().rule_key().find(rule_key.clone()).is_none() {
+            ctx.db.npc_role_item_rule().insert(NpcRoleItemRule {
+                rule_key,
+                role,
+                item_def_id,
+                max_quantity,
+            });
+        }
+    }
+}
+
+fn seed_npcs(ctx: &ReducerContext) {
+    // (npc_id, region_id, pos_x, pos_z, role)
+    let npcs: [(u64, u64, f32, f32, u8); 4] = [
+        (1, 1, 4.0, 0.0, npc_policy::ROLE_VILLAGER),
+        (2, 1, 0.0, 4.0, npc_policy::ROLE_MERCHANT),
+        (3, 1, -4.0, 0.0, npc_policy::ROLE_QUEST_GIVER),
+        (4, 1, 0.0, -4.0, npc_policy::ROLE_GUARD),
+    ];
+    for (npc_id, region_id, pos_x, pos_z, role) in npcs {
+        if ctx.db.npc_state().npc_id().find(npc_id).is_none() {
+            ctx.db.npc_state().insert(NpcState {
+                npc_id,
+                region_id,
+                pos_x,
+                pos_z,
+                role,
+                schedule_kind: npc_schedule::SCHEDULE_ROUTINE,
+                activity: npc_schedule::ACTIVITY_IDLE,
+                schedule_key: String::new(),
+                updated_at: ctx.timestamp,
+            });
+        }
+    }
+}
+
 #[spacetimedb::reducer]
 pub fn import_csv_data(ctx: &ReducerContext) {
     // Bootstrap stage: alias to seed path so CLI flow stays stable.
@@ -0,0 +1,29 @@
+use spacetimedb::{ReducerContext, Table};
+
+use crate::services::permissions;
+use crate::tables::BalanceParams;
+use crate::tables::balance::balance_params;
+
+#[spacetimedb::reducer]
+pub fn balance_param_set(ctx: &ReducerContext, key: String, value: String) -> Result<(), String> {
+    permissions::require_admin(ctx, "balance_param_set")?;
+    if key.trim().is_empty() {
+        return Err("key must not be empty".to_string());
+    }
+
+    match ctx.db.balance_params().key().find(key.clone()) {
+        Some(mut param) => {
+            param.value = value;
+            param.updated_at = ctx.timestamp;
+            ctx.db.balance_params().key().update(param);
+        }
+        None => {
+            ctx.db.balance_params().insert(BalanceParams {
+                key,
+                value,
+                updated_at: ctx.timestamp,
+            });
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,23 @@
+use std::time::Duration;
+
+use spacetimedb::{Identity, ReducerContext};
+
+use crate::services::{chat, permissions};
+
+/// Mutes `identity` in chat for `duration_secs`; 0 lifts the mute.
+#[spacetimedb::reducer]
+pub fn chat_mute_set(
+    ctx: &ReducerContext,
+    identity: Identity,
+    duration_secs: u64,
+    reason: String,
+) -> Result<(), String> {
+    permissions::require_admin(ctx, "chat_mute_set")?;
+
+    if duration_secs == 0 {
+        chat::lift_mute(ctx, identity);
+    } else {
+        chat::set_mute(ctx, identity, Duration::from_secs(duration_secs), &reason);
+    }
+    Ok(())
+}
@@ -0,0 +1,10 @@
+pub mod balance_param_set;
+pub mod chat_mute_set;
+pub mod moderator_set;
+pub mod nav_cell_cost_set;
+pub mod nav_obstacle_set;
+pub mod npc_region_budget_set;
+pub mod npc_schedule_set;
+pub mod npc_stock_set;
+pub mod npc_worker_set;
+pub mod world_generate;
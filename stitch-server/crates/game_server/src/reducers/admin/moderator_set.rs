@@ -0,0 +1,35 @@
+use spacetimedb::{Identity, ReducerContext, Table};
+
+use crate::services::permissions;
+use crate::tables::PermissionState;
+use crate::tables::permission_state::permission_state;
+
+/// Grants (or with `enabled = false` revokes) the global moderator flag used
+/// by the report queue and moderation reducers.
+#[spacetimedb::reducer]
+pub fn moderator_set(ctx: &ReducerContext, moderator: Identity, enabled: bool) -> Result<(), String> {
+    permissions::require_admin(ctx, "moderator_set")?;
+
+    let key = permissions::permission_key(0, 0, moderator);
+    match ctx.db.permission_state().permission_key().find(key.clone()) {
+        Some(mut row) => {
+            if enabled {
+                row.flags |= permissions::PERM_MODERATE;
+            } else {
+                row.flags &= !permissions::PERM_MODERATE;
+            }
+            ctx.db.permission_state().permission_key().update(row);
+        }
+        None if enabled => {
+            ctx.db.permission_state().insert(PermissionState {
+                permission_key: key,
+                target_kind: 0,
+                target_id: 0,
+                subject_identity: moderator,
+                flags: permissions::PERM_MODERATE,
+            });
+        }
+        None => {}
+    }
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use spacetimedb::{ReducerContext, Table};
+
+use crate::services::{pathfinding, permissions};
+use crate::tables::NavCellCost;
+use crate::tables::navigation::nav_cell_cost;
+
+#[spacetimedb::reducer]
+pub fn nav_cell_cost_set(
+    ctx: &ReducerContext,
+    region_id: u64,
+    hex_x: i32,
+    hex_z: i32,
+    terrain_cost: f32,
+    blocked: bool,
+) -> Result<(), String> {
+    permissions::require_admin(ctx, "nav_cell_cost_set")?;
+    if !terrain_cost.is_finite() || terrain_cost < 0.0 {
+        return Err("terrain_cost must be a non-negative number".to_string());
+    }
+
+    let row = NavCellCost {
+        cell_key: pathfinding::cell_key(region_id, hex_x, hex_z),
+        region_id,
+        hex_x,
+        hex_z,
+        terrain_cost,
+        blocked,
+    };
+    if ctx.db.nav_cell_cost().cell_key().find(row.cell_key.clone()).is_some() {
+        ctx.db.nav_cell_cost().cell_key().update(row);
+    } else {
+        ctx.db.nav_cell_cost().insert(row);
+    }
+    Ok(())
+}
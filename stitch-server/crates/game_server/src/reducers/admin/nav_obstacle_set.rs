@@ -0,0 +1,39 @@
+use spacetimedb::{ReducerContext, Table};
+
+use crate::services::permissions;
+use crate::tables::NavObstacle;
+use crate::tables::navigation::nav_obstacle;
+
+#[spacetimedb::reducer]
+pub fn nav_obstacle_set(
+    ctx: &ReducerContext,
+    region_id: u64,
+    hex_x: i32,
+    hex_z: i32,
+    blocked: bool,
+) -> Result<(), String> {
+    permissions::require_admin(ctx, "nav_obstacle_set")?;
+
+    let existing = ctx
+        .db
+        .nav_obstacle()
+        .iter()
+        .find(|o| o.region_id == region_id && o.hex_x == hex_x && o.hex_z == hex_z);
+    match existing {
+        Some(mut obstacle) => {
+            obstacle.blocked = blocked;
+            ctx.db.nav_obstacle().obstacle_id().update(obstacle);
+        }
+        None => {
+            ctx.db.nav_obstacle().insert(NavObstacle {
+                obstacle_id: 0,
+                region_id,
+                hex_x,
+                hex_z,
+                blocked,
+                created_at: ctx.timestamp,
+            });
+        }
+    }
+    Ok(())
+}
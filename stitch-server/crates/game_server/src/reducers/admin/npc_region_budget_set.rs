@@ -0,0 +1,32 @@
+use spacetimedb::{ReducerContext, Table};
+
+use crate::services::permissions;
+use crate::tables::NpcRegionBudget;
+use crate::tables::npc_cost::npc_region_budget;
+
+/// Sets a region's daily npc token cap, overriding `npc.region_daily_tokens`;
+/// `daily_token_cap = 0` leaves the region unlimited.
+#[spacetimedb::reducer]
+pub fn npc_region_budget_set(
+    ctx: &ReducerContext,
+    region_id: u64,
+    daily_token_cap: u64,
+) -> Result<(), String> {
+    permissions::require_admin(ctx, "npc_region_budget_set")?;
+
+    match ctx.db.npc_region_budget().region_id().find(region_id) {
+        Some(mut budget) => {
+            budget.daily_token_cap = daily_token_cap;
+            budget.updated_at = ctx.timestamp;
+            ctx.db.npc_region_budget().region_id().update(budget);
+        }
+        None => {
+            ctx.db.npc_region_budget().insert(NpcRegionBudget {
+                region_id,
+                daily_token_cap,
+                updated_at: ctx.timestamp,
+            });
+        }
+    }
+    Ok(())
+}
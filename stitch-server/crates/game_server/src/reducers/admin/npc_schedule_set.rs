@@ -0,0 +1,46 @@
+use spacetimedb::{ReducerContext, Table};
+
+use crate::services::{npc_schedule, permissions};
+use crate::tables::NpcActionSchedule;
+use crate::tables::npc_quest::npc_state;
+use crate::tables::npc_schedule::npc_action_schedule;
+
+/// Sets the routine entry starting at `start_hour` for an npc; `activity = 0`
+/// removes it. The npc re-plans on the next `npc_ai_agent` tick.
+#[spacetimedb::reducer]
+pub fn npc_schedule_set(
+    ctx: &ReducerContext,
+    npc_id: u64,
+    start_hour: u8,
+    activity: u8,
+    target_x: i32,
+    target_z: i32,
+) -> Result<(), String> {
+    permissions::require_admin(ctx, "npc_schedule_set")?;
+    if start_hour > 23 {
+        return Err("start_hour must be 0-23".to_string());
+    }
+    if activity > npc_schedule::ACTIVITY_SLEEP {
+        return Err("unknown activity".to_string());
+    }
+
+    let schedule_key = npc_schedule::schedule_key(npc_id, start_hour);
+    ctx.db.npc_action_schedule().schedule_key().delete(schedule_key.clone());
+    if activity != npc_schedule::ACTIVITY_IDLE {
+        ctx.db.npc_action_schedule().insert(NpcActionSchedule {
+            schedule_key: schedule_key.clone(),
+            npc_id,
+            start_hour,
+            activity,
+            target_x,
+            target_z,
+        });
+    }
+    let npc = ctx.db.npc_state().npc_id().find(npc_id);
+    if let Some(mut npc) = npc.filter(|n| n.schedule_key == schedule_key) {
+        npc.schedule_key = String::new();
+        npc.updated_at = ctx.timestamp;
+        ctx.db.npc_state().npc_id().update(npc);
+    }
+    Ok(())
+}
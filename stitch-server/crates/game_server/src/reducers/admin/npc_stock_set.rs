@@ -0,0 +1,55 @@
+use spacetimedb::{ReducerContext, Table};
+
+use crate::services::{npc_shop, permissions};
+use crate::tables::NpcShopStock;
+use crate::tables::item_def::item_def;
+use crate::tables::npc_shop::npc_shop_stock;
+
+/// Adds or updates an item in an npc vendor's stock; `max_quantity = 0`
+/// removes it. New entries start fully stocked.
+#[spacetimedb::reducer]
+pub fn npc_stock_set(
+    ctx: &ReducerContext,
+    npc_id: u64,
+    item_def_id: u64,
+    base_price: u64,
+    max_quantity: u32,
+    restock_amount: u32,
+) -> Result<(), String> {
+    permissions::require_admin(ctx, "npc_stock_set")?;
+
+    let stock_key = npc_shop::stock_key(npc_id, item_def_id);
+    if max_quantity == 0 {
+        ctx.db.npc_shop_stock().stock_key().delete(stock_key);
+        return Ok(());
+    }
+    if base_price == 0 {
+        return Err("base_price must be > 0".to_string());
+    }
+    if ctx.db.item_def().item_def_id().find(item_def_id).is_none() {
+        return Err("item_def not found".to_string());
+    }
+    match ctx.db.npc_shop_stock().stock_key().find(stock_key.clone()) {
+        Some(mut stock) => {
+            stock.base_price = base_price;
+            stock.max_quantity = max_quantity;
+            stock.quantity = stock.quantity.min(max_quantity);
+            stock.restock_amount = restock_amount;
+            stock.updated_at = ctx.timestamp;
+            ctx.db.npc_shop_stock().stock_key().update(stock);
+        }
+        None => {
+            ctx.db.npc_shop_stock().insert(NpcShopStock {
+                stock_key,
+                npc_id,
+                item_def_id,
+                base_price,
+                quantity: max_quantity,
+                max_quantity,
+                restock_amount,
+                updated_at: ctx.timestamp,
+            });
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use spacetimedb::{Identity, ReducerContext, Table};
+
+use crate::services::permissions;
+use crate::tables::NpcWorker;
+use crate::tables::npc_action::npc_worker;
+
+/// Authorizes (or with `enabled = false` revokes) an identity to lease and
+/// answer npc action requests. Any client can act as the worker, including
+/// a local stub process.
+#[spacetimedb::reducer]
+pub fn npc_worker_set(
+    ctx: &ReducerContext,
+    worker: Identity,
+    label: String,
+    enabled: bool,
+) -> Result<(), String> {
+    permissions::require_admin(ctx, "npc_worker_set")?;
+
+    if !enabled {
+        ctx.db.npc_worker().identity().delete(worker);
+        return Ok(());
+    }
+    match ctx.db.npc_worker().identity().find(worker) {
+        Some(mut existing) => {
+            existing.label = label;
+            ctx.db.npc_worker().identity().update(existing);
+        }
+        None => {
+            ctx.db.npc_worker().insert(NpcWorker {
+                identity: worker,
+                label,
+                registered_at: ctx.timestamp,
+                last_seen_at: ctx.timestamp,
+            });
+        }
+    }
+    Ok(())
+}
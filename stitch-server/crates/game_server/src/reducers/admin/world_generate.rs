@@ -0,0 +1,60 @@
+use spacetimedb::{ReducerContext, Table};
+
+use crate::services::{permissions, resources};
+use crate::services::world_gen::{self, MAX_WORLD_CHUNKS, OVERWORLD_DIMENSION, SEA_LEVEL};
+use crate::tables::WorldGenParams;
+use crate::tables::terrain_chunk::{terrain_chunk, world_gen_params};
+
+/// Regenerates the overworld; `size` is the world width in chunks.
+#[spacetimedb::reducer]
+pub fn world_generate(ctx: &ReducerContext, seed: u64, size: u32) -> Result<(), String> {
+    permissions::require_admin(ctx, "world_generate")?;
+    if size == 0 || size > MAX_WORLD_CHUNKS {
+        return Err(format!("size must be between 1 and {MAX_WORLD_CHUNKS} chunks"));
+    }
+
+    let world = world_gen::generate(seed, size);
+    let checksum = world.checksum();
+    let (spawn, land_cells, lake_count, river_cells) =
+        (world.spawn, world.land_cells, world.lake_count, world.river_cells);
+
+    let stale: Vec<i64> = ctx
+        .db
+        .terrain_chunk()
+        .iter()
+        .filter(|c| c.dimension_id == OVERWORLD_DIMENSION)
+        .map(|c| c.chunk_id)
+        .collect();
+    for chunk_id in stale {
+        ctx.db.terrain_chunk().chunk_id().delete(chunk_id);
+    }
+    let resource_nodes = resources::spawn_world_nodes(ctx, &world, OVERWORLD_DIMENSION);
+    for chunk in world.into_chunks(OVERWORLD_DIMENSION) {
+        ctx.db.terrain_chunk().insert(chunk);
+    }
+
+    let params = WorldGenParams {
+        dimension_id: OVERWORLD_DIMENSION,
+        seed,
+        size_chunks: size,
+        sea_level: SEA_LEVEL,
+        land_cells,
+        lake_count,
+        river_cells,
+        spawn_x: spawn.x,
+        spawn_z: spawn.z,
+        checksum,
+        generated_at: ctx.timestamp,
+    };
+    if ctx.db.world_gen_params().dimension_id().find(OVERWORLD_DIMENSION).is_some() {
+        ctx.db.world_gen_params().dimension_id().update(params);
+    } else {
+        ctx.db.world_gen_params().insert(params);
+    }
+
+    log::info!(
+        "world generated: seed={seed} size={size} land={land_cells} lakes={lake_count} \
+         rivers={river_cells} resource_nodes={resource_nodes} checksum={checksum:016x}"
+    );
+    Ok(())
+}
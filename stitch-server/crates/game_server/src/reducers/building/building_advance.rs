@@ -1,7 +1,7 @@
 use spacetimedb::ReducerContext;
 
-use crate::services::permissions;
-use crate::tables::building_state::building_state;
+use crate::services::{balance, permissions, skills};
+use crate::tables::building_state::{building_def, building_state};
 
 #[spacetimedb::reducer]
 pub fn building_advance(ctx: &ReducerContext, building_id: u64, steps: u32) -> Result<(), String> {
@@ -26,6 +26,17 @@ pub fn building_advance(ctx: &ReducerContext, building_id: u64, steps: u32) -> R
         return Err("no build permission".to_string());
     }
 
+    // xp is paid per step of the def's construction work, never per step of a stored project size
+    let def_required = ctx
+        .db
+        .building_def()
+        .building_type()
+        .find(building.building_type)
+        .map(|def| def.build_required)
+        .unwrap_or(0);
+    let applied = steps
+        .min(building.build_required.saturating_sub(building.build_progress))
+        .min(def_required.saturating_sub(building.build_progress));
     building.build_progress = building.build_progress.saturating_add(steps);
     if building.build_progress >= building.build_required {
         building.build_progress = building.build_required;
@@ -34,5 +45,8 @@ pub fn building_advance(ctx: &ReducerContext, building_id: u64, steps: u32) -> R
     building.updated_at = ctx.timestamp;
     ctx.db.building_state().entity_id().update(building);
 
+    let xp_per_step = balance::get_u64(ctx, balance::CONSTRUCTION_XP_PER_STEP, 2);
+    skills::award_xp(ctx, ctx.sender, skills::SKILL_CONSTRUCTION, xp_per_step.saturating_mul(applied as u64));
+
     Ok(())
 }
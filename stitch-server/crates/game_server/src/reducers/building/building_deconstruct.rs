@@ -1,10 +1,8 @@
 use spacetimedb::ReducerContext;
 
-use crate::services::permissions;
+use crate::services::{inventory, permissions};
 use crate::tables::building_state::building_state;
 
-use super::building_place::add_items_to_main_inventory;
-
 #[spacetimedb::reducer]
 pub fn building_deconstruct(ctx: &ReducerContext, building_id: u64) -> Result<(), String> {
     let mut building = ctx
@@ -26,7 +24,7 @@ pub fn building_deconstruct(ctx: &ReducerContext, building_id: u64) -> Result<()
 
     // Refund half of required materials.
     let refund = (building.required_item_qty / 2).max(1);
-    add_items_to_main_inventory(ctx, building.required_item_def_id, refund)?;
+    inventory::add_items(ctx, ctx.sender, building.required_item_def_id, refund)?;
 
     building.state = 2;
     building.updated_at = ctx.timestamp;
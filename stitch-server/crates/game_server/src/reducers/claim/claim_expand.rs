@@ -1,6 +1,7 @@
 use spacetimedb::ReducerContext;
 
-use crate::services::permissions;
+use crate::services::{claims, permissions};
+use crate::tables::claim_local_state::claim_local_state;
 use crate::tables::claim_state::claim_state;
 use crate::tables::transform_state::transform_state;
 
@@ -36,8 +37,18 @@ pub fn claim_expand(ctx: &ReducerContext, claim_id: u64, radius_delta: u32) -> R
         return Err("too far from claim center".to_string());
     }
 
-    claim.radius = claim.radius.saturating_add(radius_delta);
-    claim.tier = claim.tier.saturating_add(1);
+    let next_radius = claim.radius.saturating_add(radius_delta);
+    let next_tiles = claims::hex_tile_count(next_radius);
+    if next_tiles > claims::max_tiles(ctx, claim_id) {
+        return Err("claim tile budget exceeded; research claim tech".to_string());
+    }
+
+    let mut local = claims::ensure_local_state(ctx, &claim);
+    local.num_tiles = next_tiles;
+    local.updated_at = ctx.timestamp;
+    ctx.db.claim_local_state().claim_id().update(local);
+
+    claim.radius = next_radius;
     claim.updated_at = ctx.timestamp;
     ctx.db.claim_state().claim_id().update(claim);
 
@@ -0,0 +1,32 @@
+use spacetimedb::ReducerContext;
+
+use crate::services::claims;
+use crate::tables::claim_member_state::claim_member_state;
+
+#[spacetimedb::reducer]
+pub fn claim_member_accept(ctx: &ReducerContext, claim_id: u64, accepted: bool) -> Result<(), String> {
+    let key = claims::member_key(claim_id, ctx.sender);
+    let mut member = ctx
+        .db
+        .claim_member_state()
+        .member_key()
+        .find(key.clone())
+        .ok_or("claim invite not found".to_string())?;
+
+    if member.status != claims::MEMBER_STATUS_INVITED {
+        return Err("claim invite already resolved".to_string());
+    }
+
+    if !accepted {
+        ctx.db.claim_member_state().member_key().delete(key);
+        return Ok(());
+    }
+
+    member.status = claims::MEMBER_STATUS_ACTIVE;
+    member.joined_at = ctx.timestamp;
+    member.last_active_at = ctx.timestamp;
+    member.updated_at = ctx.timestamp;
+    ctx.db.claim_member_state().member_key().update(member);
+
+    Ok(())
+}
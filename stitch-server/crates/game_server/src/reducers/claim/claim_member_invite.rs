@@ -0,0 +1,53 @@
+use spacetimedb::{Identity, ReducerContext};
+
+use crate::services::{claims, permissions};
+use crate::tables::account::account;
+use crate::tables::claim_member_state::claim_member_state;
+use crate::tables::claim_state::claim_state;
+
+#[spacetimedb::reducer]
+pub fn claim_member_invite(
+    ctx: &ReducerContext,
+    claim_id: u64,
+    invitee: Identity,
+    rank: u8,
+) -> Result<(), String> {
+    if rank > claims::RANK_CO_OWNER {
+        return Err("rank must be guest..co_owner".to_string());
+    }
+    if invitee == ctx.sender {
+        return Err("cannot invite self".to_string());
+    }
+
+    let claim = ctx
+        .db
+        .claim_state()
+        .claim_id()
+        .find(claim_id)
+        .ok_or("claim not found".to_string())?;
+
+    let actor_rank = claims::active_rank(ctx, &claim, ctx.sender).ok_or("not a claim member".to_string())?;
+    if !permissions::has_permission(ctx, 1, claim_id, permissions::PERM_MANAGE_MEMBERS) {
+        return Err("no member manage permission".to_string());
+    }
+    if rank >= actor_rank {
+        return Err("cannot grant rank at or above own rank".to_string());
+    }
+
+    if ctx.db.account().identity().find(invitee).is_none() {
+        return Err("invitee account not found".to_string());
+    }
+    if let Some(existing) = ctx
+        .db
+        .claim_member_state()
+        .member_key()
+        .find(claims::member_key(claim_id, invitee))
+    {
+        if existing.status == claims::MEMBER_STATUS_ACTIVE {
+            return Err("already a claim member".to_string());
+        }
+    }
+
+    claims::upsert_member(ctx, claim_id, invitee, rank, claims::MEMBER_STATUS_INVITED, ctx.sender);
+    Ok(())
+}
@@ -0,0 +1,41 @@
+use spacetimedb::{Identity, ReducerContext};
+
+use crate::services::{claims, permissions};
+use crate::tables::claim_member_state::claim_member_state;
+use crate::tables::claim_state::claim_state;
+
+/// Removes a member or pending invite. Members may always remove themselves.
+#[spacetimedb::reducer]
+pub fn claim_member_kick(ctx: &ReducerContext, claim_id: u64, member: Identity) -> Result<(), String> {
+    let claim = ctx
+        .db
+        .claim_state()
+        .claim_id()
+        .find(claim_id)
+        .ok_or("claim not found".to_string())?;
+
+    if claim.owner_identity == member {
+        return Err("owner cannot be removed; transfer ownership first".to_string());
+    }
+
+    let key = claims::member_key(claim_id, member);
+    let target = ctx
+        .db
+        .claim_member_state()
+        .member_key()
+        .find(key.clone())
+        .ok_or("claim member not found".to_string())?;
+
+    if member != ctx.sender {
+        let actor_rank = claims::active_rank(ctx, &claim, ctx.sender).ok_or("not a claim member".to_string())?;
+        if !permissions::has_permission(ctx, 1, claim_id, permissions::PERM_MANAGE_MEMBERS) {
+            return Err("no member manage permission".to_string());
+        }
+        if target.rank >= actor_rank {
+            return Err("cannot remove member of equal or higher rank".to_string());
+        }
+    }
+
+    ctx.db.claim_member_state().member_key().delete(key);
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use spacetimedb::{Identity, ReducerContext};
+
+use crate::services::{claims, permissions};
+use crate::tables::claim_member_state::claim_member_state;
+use crate::tables::claim_state::claim_state;
+
+#[spacetimedb::reducer]
+pub fn claim_member_set_rank(
+    ctx: &ReducerContext,
+    claim_id: u64,
+    member: Identity,
+    rank: u8,
+) -> Result<(), String> {
+    if rank > claims::RANK_CO_OWNER {
+        return Err("rank must be guest..co_owner; use claim_ownership_transfer".to_string());
+    }
+
+    let claim = ctx
+        .db
+        .claim_state()
+        .claim_id()
+        .find(claim_id)
+        .ok_or("claim not found".to_string())?;
+
+    if member == ctx.sender {
+        return Err("cannot change own rank".to_string());
+    }
+
+    let actor_rank = claims::active_rank(ctx, &claim, ctx.sender).ok_or("not a claim member".to_string())?;
+    if !permissions::has_permission(ctx, 1, claim_id, permissions::PERM_MANAGE_MEMBERS) {
+        return Err("no member manage permission".to_string());
+    }
+
+    let mut target = ctx
+        .db
+        .claim_member_state()
+        .member_key()
+        .find(claims::member_key(claim_id, member))
+        .ok_or("claim member not found".to_string())?;
+
+    if target.rank >= actor_rank || rank >= actor_rank {
+        return Err("rank change exceeds own rank".to_string());
+    }
+
+    target.rank = rank;
+    target.updated_at = ctx.timestamp;
+    ctx.db.claim_member_state().member_key().update(target);
+
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use spacetimedb::{Identity, ReducerContext};
+
+use crate::services::claims;
+use crate::tables::claim_member_state::claim_member_state;
+use crate::tables::claim_state::claim_state;
+
+#[spacetimedb::reducer]
+pub fn claim_ownership_transfer(ctx: &ReducerContext, claim_id: u64, new_owner: Identity) -> Result<(), String> {
+    let claim = ctx
+        .db
+        .claim_state()
+        .claim_id()
+        .find(claim_id)
+        .ok_or("claim not found".to_string())?;
+
+    if claim.owner_identity != ctx.sender {
+        return Err("only owner can transfer claim".to_string());
+    }
+    if new_owner == ctx.sender {
+        return Err("already the claim owner".to_string());
+    }
+
+    let member = ctx
+        .db
+        .claim_member_state()
+        .member_key()
+        .find(claims::member_key(claim_id, new_owner))
+        .ok_or("new owner must be a claim member".to_string())?;
+    if member.status != claims::MEMBER_STATUS_ACTIVE || member.rank < claims::RANK_MEMBER {
+        return Err("new owner must be an active member".to_string());
+    }
+
+    claims::transfer_ownership(ctx, claim, new_owner);
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use spacetimedb::ReducerContext;
+
+use crate::services::{claims, economy, inventory, permissions};
+use crate::tables::claim_local_state::claim_local_state;
+use crate::tables::claim_state::claim_state;
+
+/// Converts inventory items into claim supplies, one supply per unit of item volume.
+#[spacetimedb::reducer]
+pub fn claim_supply_deposit(
+    ctx: &ReducerContext,
+    claim_id: u64,
+    item_def_id: u64,
+    quantity: u32,
+) -> Result<(), String> {
+    if quantity == 0 {
+        return Err("quantity must be > 0".to_string());
+    }
+
+    let claim = ctx
+        .db
+        .claim_state()
+        .claim_id()
+        .find(claim_id)
+        .ok_or("claim not found".to_string())?;
+
+    if claim.owner_identity != ctx.sender
+        && !permissions::has_permission(ctx, 1, claim_id, permissions::PERM_CONTAINER)
+    {
+        return Err("no supply deposit permission".to_string());
+    }
+
+    let gained = economy::supply_value(ctx, item_def_id, quantity)?;
+    inventory::consume_items(ctx, ctx.sender, item_def_id, quantity)?;
+
+    let mut local = claims::ensure_local_state(ctx, &claim);
+    local.supplies = local.supplies.saturating_add(gained);
+    if local.supplies > 0 {
+        local.protected = true;
+    }
+    local.updated_at = ctx.timestamp;
+    ctx.db.claim_local_state().claim_id().update(local);
+
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use spacetimedb::{ReducerContext, Table};
+
+use crate::services::{claims, permissions};
+use crate::tables::ClaimTechState;
+use crate::tables::claim_local_state::claim_local_state;
+use crate::tables::claim_state::claim_state;
+use crate::tables::claim_tech::{claim_tech_def, claim_tech_state};
+
+#[spacetimedb::reducer]
+pub fn claim_tech_research(ctx: &ReducerContext, claim_id: u64, tech_id: u64) -> Result<(), String> {
+    let mut claim = ctx
+        .db
+        .claim_state()
+        .claim_id()
+        .find(claim_id)
+        .ok_or("claim not found".to_string())?;
+
+    if claim.owner_identity != ctx.sender
+        && !permissions::has_permission(ctx, 1, claim_id, permissions::PERM_ADMIN)
+    {
+        return Err("no claim research permission".to_string());
+    }
+
+    let def = ctx
+        .db
+        .claim_tech_def()
+        .tech_id()
+        .find(tech_id)
+        .ok_or("claim tech not found".to_string())?;
+
+    if claims::has_tech(ctx, claim_id, tech_id) {
+        return Err("claim tech already researched".to_string());
+    }
+    if def.required_tech_id != 0 && !claims::has_tech(ctx, claim_id, def.required_tech_id) {
+        return Err("required claim tech missing".to_string());
+    }
+    if def.tier > claim.tier.saturating_add(1) {
+        return Err("claim tier too low for tech".to_string());
+    }
+
+    let mut local = claims::ensure_local_state(ctx, &claim);
+    if local.supplies < def.supply_cost {
+        return Err("not enough claim supplies".to_string());
+    }
+    local.supplies -= def.supply_cost;
+    local.updated_at = ctx.timestamp;
+    ctx.db.claim_local_state().claim_id().update(local);
+
+    ctx.db.claim_tech_state().insert(ClaimTechState {
+        tech_key: claims::tech_key(claim_id, tech_id),
+        claim_id,
+        tech_id,
+        researched_at: ctx.timestamp,
+    });
+
+    if def.tier > claim.tier {
+        claim.tier = def.tier;
+        claim.updated_at = ctx.timestamp;
+        ctx.db.claim_state().claim_id().update(claim);
+    }
+
+    Ok(())
+}
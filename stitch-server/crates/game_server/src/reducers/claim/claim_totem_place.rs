@@ -1,6 +1,6 @@
 use spacetimedb::{ReducerContext, Table};
 
-use crate::services::permissions;
+use crate::services::{claims, empires, permissions};
 use crate::tables::{ClaimState, PermissionState};
 use crate::tables::building_state::building_state;
 use crate::tables::claim_state::claim_state;
@@ -16,6 +16,9 @@ pub fn claim_totem_place(
     if radius < 3 {
         return Err("radius must be >= 3".to_string());
     }
+    if claims::hex_tile_count(radius) > claims::BASE_MAX_TILES {
+        return Err("radius exceeds base claim tile budget".to_string());
+    }
 
     if ctx.db.claim_state().claim_id().find(claim_id).is_some() {
         return Err("claim_id already exists".to_string());
@@ -44,7 +47,13 @@ pub fn claim_totem_place(
         }
     }
 
-    ctx.db.claim_state().insert(ClaimState {
+    if let Some(node) = empires::influence_at(ctx, building.region_id, building.hex_x, building.hex_z) {
+        if empires::member(ctx, node.empire_id, ctx.sender).is_none() {
+            return Err("location is under another empire's influence".to_string());
+        }
+    }
+
+    let claim = ctx.db.claim_state().insert(ClaimState {
         claim_id,
         owner_identity: ctx.sender,
         totem_building_id,
@@ -56,6 +65,7 @@ pub fn claim_totem_place(
         created_at: ctx.timestamp,
         updated_at: ctx.timestamp,
     });
+    claims::ensure_local_state(ctx, &claim);
 
     let key = permissions::permission_key(1, claim_id, ctx.sender);
     ctx.db.permission_state().insert(PermissionState {
@@ -66,5 +76,14 @@ pub fn claim_totem_place(
         flags: permissions::PERM_BUILD | permissions::PERM_ADMIN,
     });
 
+    claims::upsert_member(
+        ctx,
+        claim_id,
+        ctx.sender,
+        claims::RANK_OWNER,
+        claims::MEMBER_STATUS_ACTIVE,
+        ctx.sender,
+    );
+
     Ok(())
 }
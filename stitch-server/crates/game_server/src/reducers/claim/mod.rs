@@ -1,2 +1,9 @@
 pub mod claim_expand;
+pub mod claim_member_accept;
+pub mod claim_member_invite;
+pub mod claim_member_kick;
+pub mod claim_member_set_rank;
+pub mod claim_ownership_transfer;
+pub mod claim_supply_deposit;
+pub mod claim_tech_research;
 pub mod claim_totem_place;
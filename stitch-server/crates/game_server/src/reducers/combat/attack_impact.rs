@@ -1,11 +1,11 @@
 use spacetimedb::{ReducerContext, Table};
 
-use crate::tables::{AttackOutcome, ThreatState};
+use crate::services::{balance, combat, health, party, skills};
+use crate::tables::AttackOutcome;
 use crate::tables::combat::attack_outcome;
 use crate::tables::combat::attack_schedule_state;
 use crate::tables::combat::combat_state;
 use crate::tables::session_state::session_state;
-use crate::tables::combat::threat_state;
 use crate::tables::transform_state::transform_state;
 
 const ATTACK_RANGE_SQ: f32 = 64.0;
@@ -73,26 +73,16 @@ pub fn attack_impact(ctx: &ReducerContext, request_key: String, client_ts_ms: u6
         .find(scheduled.target_identity)
         .ok_or("target combat state missing".to_string())?;
 
-    target_combat.current_hp = (target_combat.current_hp - scheduled.impact_damage).max(0);
-    target_combat.in_combat = target_combat.current_hp > 0;
+    let target_was_down = health::is_downed(ctx, scheduled.target_identity);
+    let target_hp_after = health::damage(ctx, scheduled.target_identity, scheduled.impact_damage);
+    if !target_was_down && target_hp_after == 0 {
+        party::on_kill(ctx, scheduled.attacker_identity, scheduled.target_identity);
+    }
+    target_combat.in_combat = target_hp_after > 0;
     target_combat.updated_at = ctx.timestamp;
-    let target_hp_after = target_combat.current_hp;
     ctx.db.combat_state().identity().update(target_combat);
 
-    let threat_key = format!("{}:{}", scheduled.attacker_identity, scheduled.target_identity);
-    if let Some(mut threat) = ctx.db.threat_state().threat_key().find(threat_key.clone()) {
-        threat.threat += scheduled.impact_damage;
-        threat.updated_at = ctx.timestamp;
-        ctx.db.threat_state().threat_key().update(threat);
-    } else {
-        ctx.db.threat_state().insert(ThreatState {
-            threat_key,
-            attacker_identity: scheduled.attacker_identity,
-            target_identity: scheduled.target_identity,
-            threat: scheduled.impact_damage,
-            updated_at: ctx.timestamp,
-        });
-    }
+    combat::add_threat(ctx, scheduled.attacker_identity, scheduled.target_identity, scheduled.impact_damage);
 
     scheduled.phase = 2;
     scheduled.updated_at = ctx.timestamp;
@@ -103,6 +93,9 @@ pub fn attack_impact(ctx: &ReducerContext, request_key: String, client_ts_ms: u6
     let scheduled_damage = scheduled.impact_damage;
     ctx.db.attack_schedule_state().request_key().update(scheduled);
 
+    let combat_xp = balance::get_u64(ctx, balance::COMBAT_XP_PER_HIT, 3);
+    skills::award_xp(ctx, scheduled_attacker, skills::SKILL_COMBAT, combat_xp);
+
     let outcome_id = format!("{}:{}", scheduled_request_key, client_ts_ms);
     if ctx.db.attack_outcome().outcome_id().find(outcome_id.clone()).is_none() {
         ctx.db.attack_outcome().insert(AttackOutcome {
@@ -1,5 +1,6 @@
 use spacetimedb::{Identity, ReducerContext, Table};
 
+use crate::services::{abilities, health, stats};
 use crate::tables::{AttackScheduled, CombatState};
 use crate::tables::combat::attack_schedule_state;
 use crate::tables::combat::combat_state;
@@ -7,9 +8,6 @@ use crate::tables::session_state::session_state;
 use crate::tables::transform_state::transform_state;
 
 const ATTACK_RANGE_SQ: f32 = 64.0;
-const ATTACK_COOLDOWN_MS: u64 = 1200;
-const DEFAULT_HP: i32 = 100;
-const IMPACT_DAMAGE: i32 = 10;
 
 #[spacetimedb::reducer]
 pub fn attack_start(
@@ -75,6 +73,13 @@ pub fn attack_start(
         return Ok(());
     }
 
+    if health::is_downed(ctx, target_identity) {
+        return Err("target is already down".to_string());
+    }
+
+    let attacker_stats = stats::get(ctx, ctx.sender);
+    let target_stats = stats::get(ctx, target_identity);
+
     let mut attacker_combat = ctx
         .db
         .combat_state()
@@ -84,7 +89,7 @@ pub fn attack_start(
             identity: ctx.sender,
             region_id: attacker_session.region_id,
             in_combat: false,
-            current_hp: DEFAULT_HP,
+            current_hp: 0,
             last_attack_client_ts_ms: 0,
             updated_at: ctx.timestamp,
         });
@@ -92,11 +97,8 @@ pub fn attack_start(
     if client_ts_ms <= attacker_combat.last_attack_client_ts_ms {
         return Err("client timestamp must increase".to_string());
     }
-    if attacker_combat.last_attack_client_ts_ms > 0
-        && client_ts_ms - attacker_combat.last_attack_client_ts_ms < ATTACK_COOLDOWN_MS
-    {
-        return Err("attack cooldown active".to_string());
-    }
+    // Cooldown and stamina are checked against server time by the ability system.
+    let basic_attack = abilities::activate(ctx, ctx.sender, abilities::BASIC_ATTACK)?;
 
     attacker_combat.in_combat = true;
     attacker_combat.last_attack_client_ts_ms = client_ts_ms;
@@ -117,7 +119,7 @@ pub fn attack_start(
             identity: target_identity,
             region_id: attacker_session.region_id,
             in_combat: false,
-            current_hp: DEFAULT_HP,
+            current_hp: 0,
             last_attack_client_ts_ms: 0,
             updated_at: ctx.timestamp,
         });
@@ -131,13 +133,15 @@ pub fn attack_start(
         ctx.db.combat_state().insert(target_combat);
     }
 
+    let impact_damage =
+        stats::mitigated_damage(attacker_stats.attack_power + basic_attack.power, target_stats.armor);
     ctx.db.attack_schedule_state().insert(AttackScheduled {
         request_key,
         attacker_identity: ctx.sender,
         target_identity,
         region_id: attacker_session.region_id,
         client_ts_ms,
-        impact_damage: IMPACT_DAMAGE,
+        impact_damage,
         phase: 0,
         created_at: ctx.timestamp,
         updated_at: ctx.timestamp,
@@ -1,3 +1,4 @@
 pub mod attack_impact;
 pub mod attack_scheduled;
 pub mod attack_start;
+pub mod use_ability;
@@ -0,0 +1,108 @@
+use spacetimedb::{Identity, ReducerContext};
+
+use crate::services::{abilities, balance, buffs, combat, health, party, skills, stats};
+use crate::tables::ability::ability_def;
+use crate::tables::resource_state::resource_state;
+use crate::tables::session_state::session_state;
+use crate::tables::transform_state::transform_state;
+
+/// Uses an ability from the caller's loadout. `target` defaults to the caller
+/// for heals and buffs; attacks need another player within range.
+#[spacetimedb::reducer]
+pub fn use_ability(
+    ctx: &ReducerContext,
+    ability_def_id: u32,
+    target: Option<Identity>,
+) -> Result<(), String> {
+    let session = ctx
+        .db
+        .session_state()
+        .identity()
+        .find(ctx.sender)
+        .ok_or("active session required".to_string())?;
+    if health::is_downed(ctx, ctx.sender) {
+        return Err("you are incapacitated".to_string());
+    }
+
+    let target = target.unwrap_or(ctx.sender);
+    if target != ctx.sender {
+        let target_session = ctx
+            .db
+            .session_state()
+            .identity()
+            .find(target)
+            .ok_or("target is not online".to_string())?;
+        if target_session.region_id != session.region_id {
+            return Err("target is not in same region".to_string());
+        }
+        if health::is_downed(ctx, target) {
+            return Err("target is down".to_string());
+        }
+    }
+
+    // Validate targeting before `activate` spends stamina and starts the cooldown.
+    let (kind, max_range) = ctx
+        .db
+        .ability_def()
+        .ability_def_id()
+        .find(ability_def_id)
+        .map(|d| (d.kind, d.range))
+        .ok_or("ability_def not found".to_string())?;
+    if kind == abilities::KIND_ATTACK && target == ctx.sender {
+        return Err("cannot attack self".to_string());
+    }
+    if target != ctx.sender {
+        if max_range <= 0.0 {
+            return Err("ability can only target self".to_string());
+        }
+        let caster_tf = ctx
+            .db
+            .transform_state()
+            .entity_id()
+            .find(ctx.sender)
+            .ok_or("transform missing".to_string())?;
+        let target_tf = ctx
+            .db
+            .transform_state()
+            .entity_id()
+            .find(target)
+            .ok_or("target transform missing".to_string())?;
+        let dx = caster_tf.position[0] - target_tf.position[0];
+        let dz = caster_tf.position[2] - target_tf.position[2];
+        if dx * dx + dz * dz > max_range * max_range {
+            return Err("target out of range".to_string());
+        }
+    }
+
+    let def = abilities::activate(ctx, ctx.sender, ability_def_id)?;
+    match def.kind {
+        abilities::KIND_ATTACK => {
+            let damage = stats::mitigated_damage(
+                stats::get(ctx, ctx.sender).attack_power + def.power,
+                stats::get(ctx, target).armor,
+            );
+            let target_was_down = health::is_downed(ctx, target);
+            if health::damage(ctx, target, damage) == 0 && !target_was_down {
+                party::on_kill(ctx, ctx.sender, target);
+            }
+            combat::enter_combat(ctx, ctx.sender, session.region_id);
+            combat::enter_combat(ctx, target, session.region_id);
+            combat::add_threat(ctx, ctx.sender, target, damage);
+            let combat_xp = balance::get_u64(ctx, balance::COMBAT_XP_PER_HIT, 3);
+            skills::award_xp(ctx, ctx.sender, skills::SKILL_COMBAT, combat_xp);
+        }
+        abilities::KIND_HEAL => {
+            let max_hp = stats::get(ctx, target).max_hp;
+            let mut state = health::ensure(ctx, target);
+            state.hp = (state.hp + def.power.max(0)).min(max_hp);
+            state.updated_at = ctx.timestamp;
+            ctx.db.resource_state().identity().update(state);
+        }
+        abilities::KIND_BUFF => {}
+        _ => return Err("unknown ability kind".to_string()),
+    }
+    if def.effect_id != 0 {
+        buffs::apply(ctx, target, def.effect_id, buffs::SOURCE_ABILITY)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,29 @@
+use spacetimedb::ReducerContext;
+
+use crate::services::{crafting, inventory};
+use crate::tables::crafting::{craft_job, craft_timer, recipe_def};
+
+#[spacetimedb::reducer]
+pub fn craft_cancel(ctx: &ReducerContext, job_id: u64) -> Result<(), String> {
+    let job = ctx
+        .db
+        .craft_job()
+        .job_id()
+        .find(job_id)
+        .ok_or("craft job not found".to_string())?;
+    if job.owner_identity != ctx.sender {
+        return Err("not your craft".to_string());
+    }
+    if job.status != crafting::JOB_STATUS_PROCESSING {
+        return Err("craft already finished; collect it instead".to_string());
+    }
+
+    if let Some(recipe) = ctx.db.recipe_def().recipe_id().find(job.recipe_id) {
+        inventory::add_all(ctx, ctx.sender, &crafting::stacks(&recipe.inputs))
+            .map_err(|_| "no inventory space for refund".to_string())?;
+    }
+
+    ctx.db.craft_timer().job_id().delete(job_id);
+    ctx.db.craft_job().job_id().delete(job_id);
+    Ok(())
+}
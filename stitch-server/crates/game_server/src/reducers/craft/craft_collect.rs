@@ -0,0 +1,32 @@
+use spacetimedb::ReducerContext;
+
+use crate::services::crafting;
+use crate::tables::crafting::{craft_job, recipe_def};
+
+/// Claims the outputs of a craft that finished while the inventory was full.
+#[spacetimedb::reducer]
+pub fn craft_collect(ctx: &ReducerContext, job_id: u64) -> Result<(), String> {
+    let job = ctx
+        .db
+        .craft_job()
+        .job_id()
+        .find(job_id)
+        .ok_or("craft job not found".to_string())?;
+    if job.owner_identity != ctx.sender {
+        return Err("not your craft".to_string());
+    }
+    if job.status != crafting::JOB_STATUS_READY {
+        return Err("craft is not finished".to_string());
+    }
+
+    let recipe = ctx
+        .db
+        .recipe_def()
+        .recipe_id()
+        .find(job.recipe_id)
+        .ok_or("recipe not found".to_string())?;
+    if !crafting::try_deliver(ctx, &job, &recipe)? {
+        return Err("no inventory space".to_string());
+    }
+    Ok(())
+}
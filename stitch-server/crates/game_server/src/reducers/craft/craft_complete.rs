@@ -0,0 +1,31 @@
+use spacetimedb::ReducerContext;
+
+use crate::services::crafting;
+use crate::tables::CraftTimer;
+use crate::tables::crafting::{craft_job, recipe_def};
+
+#[spacetimedb::reducer]
+pub fn craft_complete(ctx: &ReducerContext, timer: CraftTimer) -> Result<(), String> {
+    if ctx.sender != ctx.identity() {
+        return Err("craft_complete is scheduler-only".to_string());
+    }
+
+    // Cancelled jobs take their timer with them; nothing to do.
+    let Some(mut job) = ctx.db.craft_job().job_id().find(timer.job_id) else {
+        return Ok(());
+    };
+    if job.status != crafting::JOB_STATUS_PROCESSING {
+        return Ok(());
+    }
+    let Some(recipe) = ctx.db.recipe_def().recipe_id().find(job.recipe_id) else {
+        ctx.db.craft_job().job_id().delete(job.job_id);
+        return Ok(());
+    };
+
+    if !crafting::try_deliver(ctx, &job, &recipe)? {
+        // Inventory is full: keep the outputs on the job for craft_collect.
+        job.status = crafting::JOB_STATUS_READY;
+        ctx.db.craft_job().job_id().update(job);
+    }
+    Ok(())
+}
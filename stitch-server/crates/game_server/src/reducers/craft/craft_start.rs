@@ -0,0 +1,95 @@
+use std::time::Duration;
+
+use spacetimedb::{ReducerContext, Table};
+
+use crate::services::{crafting, inventory, skills};
+use crate::tables::{CraftJob, CraftTimer};
+use crate::tables::building_state::building_state;
+use crate::tables::crafting::{craft_job, craft_timer, recipe_def};
+use crate::tables::session_state::session_state;
+use crate::tables::transform_state::transform_state;
+
+/// Starts a timed craft. Inputs leave the inventory immediately and are held
+/// by the job until it completes or `craft_cancel` refunds them.
+#[spacetimedb::reducer]
+pub fn craft_start(ctx: &ReducerContext, recipe_id: u64, building_id: u64) -> Result<(), String> {
+    let session = ctx
+        .db
+        .session_state()
+        .identity()
+        .find(ctx.sender)
+        .ok_or("active session required".to_string())?;
+
+    let recipe = ctx
+        .db
+        .recipe_def()
+        .recipe_id()
+        .find(recipe_id)
+        .ok_or("recipe not found".to_string())?;
+
+    skills::require(ctx, ctx.sender, recipe.skill_id, recipe.skill_level)?;
+
+    let active_jobs = ctx.db.craft_job().iter().filter(|j| j.owner_identity == ctx.sender).count();
+    if active_jobs >= crafting::MAX_ACTIVE_JOBS {
+        return Err("too many active crafts".to_string());
+    }
+
+    if recipe.station_building_type != 0 {
+        let building = ctx
+            .db
+            .building_state()
+            .entity_id()
+            .find(building_id)
+            .ok_or("crafting station not found".to_string())?;
+        if building.state != 1 {
+            return Err("crafting station is not complete".to_string());
+        }
+        if building.building_type != recipe.station_building_type {
+            return Err("wrong crafting station".to_string());
+        }
+        if building.region_id != session.region_id {
+            return Err("crafting station is not in this region".to_string());
+        }
+        let transform = ctx
+            .db
+            .transform_state()
+            .entity_id()
+            .find(ctx.sender)
+            .ok_or("transform missing".to_string())?;
+        let dx = transform.position[0] - building.hex_x as f32;
+        let dz = transform.position[2] - building.hex_z as f32;
+        if dx * dx + dz * dz > crafting::STATION_RANGE_SQ {
+            return Err("crafting station out of range".to_string());
+        }
+        if !crafting::can_use_station(ctx, ctx.sender, &building) {
+            return Err("no permission to use this station".to_string());
+        }
+    }
+
+    for input in &recipe.inputs {
+        if inventory::count_items(ctx, ctx.sender, input.item_def_id) < input.quantity {
+            return Err("not enough materials in inventory".to_string());
+        }
+    }
+    for input in &recipe.inputs {
+        inventory::consume_items(ctx, ctx.sender, input.item_def_id, input.quantity)?;
+    }
+
+    let completes_at = ctx.timestamp + Duration::from_millis(recipe.duration_ms);
+    let job = ctx.db.craft_job().insert(CraftJob {
+        job_id: 0,
+        owner_identity: ctx.sender,
+        recipe_id,
+        building_id: if recipe.station_building_type != 0 { building_id } else { 0 },
+        status: crafting::JOB_STATUS_PROCESSING,
+        started_at: ctx.timestamp,
+        completes_at,
+    });
+    ctx.db.craft_timer().insert(CraftTimer {
+        scheduled_id: 0,
+        scheduled_at: completes_at.into(),
+        job_id: job.job_id,
+    });
+
+    Ok(())
+}
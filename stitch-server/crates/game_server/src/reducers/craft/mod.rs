@@ -0,0 +1,4 @@
+pub mod craft_cancel;
+pub mod craft_collect;
+pub mod craft_complete;
+pub mod craft_start;
@@ -0,0 +1,40 @@
+use spacetimedb::{ReducerContext, Table};
+
+use crate::services::empires;
+use crate::tables::EmpireClaimInvitation;
+use crate::tables::claim_state::claim_state;
+use crate::tables::empire::{empire_claim_invitation, empire_state};
+
+/// Invites a claim into the empire; the claim owner accepts with `empire_claim_join`.
+#[spacetimedb::reducer]
+pub fn empire_claim_invite(ctx: &ReducerContext, empire_id: u64, claim_id: u64) -> Result<(), String> {
+    if ctx.db.empire_state().empire_id().find(empire_id).is_none() {
+        return Err("empire not found".to_string());
+    }
+    empires::require_permission(ctx, empire_id, empires::EMPIRE_PERM_MANAGE_CLAIMS)?;
+
+    let claim = ctx
+        .db
+        .claim_state()
+        .claim_id()
+        .find(claim_id)
+        .ok_or("claim not found".to_string())?;
+    if claim.tier < empires::EMPIRE_MIN_CLAIM_TIER {
+        return Err("claim tier too low to join an empire".to_string());
+    }
+    if empires::claim_empire_id(ctx, claim_id).is_some() {
+        return Err("claim already belongs to an empire".to_string());
+    }
+
+    let invite_key = empires::claim_invite_key(empire_id, claim_id);
+    ctx.db.empire_claim_invitation().invite_key().delete(invite_key.clone());
+    ctx.db.empire_claim_invitation().insert(EmpireClaimInvitation {
+        invite_key,
+        empire_id,
+        claim_id,
+        invited_by: ctx.sender,
+        created_at: ctx.timestamp,
+    });
+
+    Ok(())
+}
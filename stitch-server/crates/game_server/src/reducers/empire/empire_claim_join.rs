@@ -0,0 +1,67 @@
+use spacetimedb::{ReducerContext, Table};
+
+use crate::services::empires;
+use crate::tables::EmpireClaimState;
+use crate::tables::claim_state::claim_state;
+use crate::tables::empire::{empire_claim_invitation, empire_claim_state, empire_member_state, empire_state};
+
+/// Accepts an empire's invitation for a claim the caller owns.
+#[spacetimedb::reducer]
+pub fn empire_claim_join(ctx: &ReducerContext, empire_id: u64, claim_id: u64) -> Result<(), String> {
+    let mut empire = ctx
+        .db
+        .empire_state()
+        .empire_id()
+        .find(empire_id)
+        .ok_or("empire not found".to_string())?;
+
+    let claim = ctx
+        .db
+        .claim_state()
+        .claim_id()
+        .find(claim_id)
+        .ok_or("claim not found".to_string())?;
+    if claim.owner_identity != ctx.sender {
+        return Err("only the claim owner can join an empire".to_string());
+    }
+    if claim.tier < empires::EMPIRE_MIN_CLAIM_TIER {
+        return Err("claim tier too low to join an empire".to_string());
+    }
+    let invite_key = empires::claim_invite_key(empire_id, claim_id);
+    if ctx.db.empire_claim_invitation().invite_key().find(invite_key.clone()).is_none() {
+        return Err("no invitation from this empire".to_string());
+    }
+
+    match empires::claim_empire_id(ctx, claim_id) {
+        Some(current) if current == empire_id => return Err("already part of this empire".to_string()),
+        Some(_) => return Err("already part of another empire".to_string()),
+        None => {}
+    }
+    if ctx
+        .db
+        .empire_member_state()
+        .iter()
+        .any(|m| m.member_identity == ctx.sender && m.empire_id != empire_id)
+    {
+        return Err("claim owner belongs to another empire".to_string());
+    }
+
+    // the claim is spoken for; drop this and any other empire's offers
+    for invite in ctx.db.empire_claim_invitation().claim_id().filter(claim_id).collect::<Vec<_>>() {
+        ctx.db.empire_claim_invitation().invite_key().delete(invite.invite_key);
+    }
+    ctx.db.empire_claim_state().insert(EmpireClaimState {
+        claim_id,
+        empire_id,
+        joined_at: ctx.timestamp,
+    });
+    if empires::member(ctx, empire_id, ctx.sender).is_none() {
+        empires::upsert_member(ctx, empire_id, ctx.sender, empires::RANK_CITIZEN);
+    }
+
+    empire.num_claims = empire.num_claims.saturating_add(1);
+    empire.updated_at = ctx.timestamp;
+    ctx.db.empire_state().empire_id().update(empire);
+
+    Ok(())
+}
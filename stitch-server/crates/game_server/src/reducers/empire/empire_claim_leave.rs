@@ -0,0 +1,43 @@
+use spacetimedb::ReducerContext;
+
+use crate::services::empires;
+use crate::tables::claim_state::claim_state;
+use crate::tables::empire::{empire_claim_state, empire_state};
+
+/// Removes a claim from its empire, either by the claim owner or an empire claim manager.
+#[spacetimedb::reducer]
+pub fn empire_claim_leave(ctx: &ReducerContext, claim_id: u64) -> Result<(), String> {
+    let membership = ctx
+        .db
+        .empire_claim_state()
+        .claim_id()
+        .find(claim_id)
+        .ok_or("claim is not part of an empire".to_string())?;
+    let mut empire = ctx
+        .db
+        .empire_state()
+        .empire_id()
+        .find(membership.empire_id)
+        .ok_or("empire not found".to_string())?;
+
+    if empire.capital_claim_id == claim_id {
+        return Err("capital claim cannot leave its empire".to_string());
+    }
+
+    let claim = ctx
+        .db
+        .claim_state()
+        .claim_id()
+        .find(claim_id)
+        .ok_or("claim not found".to_string())?;
+    if claim.owner_identity != ctx.sender {
+        empires::require_permission(ctx, empire.empire_id, empires::EMPIRE_PERM_MANAGE_CLAIMS)?;
+    }
+
+    ctx.db.empire_claim_state().claim_id().delete(claim_id);
+    empire.num_claims = empire.num_claims.saturating_sub(1);
+    empire.updated_at = ctx.timestamp;
+    ctx.db.empire_state().empire_id().update(empire);
+
+    Ok(())
+}
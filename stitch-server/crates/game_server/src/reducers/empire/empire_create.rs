@@ -0,0 +1,64 @@
+use spacetimedb::{ReducerContext, Table};
+
+use crate::services::empires;
+use crate::tables::{EmpireClaimState, EmpireState};
+use crate::tables::claim_state::claim_state;
+use crate::tables::empire::{empire_claim_state, empire_member_state, empire_state};
+
+#[spacetimedb::reducer]
+pub fn empire_create(
+    ctx: &ReducerContext,
+    empire_id: u64,
+    name: String,
+    capital_claim_id: u64,
+) -> Result<(), String> {
+    let name = name.trim().to_string();
+    if name.is_empty() || name.len() > 32 {
+        return Err("empire name must be 1..=32 chars".to_string());
+    }
+    if ctx.db.empire_state().empire_id().find(empire_id).is_some() {
+        return Err("empire_id already exists".to_string());
+    }
+    if ctx.db.empire_state().iter().any(|e| e.name == name) {
+        return Err("empire name already taken".to_string());
+    }
+    if ctx.db.empire_member_state().iter().any(|m| m.member_identity == ctx.sender) {
+        return Err("already a member of an empire".to_string());
+    }
+
+    let claim = ctx
+        .db
+        .claim_state()
+        .claim_id()
+        .find(capital_claim_id)
+        .ok_or("capital claim not found".to_string())?;
+    if claim.owner_identity != ctx.sender {
+        return Err("only the claim owner can found an empire".to_string());
+    }
+    if claim.tier < empires::EMPIRE_MIN_CLAIM_TIER {
+        return Err("claim tier too low to found an empire".to_string());
+    }
+    if empires::claim_empire_id(ctx, capital_claim_id).is_some() {
+        return Err("claim already belongs to an empire".to_string());
+    }
+
+    ctx.db.empire_state().insert(EmpireState {
+        empire_id,
+        name,
+        emperor_identity: ctx.sender,
+        capital_claim_id,
+        treasury: 0,
+        num_claims: 1,
+        created_at: ctx.timestamp,
+        updated_at: ctx.timestamp,
+    });
+    empires::insert_default_ranks(ctx, empire_id);
+    empires::upsert_member(ctx, empire_id, ctx.sender, empires::RANK_EMPEROR);
+    ctx.db.empire_claim_state().insert(EmpireClaimState {
+        claim_id: capital_claim_id,
+        empire_id,
+        joined_at: ctx.timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use spacetimedb::{Identity, ReducerContext, Table};
+
+use crate::services::{claims, empires};
+use crate::tables::claim_member_state::claim_member_state;
+use crate::tables::empire::{empire_claim_state, empire_member_state};
+
+/// Sets a member's empire rank. Residents of empire claims can be enrolled this way.
+#[spacetimedb::reducer]
+pub fn empire_member_set_rank(
+    ctx: &ReducerContext,
+    empire_id: u64,
+    member: Identity,
+    rank: u8,
+) -> Result<(), String> {
+    if rank == empires::RANK_EMPEROR || rank > empires::RANK_CITIZEN {
+        return Err("rank must be 1..=4".to_string());
+    }
+    if member == ctx.sender {
+        return Err("cannot change own rank".to_string());
+    }
+
+    let actor = empires::require_permission(ctx, empire_id, empires::EMPIRE_PERM_MANAGE_MEMBERS)?;
+    if rank <= actor.rank {
+        return Err("cannot grant rank at or above own rank".to_string());
+    }
+
+    match empires::member(ctx, empire_id, member) {
+        Some(existing) => {
+            if existing.rank <= actor.rank {
+                return Err("cannot change rank of equal or higher member".to_string());
+            }
+        }
+        None => {
+            if ctx.db.empire_member_state().iter().any(|m| m.member_identity == member) {
+                return Err("player belongs to another empire".to_string());
+            }
+            let resident = ctx.db.claim_member_state().iter().any(|m| {
+                m.member_identity == member
+                    && m.status == claims::MEMBER_STATUS_ACTIVE
+                    && ctx
+                        .db
+                        .empire_claim_state()
+                        .claim_id()
+                        .find(m.claim_id)
+                        .is_some_and(|c| c.empire_id == empire_id)
+            });
+            if !resident {
+                return Err("player is not a resident of an empire claim".to_string());
+            }
+        }
+    }
+
+    empires::upsert_member(ctx, empire_id, member, rank);
+    Ok(())
+}
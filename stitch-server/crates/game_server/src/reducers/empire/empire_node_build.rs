@@ -0,0 +1,63 @@
+use spacetimedb::{ReducerContext, Table};
+
+use crate::services::{claims, empires};
+use crate::tables::EmpireNodeState;
+use crate::tables::building_state::building_state;
+use crate::tables::empire::{empire_node_state, empire_state};
+
+/// Registers a completed watchtower building as an empire node.
+#[spacetimedb::reducer]
+pub fn empire_node_build(ctx: &ReducerContext, empire_id: u64, building_id: u64) -> Result<(), String> {
+    if ctx.db.empire_state().empire_id().find(empire_id).is_none() {
+        return Err("empire not found".to_string());
+    }
+    empires::require_permission(ctx, empire_id, empires::EMPIRE_PERM_NODES)?;
+
+    let building = ctx
+        .db
+        .building_state()
+        .entity_id()
+        .find(building_id)
+        .ok_or("building not found".to_string())?;
+    if building.building_type != empires::WATCHTOWER_BUILDING_TYPE {
+        return Err("building is not a watchtower".to_string());
+    }
+    if building.owner_identity != ctx.sender {
+        return Err("not owner of watchtower building".to_string());
+    }
+    if building.state != 1 {
+        return Err("watchtower building must be complete".to_string());
+    }
+    if ctx.db.empire_node_state().node_id().find(building_id).is_some() {
+        return Err("building is already an empire node".to_string());
+    }
+
+    let in_own_claim = claims::claim_covering(ctx, building.region_id, building.hex_x, building.hex_z)
+        .and_then(|c| empires::claim_empire_id(ctx, c.claim_id))
+        .is_some_and(|id| id == empire_id);
+    let in_own_influence = empires::influence_at(ctx, building.region_id, building.hex_x, building.hex_z)
+        .is_some_and(|n| n.empire_id == empire_id);
+    if !in_own_claim && !in_own_influence {
+        return Err("watchtower must be inside empire claims or influence".to_string());
+    }
+    if let Some(other) = empires::influence_at(ctx, building.region_id, building.hex_x, building.hex_z) {
+        if other.empire_id != empire_id {
+            return Err("location is under another empire's influence".to_string());
+        }
+    }
+
+    ctx.db.empire_node_state().insert(EmpireNodeState {
+        node_id: building_id,
+        empire_id,
+        region_id: building.region_id,
+        hex_x: building.hex_x,
+        hex_z: building.hex_z,
+        energy: empires::NODE_START_ENERGY,
+        upkeep: empires::NODE_UPKEEP,
+        influence_radius: empires::NODE_INFLUENCE_RADIUS,
+        active: true,
+        updated_at: ctx.timestamp,
+    });
+
+    Ok(())
+}
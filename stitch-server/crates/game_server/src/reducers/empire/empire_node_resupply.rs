@@ -0,0 +1,36 @@
+use spacetimedb::ReducerContext;
+
+use crate::services::{economy, empires, inventory};
+use crate::tables::empire::empire_node_state;
+
+#[spacetimedb::reducer]
+pub fn empire_node_resupply(
+    ctx: &ReducerContext,
+    node_id: u64,
+    item_def_id: u64,
+    quantity: u32,
+) -> Result<(), String> {
+    if quantity == 0 {
+        return Err("quantity must be > 0".to_string());
+    }
+
+    let mut node = ctx
+        .db
+        .empire_node_state()
+        .node_id()
+        .find(node_id)
+        .ok_or("empire node not found".to_string())?;
+    if empires::member(ctx, node.empire_id, ctx.sender).is_none() {
+        return Err("only empire members can resupply nodes".to_string());
+    }
+
+    let gained = economy::supply_value(ctx, item_def_id, quantity)?;
+    inventory::consume_items(ctx, ctx.sender, item_def_id, quantity)?;
+
+    node.energy = node.energy.saturating_add(gained);
+    node.active = true;
+    node.updated_at = ctx.timestamp;
+    ctx.db.empire_node_state().node_id().update(node);
+
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use spacetimedb::ReducerContext;
+
+use crate::services::empires;
+use crate::tables::empire::{empire_rank_state, empire_state};
+
+#[spacetimedb::reducer]
+pub fn empire_rank_update(
+    ctx: &ReducerContext,
+    empire_id: u64,
+    rank: u8,
+    title: String,
+    permissions: u32,
+) -> Result<(), String> {
+    let empire = ctx
+        .db
+        .empire_state()
+        .empire_id()
+        .find(empire_id)
+        .ok_or("empire not found".to_string())?;
+    if empire.emperor_identity != ctx.sender {
+        return Err("only the emperor can edit ranks".to_string());
+    }
+    if rank == empires::RANK_EMPEROR {
+        return Err("emperor rank cannot be edited".to_string());
+    }
+
+    let title = title.trim().to_string();
+    if title.is_empty() || title.len() > 24 {
+        return Err("rank title must be 1..=24 chars".to_string());
+    }
+
+    let mut row = ctx
+        .db
+        .empire_rank_state()
+        .rank_key()
+        .find(empires::rank_key(empire_id, rank))
+        .ok_or("rank not found".to_string())?;
+    row.title = title;
+    row.permissions = permissions;
+    ctx.db.empire_rank_state().rank_key().update(row);
+
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use spacetimedb::ReducerContext;
+
+use crate::services::{economy, empires, inventory};
+use crate::tables::empire::{empire_node_siege_state, empire_node_state};
+
+/// Adds supplies to either side of an active siege: attackers fuel the siege,
+/// defenders refill the besieged node's energy.
+#[spacetimedb::reducer]
+pub fn empire_siege_add_supplies(
+    ctx: &ReducerContext,
+    siege_id: String,
+    item_def_id: u64,
+    quantity: u32,
+) -> Result<(), String> {
+    if quantity == 0 {
+        return Err("quantity must be > 0".to_string());
+    }
+
+    let mut siege = ctx
+        .db
+        .empire_node_siege_state()
+        .siege_id()
+        .find(siege_id)
+        .ok_or("siege not found".to_string())?;
+    if siege.phase != 0 {
+        return Err("siege already resolved".to_string());
+    }
+
+    let is_attacker = empires::member(ctx, siege.attacker_empire_id, ctx.sender).is_some();
+    let is_defender = empires::member(ctx, siege.defender_empire_id, ctx.sender).is_some();
+    if !is_attacker && !is_defender {
+        return Err("not a participant of this siege".to_string());
+    }
+
+    let gained = economy::supply_value(ctx, item_def_id, quantity)?;
+    inventory::consume_items(ctx, ctx.sender, item_def_id, quantity)?;
+
+    if is_attacker {
+        siege.attacker_supplies = siege.attacker_supplies.saturating_add(gained);
+        siege.updated_at = ctx.timestamp;
+        ctx.db.empire_node_siege_state().siege_id().update(siege);
+    } else {
+        let mut node = ctx
+            .db
+            .empire_node_state()
+            .node_id()
+            .find(siege.node_id)
+            .ok_or("empire node not found".to_string())?;
+        node.energy = node.energy.saturating_add(gained);
+        node.updated_at = ctx.timestamp;
+        ctx.db.empire_node_state().node_id().update(node);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,90 @@
+use std::time::Duration;
+
+use spacetimedb::{ReducerContext, Table};
+
+use crate::services::{economy, empires, inventory};
+use crate::tables::{EmpireNodeSiegeState, EmpireSiegeTimer};
+use crate::tables::empire::{empire_node_siege_state, empire_node_state, empire_siege_timer, empire_state};
+use crate::tables::transform_state::transform_state;
+
+const SIEGE_RANGE_SQ: f32 = 900.0;
+pub(crate) const SIEGE_TICK_INTERVAL: Duration = Duration::from_secs(30);
+
+#[spacetimedb::reducer]
+pub fn empire_siege_start(
+    ctx: &ReducerContext,
+    node_id: u64,
+    attacker_empire_id: u64,
+    item_def_id: u64,
+    quantity: u32,
+) -> Result<(), String> {
+    if quantity == 0 {
+        return Err("quantity must be > 0".to_string());
+    }
+
+    let node = ctx
+        .db
+        .empire_node_state()
+        .node_id()
+        .find(node_id)
+        .ok_or("empire node not found".to_string())?;
+    if node.empire_id == attacker_empire_id {
+        return Err("cannot siege own empire node".to_string());
+    }
+    let attacker = ctx
+        .db
+        .empire_state()
+        .empire_id()
+        .find(attacker_empire_id)
+        .ok_or("attacker empire not found".to_string())?;
+    empires::require_permission(ctx, attacker_empire_id, empires::EMPIRE_PERM_SIEGE)?;
+
+    if ctx
+        .db
+        .empire_node_siege_state()
+        .iter()
+        .any(|s| s.node_id == node_id && s.phase == 0)
+    {
+        return Err("node is already under siege".to_string());
+    }
+
+    let transform = ctx
+        .db
+        .transform_state()
+        .entity_id()
+        .find(ctx.sender)
+        .ok_or("transform missing".to_string())?;
+    let dx = transform.position[0] - node.hex_x as f32;
+    let dz = transform.position[2] - node.hex_z as f32;
+    if dx * dx + dz * dz > SIEGE_RANGE_SQ {
+        return Err("too far from empire node".to_string());
+    }
+
+    let supplies = economy::supply_value(ctx, item_def_id, quantity)?;
+    inventory::consume_items(ctx, ctx.sender, item_def_id, quantity)?;
+
+    // A depleted watchtower falls immediately.
+    if node.energy == 0 {
+        empires::transfer_node(ctx, node, attacker_empire_id, attacker.emperor_identity);
+        return Ok(());
+    }
+
+    let siege_id = format!("{}:{}", node_id, ctx.timestamp);
+    ctx.db.empire_node_siege_state().insert(EmpireNodeSiegeState {
+        siege_id: siege_id.clone(),
+        node_id,
+        attacker_empire_id,
+        defender_empire_id: node.empire_id,
+        attacker_supplies: supplies,
+        phase: 0,
+        started_at: ctx.timestamp,
+        updated_at: ctx.timestamp,
+    });
+    ctx.db.empire_siege_timer().insert(EmpireSiegeTimer {
+        scheduled_id: 0,
+        scheduled_at: SIEGE_TICK_INTERVAL.into(),
+        siege_id,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,10 @@
+pub mod empire_claim_invite;
+pub mod empire_claim_join;
+pub mod empire_claim_leave;
+pub mod empire_create;
+pub mod empire_member_set_rank;
+pub mod empire_node_build;
+pub mod empire_node_resupply;
+pub mod empire_rank_update;
+pub mod empire_siege_add_supplies;
+pub mod empire_siege_start;
@@ -0,0 +1,91 @@
+use std::time::Duration;
+
+use spacetimedb::{ReducerContext, Table};
+
+use crate::services::{claims, housing};
+use crate::tables::HousingMovingCost;
+use crate::tables::building_state::building_state;
+use crate::tables::housing::{dimension_network, housing_moving_cost, housing_state};
+
+const BASE_MOVING_MINUTES: u32 = 12 * 60;
+const MINUTES_PER_INTERIOR_BUILDING: u32 = 30;
+const MAX_MOVING_MINUTES: u32 = 20 * 24 * 60;
+
+#[spacetimedb::reducer]
+pub fn housing_change_entrance(ctx: &ReducerContext, new_building_id: u64) -> Result<(), String> {
+    let mut house = ctx
+        .db
+        .housing_state()
+        .owner_identity()
+        .find(ctx.sender)
+        .ok_or("house not found".to_string())?;
+
+    if house.locked_until > ctx.timestamp {
+        return Err("house is already moving".to_string());
+    }
+    if house.entrance_building_id == new_building_id {
+        return Ok(());
+    }
+    if housing::occupant_count(ctx, house.dimension_id) > 0 {
+        return Err("house must be empty to move".to_string());
+    }
+
+    let new_entrance = ctx
+        .db
+        .building_state()
+        .entity_id()
+        .find(new_building_id)
+        .ok_or("new entrance building not found".to_string())?;
+    if new_entrance.owner_identity != ctx.sender {
+        return Err("not owner of new entrance building".to_string());
+    }
+    if new_entrance.state != 1 {
+        return Err("new entrance building must be complete".to_string());
+    }
+    if housing::dimension_of_region(new_entrance.region_id).is_some() {
+        return Err("entrance cannot be placed inside an interior".to_string());
+    }
+
+    // Moving within the same claim is free; otherwise the interior is locked while it travels.
+    let old_claim = ctx
+        .db
+        .building_state()
+        .entity_id()
+        .find(house.entrance_building_id)
+        .and_then(|b| claims::claim_covering(ctx, b.region_id, b.hex_x, b.hex_z))
+        .map(|c| c.claim_id);
+    let new_claim = claims::claim_covering(ctx, new_entrance.region_id, new_entrance.hex_x, new_entrance.hex_z)
+        .map(|c| c.claim_id);
+
+    let minutes = if old_claim.is_some() && old_claim == new_claim {
+        0
+    } else {
+        let contents = housing::interior_building_count(ctx, house.dimension_id) as u32;
+        BASE_MOVING_MINUTES
+            .saturating_add(contents.saturating_mul(MINUTES_PER_INTERIOR_BUILDING))
+            .min(MAX_MOVING_MINUTES)
+    };
+
+    let cost = HousingMovingCost {
+        owner_identity: ctx.sender,
+        moving_time_cost_minutes: minutes,
+        computed_at: ctx.timestamp,
+    };
+    if ctx.db.housing_moving_cost().owner_identity().find(ctx.sender).is_some() {
+        ctx.db.housing_moving_cost().owner_identity().update(cost);
+    } else {
+        ctx.db.housing_moving_cost().insert(cost);
+    }
+
+    if let Some(mut network) = ctx.db.dimension_network().network_id().find(house.network_id) {
+        network.building_id = new_building_id;
+        ctx.db.dimension_network().network_id().update(network);
+    }
+
+    house.entrance_building_id = new_building_id;
+    house.locked_until = ctx.timestamp + Duration::from_secs(minutes as u64 * 60);
+    house.updated_at = ctx.timestamp;
+    ctx.db.housing_state().owner_identity().update(house);
+
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use spacetimedb::{ReducerContext, Table};
+
+use crate::services::housing;
+use crate::tables::HousingState;
+use crate::tables::building_state::building_state;
+use crate::tables::housing::housing_state;
+
+#[spacetimedb::reducer]
+pub fn housing_create(ctx: &ReducerContext, entrance_building_id: u64) -> Result<(), String> {
+    if ctx.db.housing_state().owner_identity().find(ctx.sender).is_some() {
+        return Err("player already owns a house".to_string());
+    }
+
+    let building = ctx
+        .db
+        .building_state()
+        .entity_id()
+        .find(entrance_building_id)
+        .ok_or("entrance building not found".to_string())?;
+    if building.owner_identity != ctx.sender {
+        return Err("not owner of entrance building".to_string());
+    }
+    if building.state != 1 {
+        return Err("entrance building must be complete".to_string());
+    }
+    if housing::dimension_of_region(building.region_id).is_some() {
+        return Err("entrance cannot be placed inside an interior".to_string());
+    }
+
+    let (network_id, dimension_id) = housing::create_interior(ctx, ctx.sender, entrance_building_id);
+    ctx.db.housing_state().insert(HousingState {
+        owner_identity: ctx.sender,
+        entrance_building_id,
+        network_id,
+        dimension_id,
+        locked_until: ctx.timestamp,
+        is_empty: true,
+        last_visited_at: ctx.timestamp,
+        created_at: ctx.timestamp,
+        updated_at: ctx.timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,97 @@
+use spacetimedb::{Identity, ReducerContext, Table};
+
+use crate::services::{housing, permissions};
+use crate::tables::HousingVisitState;
+use crate::tables::building_state::building_state;
+use crate::tables::housing::{dimension_desc, housing_state, housing_visit_state};
+use crate::tables::session_state::session_state;
+use crate::tables::transform_state::transform_state;
+
+const ENTRANCE_RANGE_SQ: f32 = 25.0;
+
+#[spacetimedb::reducer]
+pub fn housing_enter(ctx: &ReducerContext, owner_identity: Identity) -> Result<(), String> {
+    let mut house = ctx
+        .db
+        .housing_state()
+        .owner_identity()
+        .find(owner_identity)
+        .ok_or("house not found".to_string())?;
+
+    if house.locked_until > ctx.timestamp {
+        return Err("house is locked while moving".to_string());
+    }
+
+    let dimension = ctx
+        .db
+        .dimension_desc()
+        .dimension_id()
+        .find(house.dimension_id)
+        .ok_or("interior dimension missing".to_string())?;
+    if dimension.collapse_at.is_some() {
+        return Err("interior has collapsed and is regenerating".to_string());
+    }
+
+    if owner_identity != ctx.sender
+        && !permissions::has_permission(ctx, 3, house.dimension_id, permissions::PERM_ENTER)
+    {
+        return Err("no permission to enter house".to_string());
+    }
+
+    let session = ctx
+        .db
+        .session_state()
+        .identity()
+        .find(ctx.sender)
+        .ok_or("active session required".to_string())?;
+    let entrance = ctx
+        .db
+        .building_state()
+        .entity_id()
+        .find(house.entrance_building_id)
+        .ok_or("entrance building missing".to_string())?;
+    if session.region_id != entrance.region_id {
+        return Err("entrance is in another region".to_string());
+    }
+
+    let transform = ctx
+        .db
+        .transform_state()
+        .entity_id()
+        .find(ctx.sender)
+        .ok_or("transform missing".to_string())?;
+    let dx = transform.position[0] - entrance.hex_x as f32;
+    let dz = transform.position[2] - entrance.hex_z as f32;
+    if dx * dx + dz * dz > ENTRANCE_RANGE_SQ {
+        return Err("too far from house entrance".to_string());
+    }
+
+    let visit = HousingVisitState {
+        identity: ctx.sender,
+        dimension_id: house.dimension_id,
+        return_region_id: transform.region_id,
+        return_position: transform.position.clone(),
+        entered_at: ctx.timestamp,
+    };
+    if ctx.db.housing_visit_state().identity().find(ctx.sender).is_some() {
+        ctx.db.housing_visit_state().identity().update(visit);
+    } else {
+        ctx.db.housing_visit_state().insert(visit);
+    }
+
+    housing::relocate(
+        ctx,
+        ctx.sender,
+        housing::interior_region_id(house.dimension_id),
+        vec![dimension.spawn_x, 0.0, dimension.spawn_z],
+    );
+
+    house.is_empty = false;
+    if owner_identity == ctx.sender {
+        house.last_visited_at = ctx.timestamp;
+    }
+    house.updated_at = ctx.timestamp;
+    ctx.db.housing_state().owner_identity().update(house);
+
+    Ok(())
+}
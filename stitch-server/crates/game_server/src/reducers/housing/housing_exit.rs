@@ -0,0 +1,40 @@
+use spacetimedb::{ReducerContext, Table};
+
+use crate::services::housing;
+use crate::tables::building_state::building_state;
+use crate::tables::housing::{housing_state, housing_visit_state};
+
+#[spacetimedb::reducer]
+pub fn housing_exit(ctx: &ReducerContext) -> Result<(), String> {
+    let visit = ctx
+        .db
+        .housing_visit_state()
+        .identity()
+        .find(ctx.sender)
+        .ok_or("not inside a house".to_string())?;
+
+    // Return through the current entrance, which may have moved since entering.
+    let house = ctx
+        .db
+        .housing_state()
+        .iter()
+        .find(|h| h.dimension_id == visit.dimension_id);
+    let (region_id, position) = match house
+        .as_ref()
+        .and_then(|h| ctx.db.building_state().entity_id().find(h.entrance_building_id))
+    {
+        Some(entrance) => (entrance.region_id, vec![entrance.hex_x as f32, 0.0, entrance.hex_z as f32]),
+        None => (visit.return_region_id, visit.return_position.clone()),
+    };
+
+    ctx.db.housing_visit_state().identity().delete(ctx.sender);
+    housing::relocate(ctx, ctx.sender, region_id, position);
+
+    if let Some(mut house) = house {
+        house.is_empty = housing::occupant_count(ctx, house.dimension_id) == 0;
+        house.updated_at = ctx.timestamp;
+        ctx.db.housing_state().owner_identity().update(house);
+    }
+
+    Ok(())
+}
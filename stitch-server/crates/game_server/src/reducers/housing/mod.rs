@@ -0,0 +1,4 @@
+pub mod housing_change_entrance;
+pub mod housing_create;
+pub mod housing_enter;
+pub mod housing_exit;
@@ -0,0 +1,85 @@
+use spacetimedb::{ReducerContext, Table};
+
+use crate::services::{abilities, equipment, inventory, skills, stats};
+use crate::tables::EquipmentSlot;
+use crate::tables::equipment::{equipment_def, equipment_slot};
+use crate::tables::inventory_slot::inventory_slot;
+use crate::tables::item_instance::item_instance;
+use crate::tables::item_stack::item_stack;
+
+use super::inventory_bootstrap::slot_key;
+use super::inventory_lock::ensure_not_locked;
+
+/// Moves an item from a main-inventory slot into its equipment slot. Whatever
+/// was equipped there before takes the freed inventory slot.
+#[spacetimedb::reducer]
+pub fn equip(ctx: &ReducerContext, inventory_slot_index: u32) -> Result<(), String> {
+    let container = inventory::main_container(ctx, ctx.sender)?;
+    ensure_not_locked(ctx, container.container_id)?;
+
+    let mut inv_slot = ctx
+        .db
+        .inventory_slot()
+        .slot_key()
+        .find(slot_key(container.container_id, inventory_slot_index))
+        .ok_or("inventory slot not found".to_string())?;
+    if inv_slot.locked {
+        return Err("slot is locked".to_string());
+    }
+    if inv_slot.item_instance_id == 0 {
+        return Err("inventory slot is empty".to_string());
+    }
+
+    let instance = ctx
+        .db
+        .item_instance()
+        .item_instance_id()
+        .find(inv_slot.item_instance_id)
+        .ok_or("item instance missing".to_string())?;
+    let def = ctx
+        .db
+        .equipment_def()
+        .item_def_id()
+        .find(instance.item_def_id)
+        .ok_or("item is not equippable".to_string())?;
+    skills::require(ctx, ctx.sender, def.skill_id, def.skill_level)?;
+
+    let quantity = ctx
+        .db
+        .item_stack()
+        .item_instance_id()
+        .find(instance.item_instance_id)
+        .map(|s| s.quantity)
+        .unwrap_or(0);
+    if quantity != 1 {
+        return Err("only a single item can be equipped".to_string());
+    }
+
+    let key = equipment::slot_key(ctx.sender, def.slot);
+    let previous = ctx.db.equipment_slot().slot_key().find(key.clone());
+    inv_slot.item_instance_id = previous.as_ref().map(|p| p.item_instance_id).unwrap_or(0);
+    ctx.db.inventory_slot().slot_key().update(inv_slot);
+
+    match previous {
+        Some(mut slot) => {
+            slot.item_instance_id = instance.item_instance_id;
+            ctx.db.equipment_slot().slot_key().update(slot);
+        }
+        None => {
+            ctx.db.equipment_slot().insert(EquipmentSlot {
+                slot_key: key,
+                identity: ctx.sender,
+                slot: def.slot,
+                item_instance_id: instance.item_instance_id,
+            });
+        }
+    }
+
+    stats::recompute(ctx, ctx.sender);
+    abilities::refresh_loadout(ctx, ctx.sender);
+    // A swapped-out pack can shrink carry volume below what is already held.
+    if inventory::volume_headroom(ctx, ctx.sender)? < 0 {
+        return Err("carrying too much".to_string());
+    }
+    Ok(())
+}
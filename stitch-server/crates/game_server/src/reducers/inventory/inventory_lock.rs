@@ -1,5 +1,6 @@
 use spacetimedb::{ReducerContext, Table};
 
+use crate::services::permissions;
 use crate::tables::InventoryLock;
 use crate::tables::inventory_container::inventory_container;
 use crate::tables::inventory_lock::inventory_lock as inventory_lock_table;
@@ -59,3 +60,22 @@ pub(crate) fn ensure_owner(ctx: &ReducerContext, container_id: u64) -> Result<()
 
     Ok(())
 }
+
+/// Owner, or a player granted container access directly or through their
+/// party.
+pub(crate) fn ensure_access(ctx: &ReducerContext, container_id: u64) -> Result<(), String> {
+    let container = ctx
+        .db
+        .inventory_container()
+        .container_id()
+        .find(container_id)
+        .ok_or("container not found".to_string())?;
+
+    if container.owner_identity != ctx.sender
+        && !permissions::has_permission(ctx, 4, container_id, permissions::PERM_CONTAINER)
+    {
+        return Err("unauthorized inventory access".to_string());
+    }
+
+    Ok(())
+}
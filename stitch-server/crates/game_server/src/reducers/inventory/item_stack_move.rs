@@ -8,7 +8,7 @@ use crate::tables::item_instance::item_instance;
 use crate::tables::item_stack::item_stack;
 
 use super::inventory_bootstrap::{next_item_instance_id, slot_key};
-use super::inventory_lock::{ensure_not_locked, ensure_owner};
+use super::inventory_lock::{ensure_access, ensure_not_locked};
 
 #[spacetimedb::reducer]
 pub fn item_stack_move(
@@ -25,7 +25,7 @@ pub fn item_stack_move(
         return Ok(());
     }
 
-    ensure_owner(ctx, container_id)?;
+    ensure_access(ctx, container_id)?;
     ensure_not_locked(ctx, container_id)?;
 
     let from_key = slot_key(container_id, from_slot_index);
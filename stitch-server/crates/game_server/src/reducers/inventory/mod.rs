@@ -1,3 +1,5 @@
+pub mod equip;
 pub mod inventory_bootstrap;
 pub mod inventory_lock;
 pub mod item_stack_move;
+pub mod unequip;
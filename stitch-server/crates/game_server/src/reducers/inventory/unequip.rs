@@ -0,0 +1,44 @@
+use spacetimedb::{ReducerContext, Table};
+
+use crate::services::{abilities, equipment, inventory, stats};
+use crate::tables::equipment::equipment_slot;
+use crate::tables::inventory_slot::inventory_slot;
+
+use super::inventory_lock::ensure_not_locked;
+
+/// Moves an equipped item back into the first free main-inventory slot.
+#[spacetimedb::reducer]
+pub fn unequip(ctx: &ReducerContext, slot: u8) -> Result<(), String> {
+    if !equipment::is_valid_slot(slot) {
+        return Err("invalid equipment slot".to_string());
+    }
+    let equipped = ctx
+        .db
+        .equipment_slot()
+        .slot_key()
+        .find(equipment::slot_key(ctx.sender, slot))
+        .ok_or("nothing equipped in that slot".to_string())?;
+
+    let container = inventory::main_container(ctx, ctx.sender)?;
+    ensure_not_locked(ctx, container.container_id)?;
+
+    let mut free_slot = ctx
+        .db
+        .inventory_slot()
+        .iter()
+        .filter(|s| s.container_id == container.container_id)
+        .filter(|s| s.item_instance_id == 0 && !s.locked && s.item_type == 0)
+        .min_by_key(|s| s.slot_index)
+        .ok_or("no free inventory slot".to_string())?;
+
+    free_slot.item_instance_id = equipped.item_instance_id;
+    ctx.db.inventory_slot().slot_key().update(free_slot);
+    ctx.db.equipment_slot().slot_key().delete(equipped.slot_key);
+
+    stats::recompute(ctx, ctx.sender);
+    abilities::refresh_loadout(ctx, ctx.sender);
+    if inventory::volume_headroom(ctx, ctx.sender)? < 0 {
+        return Err("carrying too much".to_string());
+    }
+    Ok(())
+}
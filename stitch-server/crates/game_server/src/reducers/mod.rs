@@ -1,7 +1,15 @@
+pub mod admin;
 pub mod building;
 pub mod claim;
 pub mod combat;
+pub mod craft;
+pub mod empire;
+pub mod housing;
 pub mod inventory;
+pub mod moderation;
 pub mod npc_quest;
+pub mod party;
 pub mod player;
+pub mod resource;
+pub mod social;
 pub mod trade_market;
@@ -0,0 +1,3 @@
+pub mod moderation_action_apply;
+pub mod report_resolve;
+pub mod report_triage;
@@ -0,0 +1,43 @@
+use spacetimedb::{Identity, ReducerContext};
+
+use crate::services::moderation;
+use crate::tables::moderation::report_queue;
+
+/// Warns (1), mutes (2), bans (3) or restores (4) `target`. Mutes need a
+/// duration; a ban with `duration_secs = 0` is permanent. A non-zero
+/// `report_id` resolves that report with the new action linked.
+#[spacetimedb::reducer]
+pub fn moderation_action_apply(
+    ctx: &ReducerContext,
+    target: Identity,
+    action: u8,
+    duration_secs: u64,
+    reason: String,
+    report_id: u64,
+) -> Result<(), String> {
+    if !moderation::is_moderator(ctx) {
+        return Err("moderation_action_apply requires moderator authorization".to_string());
+    }
+    if reason.trim().is_empty() {
+        return Err("reason must not be empty".to_string());
+    }
+    let report = match report_id {
+        0 => None,
+        id => {
+            let report = ctx.db.report_queue().report_id().find(id).ok_or("report not found".to_string())?;
+            if report.target_id != target {
+                return Err("report is about another player".to_string());
+            }
+            if !moderation::is_pending(&report) {
+                return Err("report is already closed".to_string());
+            }
+            Some(report)
+        }
+    };
+
+    let record = moderation::apply_action(ctx, target, action, duration_secs, &reason)?;
+    if let Some(report) = report {
+        moderation::close_report(ctx, report, moderation::REPORT_RESOLVED, record.action_id, &reason);
+    }
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use spacetimedb::ReducerContext;
+
+use crate::services::moderation;
+use crate::tables::moderation::{moderation_action, report_queue};
+
+/// Closes a pending report. A non-zero `action_id` links an existing
+/// moderation action on the reported player and resolves the report;
+/// `action_id = 0` dismisses it.
+#[spacetimedb::reducer]
+pub fn report_resolve(
+    ctx: &ReducerContext,
+    report_id: u64,
+    action_id: u64,
+    resolution: String,
+) -> Result<(), String> {
+    if !moderation::is_moderator(ctx) {
+        return Err("report_resolve requires moderator authorization".to_string());
+    }
+    let report = ctx.db.report_queue().report_id().find(report_id).ok_or("report not found".to_string())?;
+    if !moderation::is_pending(&report) {
+        return Err("report is already closed".to_string());
+    }
+    if action_id == 0 {
+        moderation::close_report(ctx, report, moderation::REPORT_DISMISSED, 0, &resolution);
+        return Ok(());
+    }
+    let action = ctx
+        .db
+        .moderation_action()
+        .action_id()
+        .find(action_id)
+        .ok_or("moderation action not found".to_string())?;
+    if action.target_id != report.target_id {
+        return Err("moderation action targets another player".to_string());
+    }
+    moderation::close_report(ctx, report, moderation::REPORT_RESOLVED, action_id, &resolution);
+    Ok(())
+}
@@ -0,0 +1,25 @@
+use spacetimedb::ReducerContext;
+
+use crate::services::moderation;
+use crate::tables::moderation::report_queue;
+
+/// Takes a pending report and sets its priority (1-3).
+#[spacetimedb::reducer]
+pub fn report_triage(ctx: &ReducerContext, report_id: u64, priority: u8) -> Result<(), String> {
+    if !moderation::is_moderator(ctx) {
+        return Err("report_triage requires moderator authorization".to_string());
+    }
+    if !(moderation::PRIORITY_LOW..=moderation::PRIORITY_HIGH).contains(&priority) {
+        return Err("priority must be 1-3".to_string());
+    }
+    let mut report = ctx.db.report_queue().report_id().find(report_id).ok_or("report not found".to_string())?;
+    if !moderation::is_pending(&report) {
+        return Err("report is already closed".to_string());
+    }
+    report.status = moderation::REPORT_TRIAGED;
+    report.priority = priority;
+    report.assigned_to = Some(ctx.sender);
+    report.updated_at = ctx.timestamp;
+    ctx.db.report_queue().report_id().update(report);
+    Ok(())
+}
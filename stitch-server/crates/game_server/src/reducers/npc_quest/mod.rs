@@ -1,6 +1,9 @@
-pub mod agent_tick;
-pub mod npc_quest;
+pub mod npc_action_claim;
+pub mod npc_action_submit;
+pub mod npc_conversation;
+pub mod npc_quest_request;
 pub mod npc_talk;
 pub mod npc_trade;
+pub mod path_request;
 pub mod quest_chain_start;
 pub mod quest_stage_complete;
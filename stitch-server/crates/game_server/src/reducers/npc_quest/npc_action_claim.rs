@@ -0,0 +1,41 @@
+use spacetimedb::{ReducerContext, Table};
+
+use crate::services::npc_agent;
+use crate::tables::{NpcActionLease, NpcActionRequest};
+use crate::tables::npc_action::{npc_action_lease, npc_action_request, npc_worker};
+
+const MAX_CLAIM_BATCH: u32 = 16;
+
+/// Leases up to `max_requests` pending requests, oldest first, to the calling
+/// worker. The leased copies appear in `npc_action_lease`.
+#[spacetimedb::reducer]
+pub fn npc_action_claim(ctx: &ReducerContext, max_requests: u32) -> Result<(), String> {
+    let Some(mut worker) = ctx.db.npc_worker().identity().find(ctx.sender) else {
+        return Err("npc_action_claim requires a registered npc worker".to_string());
+    };
+    worker.last_seen_at = ctx.timestamp;
+    ctx.db.npc_worker().identity().update(worker);
+
+    let mut pending: Vec<NpcActionRequest> =
+        ctx.db.npc_action_request().status().filter(npc_agent::REQUEST_PENDING).collect();
+    pending.sort_by_key(|r| r.request_id);
+
+    let expires_at = ctx.timestamp + npc_agent::lease_duration(ctx);
+    for mut request in pending.into_iter().take(max_requests.clamp(1, MAX_CLAIM_BATCH) as usize) {
+        ctx.db.npc_action_lease().insert(NpcActionLease {
+            request_id: request.request_id,
+            worker_identity: ctx.sender,
+            npc_id: request.npc_id,
+            action_type: request.action_type,
+            context: request.context.clone(),
+            expires_at,
+        });
+        request.status = npc_agent::REQUEST_LEASED;
+        request.attempts += 1;
+        request.lease_owner = Some(ctx.sender);
+        request.lease_expires_at = Some(expires_at);
+        request.updated_at = ctx.timestamp;
+        ctx.db.npc_action_request().request_id().update(request);
+    }
+    Ok(())
+}
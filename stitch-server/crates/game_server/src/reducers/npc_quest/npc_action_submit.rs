@@ -0,0 +1,62 @@
+use spacetimedb::ReducerContext;
+
+use crate::services::{npc_agent, npc_cost, npc_policy};
+use crate::tables::{NpcActionProposal, NpcActionRequest, NpcActionUsage};
+use crate::tables::npc_action::npc_action_request;
+
+fn leased_request(ctx: &ReducerContext, request_id: u64) -> Result<NpcActionRequest, String> {
+    if !npc_agent::is_worker(ctx, ctx.sender) {
+        return Err("requires a registered npc worker".to_string());
+    }
+    let request = ctx
+        .db
+        .npc_action_request()
+        .request_id()
+        .find(request_id)
+        .ok_or("npc action request not found".to_string())?;
+    if request.status != npc_agent::REQUEST_LEASED || request.lease_owner != Some(ctx.sender) {
+        return Err("request is not leased by caller".to_string());
+    }
+    if request.lease_expires_at.is_some_and(|t| t < ctx.timestamp) {
+        return Err("lease expired".to_string());
+    }
+    Ok(request)
+}
+
+/// Worker answer for a leased request, with the model usage behind it.
+/// Proposals breaking npc policy are logged as violations and, like proposals
+/// that fail to apply, recorded as rejected with the scripted fallback reply.
+#[spacetimedb::reducer]
+pub fn npc_action_submit(
+    ctx: &ReducerContext,
+    request_id: u64,
+    proposal: NpcActionProposal,
+    usage: NpcActionUsage,
+) -> Result<(), String> {
+    let request = leased_request(ctx, request_id)?;
+    npc_cost::record_usage(ctx, request.npc_id, request.region_id, &usage);
+
+    if let Err(violation) = npc_policy::check(ctx, &request, &proposal) {
+        npc_policy::record_violation(ctx, &request, &proposal, &violation);
+        let fallback = npc_agent::FALLBACK_REPLY.to_string();
+        npc_agent::finish(ctx, request, npc_agent::RESULT_REJECTED, fallback, violation.reason);
+        return Ok(());
+    }
+    match npc_agent::apply(ctx, &request, &proposal) {
+        Ok(detail) => npc_agent::finish(ctx, request, npc_agent::RESULT_APPLIED, proposal.reply, detail),
+        Err(reason) => {
+            log::info!("npc action rejected: request_id={request_id} reason={reason}");
+            let fallback = npc_agent::FALLBACK_REPLY.to_string();
+            npc_agent::finish(ctx, request, npc_agent::RESULT_REJECTED, fallback, reason);
+        }
+    }
+    Ok(())
+}
+
+/// Worker gives up on a leased request; it is retried until attempts run out.
+#[spacetimedb::reducer]
+pub fn npc_action_fail(ctx: &ReducerContext, request_id: u64, reason: String) -> Result<(), String> {
+    let request = leased_request(ctx, request_id)?;
+    npc_agent::release(ctx, request, &reason);
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use spacetimedb::ReducerContext;
+
+use crate::services::{npc_agent, npc_memory};
+use crate::tables::NpcConversationSession;
+use crate::tables::npc_conversation::npc_conversation_session;
+
+use super::npc_talk::npc_in_reach;
+
+fn own_active_session(ctx: &ReducerContext, session_id: u64) -> Result<NpcConversationSession, String> {
+    let session = ctx
+        .db
+        .npc_conversation_session()
+        .session_id()
+        .find(session_id)
+        .ok_or("conversation not found".to_string())?;
+    if session.identity != ctx.sender {
+        return Err("not your conversation".to_string());
+    }
+    if session.status != npc_memory::SESSION_ACTIVE {
+        return Err("conversation is closed".to_string());
+    }
+    Ok(session)
+}
+
+/// Opens a conversation with an npc, or keeps the caller's active one.
+#[spacetimedb::reducer]
+pub fn npc_conversation_open(ctx: &ReducerContext, npc_id: u64) -> Result<(), String> {
+    npc_in_reach(ctx, npc_id, npc_agent::ACTION_TALK)?;
+    npc_memory::open_session(ctx, npc_id, ctx.sender);
+    Ok(())
+}
+
+/// Adds a player turn and queues the npc's answer for the worker.
+#[spacetimedb::reducer]
+pub fn npc_conversation_say(ctx: &ReducerContext, session_id: u64, text: String) -> Result<(), String> {
+    if text.trim().is_empty() {
+        return Err("text must not be empty".to_string());
+    }
+    let session = own_active_session(ctx, session_id)?;
+    let npc = npc_in_reach(ctx, session.npc_id, npc_agent::ACTION_TALK)?;
+
+    npc_memory::record_input(ctx, session, &text);
+    npc_agent::enqueue(ctx, &npc, ctx.sender, npc_agent::ACTION_TALK, session_id, &text);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn npc_conversation_close(ctx: &ReducerContext, session_id: u64) -> Result<(), String> {
+    let session = own_active_session(ctx, session_id)?;
+    npc_memory::close_session(ctx, session, npc_memory::SESSION_CLOSED);
+    Ok(())
+}
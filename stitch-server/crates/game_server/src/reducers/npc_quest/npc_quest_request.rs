@@ -1,13 +1,10 @@
 use spacetimedb::{ReducerContext, Table};
 
+use crate::services::{npc_agent, npc_policy};
 use crate::tables::NpcInteractionLog;
 use crate::tables::npc_quest::npc_interaction_log;
-use crate::tables::session_state::session_state;
-use crate::tables::transform_state::transform_state;
 
-use super::npc_talk::ensure_npc;
-
-const NPC_INTERACTION_RANGE_SQ: f32 = 100.0;
+use super::npc_talk::npc_in_reach;
 
 #[spacetimedb::reducer]
 pub fn npc_quest(ctx: &ReducerContext, npc_id: u64, request_id: String) -> Result<(), String> {
@@ -16,24 +13,9 @@ pub fn npc_quest(ctx: &ReducerContext, npc_id: u64, request_id: String) -> Resul
         return Err("request_id must not be empty".to_string());
     }
 
-    let session = ctx
-        .db
-        .session_state()
-        .identity()
-        .find(ctx.sender)
-        .ok_or("active session required".to_string())?;
-    let caller_tf = ctx
-        .db
-        .transform_state()
-        .entity_id()
-        .find(ctx.sender)
-        .ok_or("caller transform missing".to_string())?;
-
-    let npc = ensure_npc(ctx, npc_id, session.region_id, &caller_tf.position);
-    let dx = caller_tf.position[0] - npc.pos_x;
-    let dz = caller_tf.position[2] - npc.pos_z;
-    if dx * dx + dz * dz > NPC_INTERACTION_RANGE_SQ {
-        return Err("npc is too far for quest".to_string());
+    let npc = npc_in_reach(ctx, npc_id, npc_agent::ACTION_QUEST)?;
+    if npc.role != npc_policy::ROLE_QUEST_GIVER {
+        return Err("npc is not a quest giver".to_string());
     }
 
     let interaction_key = format!("quest:{}:{}", ctx.sender, req);
@@ -53,10 +35,11 @@ pub fn npc_quest(ctx: &ReducerContext, npc_id: u64, request_id: String) -> Resul
         caller_identity: ctx.sender,
         interaction_kind: 3,
         status: 1,
-        detail: "quest dialog accepted".to_string(),
+        detail: "quest dialog queued".to_string(),
         created_at: ctx.timestamp,
         updated_at: ctx.timestamp,
     });
+    npc_agent::enqueue(ctx, &npc, ctx.sender, npc_agent::ACTION_QUEST, 0, "");
 
     Ok(())
 }
@@ -1,5 +1,6 @@
 use spacetimedb::{ReducerContext, Table};
 
+use crate::services::{achievements, npc_agent, npc_memory, npc_schedule, quests};
 use crate::tables::{NpcInteractionLog, NpcState};
 use crate::tables::npc_quest::npc_interaction_log;
 use crate::tables::npc_quest::npc_state;
@@ -15,25 +16,7 @@ pub fn npc_talk(ctx: &ReducerContext, npc_id: u64, request_id: String) -> Result
         return Err("request_id must not be empty".to_string());
     }
 
-    let session = ctx
-        .db
-        .session_state()
-        .identity()
-        .find(ctx.sender)
-        .ok_or("active session required".to_string())?;
-    let caller_tf = ctx
-        .db
-        .transform_state()
-        .entity_id()
-        .find(ctx.sender)
-        .ok_or("caller transform missing".to_string())?;
-
-    let npc = ensure_npc(ctx, npc_id, session.region_id, &caller_tf.position);
-    let dx = caller_tf.position[0] - npc.pos_x;
-    let dz = caller_tf.position[2] - npc.pos_z;
-    if dx * dx + dz * dz > NPC_INTERACTION_RANGE_SQ {
-        return Err("npc is too far to talk".to_string());
-    }
+    let npc = npc_in_reach(ctx, npc_id, npc_agent::ACTION_TALK)?;
 
     let interaction_key = format!("talk:{}:{}", ctx.sender, req);
     if ctx
@@ -52,39 +35,43 @@ pub fn npc_talk(ctx: &ReducerContext, npc_id: u64, request_id: String) -> Result
         caller_identity: ctx.sender,
         interaction_kind: 1,
         status: 1,
-        detail: "talk accepted".to_string(),
+        detail: "talk queued".to_string(),
         created_at: ctx.timestamp,
         updated_at: ctx.timestamp,
     });
+    npc_agent::enqueue(ctx, &npc, ctx.sender, npc_agent::ACTION_TALK, 0, "");
+    npc_memory::adjust_relation(ctx, npc_id, ctx.sender, 0, 0);
+    // credit goes to the looked-up npc row, so invented ids never count as talks or discoveries
+    quests::record(ctx, ctx.sender, quests::COND_TALK, npc.npc_id, 1);
+    achievements::discover(ctx, ctx.sender, achievements::KNOWLEDGE_NPC, npc.npc_id);
 
     Ok(())
 }
 
-pub(crate) fn ensure_npc(
-    ctx: &ReducerContext,
-    npc_id: u64,
-    region_id: u64,
-    fallback_pos: &[f32],
-) -> NpcState {
-    if let Some(npc) = ctx.db.npc_state().npc_id().find(npc_id) {
-        return npc;
+/// Looks up an existing npc in the caller's region and interaction range that its current
+/// activity leaves available for `action_type`.
+pub(crate) fn npc_in_reach(ctx: &ReducerContext, npc_id: u64, action_type: u8) -> Result<NpcState, String> {
+    let session = ctx
+        .db
+        .session_state()
+        .identity()
+        .find(ctx.sender)
+        .ok_or("active session required".to_string())?;
+    let caller_tf = ctx
+        .db
+        .transform_state()
+        .entity_id()
+        .find(ctx.sender)
+        .ok_or("caller transform missing".to_string())?;
+    let npc = ctx.db.npc_state().npc_id().find(npc_id).ok_or("npc not found".to_string())?;
+    if npc.region_id != session.region_id {
+        return Err("npc is not in this region".to_string());
     }
-
-    let npc = NpcState {
-        npc_id,
-        region_id,
-        pos_x: fallback_pos.first().copied().unwrap_or(0.0),
-        pos_z: fallback_pos.get(2).copied().unwrap_or(0.0),
-        schedule_kind: 1,
-        updated_at: ctx.timestamp,
-    };
-    ctx.db.npc_state().insert(NpcState {
-        npc_id: npc.npc_id,
-        region_id: npc.region_id,
-        pos_x: npc.pos_x,
-        pos_z: npc.pos_z,
-        schedule_kind: npc.schedule_kind,
-        updated_at: npc.updated_at,
-    });
-    npc
+    let dx = caller_tf.position[0] - npc.pos_x;
+    let dz = caller_tf.position[2] - npc.pos_z;
+    if dx * dx + dz * dz > NPC_INTERACTION_RANGE_SQ {
+        return Err("npc is too far".to_string());
+    }
+    npc_schedule::require_available(&npc, action_type)?;
+    Ok(npc)
 }
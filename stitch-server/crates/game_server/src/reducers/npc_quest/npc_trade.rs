@@ -1,39 +1,34 @@
 use spacetimedb::{ReducerContext, Table};
 
+use crate::services::{npc_agent, npc_policy, npc_shop};
 use crate::tables::NpcInteractionLog;
 use crate::tables::npc_quest::npc_interaction_log;
-use crate::tables::session_state::session_state;
-use crate::tables::transform_state::transform_state;
 
-use super::npc_talk::ensure_npc;
-
-const NPC_INTERACTION_RANGE_SQ: f32 = 100.0;
+use super::npc_talk::npc_in_reach;
 
+/// Buys from (`side = 0`) or sells to (`side = 1`) an npc vendor.
+/// `price_limit` is the highest unit price paid, or the lowest accepted.
 #[spacetimedb::reducer]
-pub fn npc_trade(ctx: &ReducerContext, npc_id: u64, request_id: String) -> Result<(), String> {
+pub fn npc_trade(
+    ctx: &ReducerContext,
+    npc_id: u64,
+    request_id: String,
+    item_def_id: u64,
+    quantity: u32,
+    side: u8,
+    price_limit: u64,
+) -> Result<(), String> {
     let req = request_id.trim();
     if req.is_empty() {
         return Err("request_id must not be empty".to_string());
     }
+    if quantity == 0 {
+        return Err("quantity must be > 0".to_string());
+    }
 
-    let session = ctx
-        .db
-        .session_state()
-        .identity()
-        .find(ctx.sender)
-        .ok_or("active session required".to_string())?;
-    let caller_tf = ctx
-        .db
-        .transform_state()
-        .entity_id()
-        .find(ctx.sender)
-        .ok_or("caller transform missing".to_string())?;
-
-    let npc = ensure_npc(ctx, npc_id, session.region_id, &caller_tf.position);
-    let dx = caller_tf.position[0] - npc.pos_x;
-    let dz = caller_tf.position[2] - npc.pos_z;
-    if dx * dx + dz * dz > NPC_INTERACTION_RANGE_SQ {
-        return Err("npc is too far to trade".to_string());
+    let npc = npc_in_reach(ctx, npc_id, npc_agent::ACTION_TRADE)?;
+    if npc.role != npc_policy::ROLE_MERCHANT {
+        return Err("npc is not a merchant".to_string());
     }
 
     let interaction_key = format!("trade:{}:{}", ctx.sender, req);
@@ -47,16 +42,22 @@ pub fn npc_trade(ctx: &ReducerContext, npc_id: u64, request_id: String) -> Resul
         return Ok(());
     }
 
+    let detail = match side {
+        npc_shop::SIDE_BUY => npc_shop::buy(ctx, &npc, ctx.sender, item_def_id, quantity, price_limit)?,
+        npc_shop::SIDE_SELL => npc_shop::sell(ctx, &npc, ctx.sender, item_def_id, quantity, price_limit)?,
+        _ => return Err("side must be 0 (buy) or 1 (sell)".to_string()),
+    };
     ctx.db.npc_interaction_log().insert(NpcInteractionLog {
         interaction_key,
         npc_id,
         caller_identity: ctx.sender,
         interaction_kind: 2,
         status: 1,
-        detail: "trade accepted".to_string(),
+        detail: detail.clone(),
         created_at: ctx.timestamp,
         updated_at: ctx.timestamp,
     });
+    npc_agent::enqueue(ctx, &npc, ctx.sender, npc_agent::ACTION_TRADE, 0, &detail);
 
     Ok(())
 }
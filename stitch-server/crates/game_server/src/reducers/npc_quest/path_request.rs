@@ -0,0 +1,33 @@
+use spacetimedb::ReducerContext;
+
+use crate::services::{pathfinding, permissions, terrain};
+use crate::tables::npc_quest::npc_state;
+use crate::utils::hex::HexCoordinates;
+
+/// Plans a path for an NPC; the NPC mover agent walks it.
+#[spacetimedb::reducer]
+pub fn path_request(
+    ctx: &ReducerContext,
+    npc_id: u64,
+    target_x: i32,
+    target_z: i32,
+    node_limit: u32,
+) -> Result<(), String> {
+    if ctx.sender != ctx.identity() {
+        permissions::require_admin(ctx, "path_request")?;
+    }
+
+    let npc = ctx.db.npc_state().npc_id().find(npc_id).ok_or("npc not found")?;
+    let start = terrain::hex_at(npc.pos_x, npc.pos_z);
+    let goal = HexCoordinates::new(target_x, target_z);
+    let limit = if node_limit == 0 { pathfinding::DEFAULT_NODE_LIMIT } else { node_limit };
+
+    let path = pathfinding::find_region_path(ctx, npc.region_id, start, goal, limit)?;
+    let nav_path = pathfinding::store_npc_path(ctx, npc_id, npc.region_id, &path);
+    log::info!(
+        "npc path planned: npc_id={npc_id} path_id={} steps={}",
+        nav_path.path_id,
+        path.len().saturating_sub(1)
+    );
+    Ok(())
+}
@@ -0,0 +1,7 @@
+pub mod party_accept;
+pub mod party_invite;
+pub mod party_kick;
+pub mod party_leave;
+pub mod party_loot_rule_set;
+pub mod party_permission_set;
+pub mod party_promote;
@@ -0,0 +1,36 @@
+use spacetimedb::{ReducerContext, Table};
+
+use crate::services::party;
+use crate::tables::PartyMember;
+use crate::tables::party::{party_invitation, party_member, party_state};
+
+#[spacetimedb::reducer]
+pub fn party_accept(ctx: &ReducerContext, party_id: u64) -> Result<(), String> {
+    let key = party::invite_key(party_id, ctx.sender);
+    let invite = ctx
+        .db
+        .party_invitation()
+        .invite_key()
+        .find(key.clone())
+        .ok_or("no party invite".to_string())?;
+    if invite.expires_at <= ctx.timestamp {
+        return Err("party invite expired".to_string());
+    }
+    if ctx.db.party_member().member_identity().find(ctx.sender).is_some() {
+        return Err("already in a party".to_string());
+    }
+    let mut party = ctx.db.party_state().party_id().find(party_id).ok_or("party not found".to_string())?;
+    if party::members(ctx, party_id).len() >= party::max_size(ctx) {
+        return Err("party is full".to_string());
+    }
+
+    ctx.db.party_invitation().invite_key().delete(key);
+    ctx.db.party_member().insert(PartyMember {
+        member_identity: ctx.sender,
+        party_id,
+        joined_at: ctx.timestamp,
+    });
+    party.updated_at = ctx.timestamp;
+    ctx.db.party_state().party_id().update(party);
+    Ok(())
+}
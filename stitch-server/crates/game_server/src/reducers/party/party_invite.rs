@@ -0,0 +1,56 @@
+use spacetimedb::{Identity, ReducerContext, Table};
+
+use crate::services::party;
+use crate::tables::{PartyInvitation, PartyMember, PartyState};
+use crate::tables::party::{party_invitation, party_member, party_state};
+use crate::tables::player_state::player_state;
+
+/// Invites `invitee` to the caller's party, founding one with the caller as
+/// leader if they are not in a party yet. Only the leader can invite.
+#[spacetimedb::reducer]
+pub fn party_invite(ctx: &ReducerContext, invitee: Identity) -> Result<(), String> {
+    if invitee == ctx.sender {
+        return Err("cannot invite yourself".to_string());
+    }
+    if ctx.db.player_state().player_id().find(invitee).is_none() {
+        return Err("player not found".to_string());
+    }
+    if ctx.db.party_member().member_identity().find(invitee).is_some() {
+        return Err("player is already in a party".to_string());
+    }
+
+    let party = match party::party_of(ctx, ctx.sender) {
+        Some(_) => party::require_leader(ctx)?,
+        None => {
+            let party = ctx.db.party_state().insert(PartyState {
+                party_id: 0,
+                leader_identity: ctx.sender,
+                loot_rule: party::LOOT_FREE_FOR_ALL,
+                loot_cursor: 0,
+                created_at: ctx.timestamp,
+                updated_at: ctx.timestamp,
+            });
+            ctx.db.party_member().insert(PartyMember {
+                member_identity: ctx.sender,
+                party_id: party.party_id,
+                joined_at: ctx.timestamp,
+            });
+            party
+        }
+    };
+    if party::members(ctx, party.party_id).len() >= party::max_size(ctx) {
+        return Err("party is full".to_string());
+    }
+
+    party::purge_expired_invites(ctx);
+    let key = party::invite_key(party.party_id, invitee);
+    ctx.db.party_invitation().invite_key().delete(key.clone());
+    ctx.db.party_invitation().insert(PartyInvitation {
+        invite_key: key,
+        party_id: party.party_id,
+        invitee_identity: invitee,
+        inviter_identity: ctx.sender,
+        expires_at: ctx.timestamp + party::INVITE_TTL,
+    });
+    Ok(())
+}
@@ -0,0 +1,17 @@
+use spacetimedb::{Identity, ReducerContext};
+
+use crate::services::party;
+use crate::tables::party::party_member;
+
+#[spacetimedb::reducer]
+pub fn party_kick(ctx: &ReducerContext, member: Identity) -> Result<(), String> {
+    let party = party::require_leader(ctx)?;
+    if member == ctx.sender {
+        return Err("use party_leave to leave the party".to_string());
+    }
+    if ctx.db.party_member().member_identity().find(member).is_none_or(|m| m.party_id != party.party_id) {
+        return Err("player is not in your party".to_string());
+    }
+    party::remove_member(ctx, party, member);
+    Ok(())
+}
@@ -0,0 +1,10 @@
+use spacetimedb::ReducerContext;
+
+use crate::services::party;
+
+#[spacetimedb::reducer]
+pub fn party_leave(ctx: &ReducerContext) -> Result<(), String> {
+    let party = party::party_of(ctx, ctx.sender).ok_or("not in a party".to_string())?;
+    party::remove_member(ctx, party, ctx.sender);
+    Ok(())
+}
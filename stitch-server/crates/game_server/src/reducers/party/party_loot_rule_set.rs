@@ -0,0 +1,18 @@
+use spacetimedb::ReducerContext;
+
+use crate::services::party;
+use crate::tables::party::party_state;
+
+/// Switches kill loot between free-for-all (0) and round robin (1).
+#[spacetimedb::reducer]
+pub fn party_loot_rule_set(ctx: &ReducerContext, loot_rule: u8) -> Result<(), String> {
+    let mut party = party::require_leader(ctx)?;
+    if loot_rule != party::LOOT_FREE_FOR_ALL && loot_rule != party::LOOT_ROUND_ROBIN {
+        return Err("unknown loot rule".to_string());
+    }
+    party.loot_rule = loot_rule;
+    party.loot_cursor = 0;
+    party.updated_at = ctx.timestamp;
+    ctx.db.party_state().party_id().update(party);
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use spacetimedb::{ReducerContext, Table};
+
+use crate::services::{party, permissions};
+use crate::tables::PartyPermission;
+use crate::tables::inventory_container::inventory_container;
+use crate::tables::party::{party_permission, party_state};
+
+/// Grants every member of `party_id` enter/container/build access to a claim
+/// (1), building (2) or container (4); `flags = 0` revokes. Claims and
+/// buildings need admin rights on the target, containers their owner.
+#[spacetimedb::reducer]
+pub fn party_permission_set(
+    ctx: &ReducerContext,
+    target_kind: u8,
+    target_id: u64,
+    party_id: u64,
+    flags: u32,
+) -> Result<(), String> {
+    let authorized = match target_kind {
+        1 | 2 => permissions::has_permission(ctx, target_kind, target_id, permissions::PERM_ADMIN),
+        4 => ctx
+            .db
+            .inventory_container()
+            .container_id()
+            .find(target_id)
+            .is_some_and(|c| c.owner_identity == ctx.sender),
+        _ => return Err("party permissions apply to claims, buildings and containers".to_string()),
+    };
+    if !authorized {
+        return Err("not allowed to grant access to this target".to_string());
+    }
+    let grantable = permissions::PERM_ENTER | permissions::PERM_CONTAINER | permissions::PERM_BUILD;
+    if flags & !grantable != 0 {
+        return Err("parties can only be granted enter, container and build access".to_string());
+    }
+
+    let key = party::permission_key(target_kind, target_id, party_id);
+    ctx.db.party_permission().permission_key().delete(key.clone());
+    if flags == 0 {
+        return Ok(());
+    }
+    if ctx.db.party_state().party_id().find(party_id).is_none() {
+        return Err("party not found".to_string());
+    }
+    ctx.db.party_permission().insert(PartyPermission {
+        permission_key: key,
+        target_kind,
+        target_id,
+        party_id,
+        flags,
+    });
+    Ok(())
+}
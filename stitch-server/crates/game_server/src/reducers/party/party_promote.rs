@@ -0,0 +1,17 @@
+use spacetimedb::{Identity, ReducerContext};
+
+use crate::services::party;
+use crate::tables::party::{party_member, party_state};
+
+/// Hands party leadership to another member.
+#[spacetimedb::reducer]
+pub fn party_promote(ctx: &ReducerContext, member: Identity) -> Result<(), String> {
+    let mut party = party::require_leader(ctx)?;
+    if ctx.db.party_member().member_identity().find(member).is_none_or(|m| m.party_id != party.party_id) {
+        return Err("player is not in your party".to_string());
+    }
+    party.leader_identity = member;
+    party.updated_at = ctx.timestamp;
+    ctx.db.party_state().party_id().update(party);
+    Ok(())
+}
@@ -0,0 +1,28 @@
+use spacetimedb::ReducerContext;
+
+use crate::services::achievements;
+use crate::tables::achievement::{achievement_def, achievement_state};
+
+/// Explicit acquire for a discovered achievement, e.g. one added after the
+/// player already met its conditions. Discovery events normally do this.
+#[spacetimedb::reducer]
+pub fn achievement_acquire(ctx: &ReducerContext, achievement_id: u64) -> Result<(), String> {
+    let def = ctx
+        .db
+        .achievement_def()
+        .achievement_id()
+        .find(achievement_id)
+        .ok_or("achievement not found".to_string())?;
+    achievements::evaluate(ctx, ctx.sender);
+
+    let state = ctx
+        .db
+        .achievement_state()
+        .achievement_key()
+        .find(achievements::achievement_key(ctx.sender, achievement_id))
+        .ok_or("achievement not discovered".to_string())?;
+    if state.status != achievements::STATUS_ACQUIRED {
+        return Err(format!("{} conditions not met", def.name));
+    }
+    Ok(())
+}
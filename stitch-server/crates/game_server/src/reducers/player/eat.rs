@@ -0,0 +1,94 @@
+use spacetimedb::ReducerContext;
+
+use crate::reducers::inventory::inventory_bootstrap::slot_key;
+use crate::reducers::inventory::inventory_lock::ensure_not_locked;
+use crate::services::{buffs, health, inventory, stats};
+use crate::tables::combat::combat_state;
+use crate::tables::inventory_slot::inventory_slot;
+use crate::tables::item_def::food_def;
+use crate::tables::item_instance::item_instance;
+use crate::tables::item_stack::item_stack;
+use crate::tables::resource_state::resource_state;
+
+/// Eats one item from a main-inventory slot.
+#[spacetimedb::reducer]
+pub fn eat(ctx: &ReducerContext, inventory_slot_index: u32) -> Result<(), String> {
+    let mut state = health::ensure(ctx, ctx.sender);
+    if state.hp == 0 {
+        return Err("you are incapacitated".to_string());
+    }
+
+    let container = inventory::main_container(ctx, ctx.sender)?;
+    ensure_not_locked(ctx, container.container_id)?;
+    let mut slot = ctx
+        .db
+        .inventory_slot()
+        .slot_key()
+        .find(slot_key(container.container_id, inventory_slot_index))
+        .ok_or("inventory slot not found".to_string())?;
+    if slot.locked {
+        return Err("slot is locked".to_string());
+    }
+    if slot.item_instance_id == 0 {
+        return Err("nothing to eat".to_string());
+    }
+    let instance = ctx
+        .db
+        .item_instance()
+        .item_instance_id()
+        .find(slot.item_instance_id)
+        .ok_or("item instance missing".to_string())?;
+    let food = ctx
+        .db
+        .food_def()
+        .item_def_id()
+        .find(instance.item_def_id)
+        .ok_or("item cannot be eaten".to_string())?;
+
+    if !food.consumable_in_combat {
+        let in_combat = ctx
+            .db
+            .combat_state()
+            .identity()
+            .find(ctx.sender)
+            .is_some_and(|c| c.in_combat);
+        if in_combat {
+            return Err("cannot eat this while in combat".to_string());
+        }
+    }
+
+    let mut stack = ctx
+        .db
+        .item_stack()
+        .item_instance_id()
+        .find(instance.item_instance_id)
+        .ok_or("item stack missing".to_string())?;
+    if stack.quantity > 1 {
+        stack.quantity -= 1;
+        ctx.db.item_stack().item_instance_id().update(stack);
+    } else {
+        ctx.db.item_stack().item_instance_id().delete(instance.item_instance_id);
+        ctx.db.item_instance().item_instance_id().delete(instance.item_instance_id);
+        slot.item_instance_id = 0;
+        ctx.db.inventory_slot().slot_key().update(slot);
+    }
+
+    let current = stats::get(ctx, ctx.sender);
+    state.hp = (state.hp + food.hp_restore).clamp(0, current.max_hp);
+    state.stamina = (state.stamina + food.stamina_restore).clamp(0, current.max_stamina);
+    state.satiation = (state.satiation + food.satiation_restore).clamp(0, current.max_satiation);
+    if food.hp_restore < 0 {
+        state.last_damage_at = ctx.timestamp;
+    }
+    state.updated_at = ctx.timestamp;
+    let fed = state.satiation > 0;
+    ctx.db.resource_state().identity().update(state);
+
+    if fed {
+        buffs::remove(ctx, ctx.sender, health::STARVING_EFFECT_ID);
+    }
+    if food.effect_id != 0 {
+        buffs::apply(ctx, ctx.sender, food.effect_id, buffs::SOURCE_FOOD)?;
+    }
+    Ok(())
+}
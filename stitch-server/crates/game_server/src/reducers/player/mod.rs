@@ -1 +1,4 @@
+pub mod achievement_acquire;
+pub mod eat;
 pub mod move_player;
+pub mod player_respawn;
@@ -1,5 +1,6 @@
 use spacetimedb::{ReducerContext, Table};
 
+use crate::services::{achievements, terrain};
 use crate::tables::{MovementActorState, MovementRequestLog, TransformState};
 use crate::tables::movement::movement_actor_state;
 use crate::tables::movement::movement_request_log;
@@ -65,7 +66,8 @@ pub fn move_to(
     }
 
     let actor_state = ctx.db.movement_actor_state().identity().find(ctx.sender);
-    if let Err(reason) = anti_cheat::validate_actor_progression(actor_state, client_ts_ms, &next_position)
+    if let Err(reason) =
+        anti_cheat::validate_actor_progression(ctx, region_id, actor_state, client_ts_ms, &next_position)
     {
         anti_cheat::log_movement_violation(
             ctx,
@@ -115,5 +117,9 @@ pub fn move_to(
         ctx.db.movement_actor_state().insert(next_actor_state);
     }
 
+    if let Some(cell) = terrain::TerrainSampler::for_region(ctx, region_id).cell(terrain::hex_at(x, z)) {
+        achievements::discover(ctx, ctx.sender, achievements::KNOWLEDGE_BIOME, cell.biome_id as u64);
+    }
+
     Ok(())
 }
@@ -0,0 +1,10 @@
+use spacetimedb::ReducerContext;
+
+use crate::services::health;
+
+/// Lets a downed player respawn at their region's spawn point right away
+/// instead of waiting for the regen agent.
+#[spacetimedb::reducer]
+pub fn player_respawn(ctx: &ReducerContext) -> Result<(), String> {
+    health::respawn(ctx, ctx.sender)
+}
@@ -0,0 +1,79 @@
+use std::time::Duration;
+
+use spacetimedb::{Identity, ReducerContext};
+
+use crate::services::{balance, inventory, loot, resources, skills};
+use crate::tables::GatherTimer;
+use crate::tables::item_def::tool_def;
+use crate::tables::resource_node::{resource_def, resource_node};
+use crate::tables::transform_state::transform_state;
+
+use super::gather_start::GATHER_RANGE_SQ;
+
+/// Bare-handed gathering still chips away at a node.
+const BARE_HAND_POWER: u32 = 5;
+
+#[spacetimedb::reducer]
+pub fn gather_complete(ctx: &ReducerContext, timer: GatherTimer) -> Result<(), String> {
+    if ctx.sender != ctx.identity() {
+        return Err("gather_complete is scheduler-only".to_string());
+    }
+
+    let gatherer = timer.gatherer_identity;
+    let Some(mut node) = ctx.db.resource_node().entity_id().find(timer.node_id) else {
+        return Ok(());
+    };
+    if resources::is_depleted(&node) {
+        return Ok(());
+    }
+    let Some(def) = ctx.db.resource_def().resource_def_id().find(node.resource_def_id) else {
+        return Ok(());
+    };
+
+    // Walking away or dropping the tool mid-action cancels the gather.
+    let in_range = ctx.db.transform_state().entity_id().find(gatherer).is_some_and(|tf| {
+        let dx = tf.position[0] - node.hex_x as f32;
+        let dz = tf.position[2] - node.hex_z as f32;
+        dx * dx + dz * dz <= GATHER_RANGE_SQ
+    });
+    if !in_range {
+        log_cancel(gatherer, node.entity_id, "out_of_range");
+        return Ok(());
+    }
+    let power = if timer.tool_item_def_id == 0 {
+        BARE_HAND_POWER
+    } else {
+        if inventory::count_items(ctx, gatherer, timer.tool_item_def_id) == 0 {
+            log_cancel(gatherer, node.entity_id, "tool_missing");
+            return Ok(());
+        }
+        ctx.db
+            .tool_def()
+            .item_def_id()
+            .find(timer.tool_item_def_id)
+            .map(|t| t.power)
+            .unwrap_or(BARE_HAND_POWER)
+    };
+
+    let drops = loot::roll_item_list(ctx, def.item_list_id);
+    if !inventory::can_fit_all(ctx, gatherer, &drops).unwrap_or(false) {
+        log_cancel(gatherer, node.entity_id, "inventory_full");
+        return Ok(());
+    }
+    inventory::add_all(ctx, gatherer, &drops)?;
+    let xp = balance::get_u64(ctx, balance::GATHER_XP_PER_ACTION, 5);
+    skills::award_xp(ctx, gatherer, def.skill_id, xp);
+
+    node.health = node.health.saturating_sub(power.max(1));
+    if resources::is_depleted(&node) {
+        node.depleted_at = Some(ctx.timestamp);
+        node.respawn_at = Some(ctx.timestamp + Duration::from_secs(def.respawn_secs));
+    }
+    ctx.db.resource_node().entity_id().update(node);
+
+    Ok(())
+}
+
+fn log_cancel(gatherer: Identity, node_id: u64, reason: &str) {
+    log::info!("gather cancelled: identity={gatherer} node_id={node_id} reason={reason}");
+}
@@ -0,0 +1,72 @@
+use std::time::Duration;
+
+use spacetimedb::{ReducerContext, Table};
+
+use crate::services::{balance, health, resources, skills, terrain};
+use crate::tables::GatherTimer;
+use crate::tables::resource_node::{gather_timer, resource_def, resource_node};
+use crate::tables::session_state::session_state;
+use crate::tables::transform_state::transform_state;
+
+pub const GATHER_RANGE_SQ: f32 = 9.0;
+
+#[spacetimedb::reducer]
+pub fn gather_start(ctx: &ReducerContext, node_id: u64) -> Result<(), String> {
+    let session = ctx
+        .db
+        .session_state()
+        .identity()
+        .find(ctx.sender)
+        .ok_or("active session required".to_string())?;
+
+    if ctx.db.gather_timer().iter().any(|t| t.gatherer_identity == ctx.sender) {
+        return Err("already gathering".to_string());
+    }
+
+    let node = ctx
+        .db
+        .resource_node()
+        .entity_id()
+        .find(node_id)
+        .ok_or("resource node not found".to_string())?;
+    if resources::is_depleted(&node) {
+        return Err("resource node is depleted".to_string());
+    }
+    if terrain::dimension_for_region(session.region_id) != Some(node.dimension_id) {
+        return Err("resource node is not in this region".to_string());
+    }
+
+    let transform = ctx
+        .db
+        .transform_state()
+        .entity_id()
+        .find(ctx.sender)
+        .ok_or("transform missing".to_string())?;
+    let dx = transform.position[0] - node.hex_x as f32;
+    let dz = transform.position[2] - node.hex_z as f32;
+    if dx * dx + dz * dz > GATHER_RANGE_SQ {
+        return Err("resource node out of range".to_string());
+    }
+
+    let def = ctx
+        .db
+        .resource_def()
+        .resource_def_id()
+        .find(node.resource_def_id)
+        .ok_or("resource_def missing".to_string())?;
+    skills::require(ctx, ctx.sender, def.skill_id, def.skill_level)?;
+    let tool = resources::find_tool(ctx, ctx.sender, &def)?;
+    let stamina_cost = balance::get_u64(ctx, balance::GATHER_STAMINA_COST, 5) as i32;
+    health::use_stamina(ctx, ctx.sender, stamina_cost)?;
+
+    ctx.db.gather_timer().insert(GatherTimer {
+        scheduled_id: 0,
+        scheduled_at: (ctx.timestamp + Duration::from_millis(def.gather_duration_ms)).into(),
+        gatherer_identity: ctx.sender,
+        node_id,
+        tool_item_def_id: tool.map(|t| t.item_def_id).unwrap_or(0),
+        started_at: ctx.timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,2 @@
+pub mod gather_complete;
+pub mod gather_start;
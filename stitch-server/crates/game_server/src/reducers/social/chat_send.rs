@@ -0,0 +1,81 @@
+use spacetimedb::{Identity, ReducerContext, Table};
+
+use crate::services::{balance, chat, moderation, permissions};
+use crate::tables::ChatMessage;
+use crate::tables::chat::chat_message;
+use crate::tables::player_state::player_state;
+use crate::tables::session_state::session_state;
+use crate::tables::transform_state::transform_state;
+use crate::validation::rate_limit;
+
+/// Sends a chat line. Region and local chat use the sender's region, whispers
+/// need a `recipient`, party and guild chat need membership of `scope_id`,
+/// and system messages are server/admin-only.
+#[spacetimedb::reducer]
+pub fn chat_send(
+    ctx: &ReducerContext,
+    channel_type: u8,
+    scope_id: u64,
+    recipient: Option<Identity>,
+    text: String,
+) -> Result<(), String> {
+    moderation::require_not_banned(ctx, ctx.sender)?;
+    chat::require_unmuted(ctx, ctx.sender)?;
+    let burst = balance::get_u64(ctx, balance::CHAT_BURST, 5) as u32;
+    let refill_secs = balance::get_u64(ctx, balance::CHAT_REFILL_SECS, 2);
+    rate_limit::take(ctx, ctx.sender, rate_limit::ACTION_CHAT, burst, refill_secs)?;
+
+    let scope_id = match channel_type {
+        chat::CHANNEL_REGION | chat::CHANNEL_LOCAL => {
+            let session = ctx
+                .db
+                .session_state()
+                .identity()
+                .find(ctx.sender)
+                .ok_or("active session required".to_string())?;
+            session.region_id
+        }
+        chat::CHANNEL_PARTY | chat::CHANNEL_GUILD => {
+            if !chat::is_member(&ctx.as_read_only(), ctx.sender, channel_type, scope_id) {
+                return Err("not a member of this channel".to_string());
+            }
+            scope_id
+        }
+        chat::CHANNEL_WHISPER => {
+            let target = recipient.ok_or("whisper needs a recipient".to_string())?;
+            if target == ctx.sender {
+                return Err("cannot whisper to yourself".to_string());
+            }
+            if ctx.db.player_state().player_id().find(target).is_none() {
+                return Err("recipient not found".to_string());
+            }
+            0
+        }
+        chat::CHANNEL_SYSTEM => {
+            permissions::require_admin(ctx, "system chat")?;
+            0
+        }
+        _ => return Err("unknown channel type".to_string()),
+    };
+
+    let filtered = chat::filter_text(&text)?;
+    let channel = chat::ensure_channel(ctx, channel_type, scope_id);
+    if filtered != text.trim() {
+        chat::log_filtered(ctx, ctx.sender, channel.channel_id, text.trim());
+    }
+    let (pos_x, pos_z) = match ctx.db.transform_state().entity_id().find(ctx.sender) {
+        Some(tf) => (tf.position[0], tf.position[2]),
+        None => (0.0, 0.0),
+    };
+    ctx.db.chat_message().insert(ChatMessage {
+        message_id: 0,
+        channel_id: channel.channel_id,
+        sender_id: ctx.sender,
+        recipient_id: if channel_type == chat::CHANNEL_WHISPER { recipient } else { None },
+        pos_x,
+        pos_z,
+        text: filtered,
+        sent_at: ctx.timestamp,
+    });
+    Ok(())
+}
@@ -0,0 +1,2 @@
+pub mod chat_send;
+pub mod report_player;
@@ -0,0 +1,67 @@
+use std::time::Duration;
+
+use spacetimedb::{Identity, ReducerContext, Table};
+
+use crate::services::{balance, moderation};
+use crate::tables::ReportQueue;
+use crate::tables::moderation::report_queue;
+use crate::tables::player_state::player_state;
+
+/// Files a report on `target` with a snapshot of their recent chat, movement
+/// violations and trades. A repeat report on the same target within
+/// `report.dedup_secs` refreshes the pending report instead of adding one.
+#[spacetimedb::reducer]
+pub fn report_player(
+    ctx: &ReducerContext,
+    target: Identity,
+    category: u8,
+    details: String,
+) -> Result<(), String> {
+    moderation::require_not_banned(ctx, ctx.sender)?;
+    if target == ctx.sender {
+        return Err("cannot report yourself".to_string());
+    }
+    if ctx.db.player_state().player_id().find(target).is_none() {
+        return Err("player not found".to_string());
+    }
+    if !(moderation::CATEGORY_CHAT..=moderation::CATEGORY_OTHER).contains(&category) {
+        return Err("unknown report category".to_string());
+    }
+    let details: String = details.trim().chars().take(moderation::MAX_DETAILS_CHARS).collect();
+
+    let chat_snapshot = moderation::chat_snapshot(ctx, ctx.sender, target);
+    let movement_violations = moderation::movement_violations(ctx, target);
+    let trade_snapshot = moderation::trade_snapshot(ctx, target);
+
+    let window = Duration::from_secs(balance::get_u64(ctx, balance::REPORT_DEDUP_SECS, 3600));
+    if let Some(mut report) = moderation::recent_report(ctx, ctx.sender, target, window) {
+        if !details.is_empty() && !report.details.contains(&details) {
+            report.details = format!("{}\n{details}", report.details).trim().to_string();
+        }
+        report.chat_snapshot = chat_snapshot;
+        report.movement_violations = movement_violations;
+        report.trade_snapshot = trade_snapshot;
+        report.updated_at = ctx.timestamp;
+        ctx.db.report_queue().report_id().update(report);
+        return Ok(());
+    }
+
+    ctx.db.report_queue().insert(ReportQueue {
+        report_id: 0,
+        reporter_id: ctx.sender,
+        target_id: target,
+        category,
+        details,
+        chat_snapshot,
+        movement_violations,
+        trade_snapshot,
+        status: moderation::REPORT_OPEN,
+        priority: moderation::initial_priority(ctx, ctx.sender, target),
+        assigned_to: None,
+        action_id: 0,
+        resolution: String::new(),
+        created_at: ctx.timestamp,
+        updated_at: ctx.timestamp,
+    });
+    Ok(())
+}
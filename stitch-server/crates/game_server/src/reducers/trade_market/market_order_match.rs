@@ -1,5 +1,6 @@
 use spacetimedb::{ReducerContext, Table};
 
+use crate::services::economy;
 use crate::tables::MarketFill;
 use crate::tables::trade_market::market_fill;
 use crate::tables::trade_market::market_order;
@@ -64,6 +65,7 @@ pub fn market_order_match(
     sell.updated_at = ctx.timestamp;
 
     let buy_item_def_id = buy.item_def_id;
+    let region_id = buy.region_id;
     let buyer_identity = buy.owner_identity;
     let seller_identity = sell.owner_identity;
     let fill_unit_price = sell.unit_price;
@@ -84,6 +86,7 @@ pub fn market_order_match(
             created_at: ctx.timestamp,
         });
     }
+    economy::record_fill(ctx, region_id, buy_item_def_id, fill_unit_price, fill_qty);
 
     Ok(())
 }
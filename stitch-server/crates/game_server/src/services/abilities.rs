@@ -0,0 +1,106 @@
+use std::collections::HashSet;
+use std::time::Duration;
+
+use spacetimedb::{Identity, ReducerContext, Table};
+
+use crate::services::{equipment, health, skills};
+use crate::tables::{AbilityDef, AbilityState};
+use crate::tables::ability::{ability_def, ability_state};
+use crate::tables::equipment::{equipment_def, equipment_slot};
+use crate::tables::item_instance::item_instance;
+
+pub const KIND_ATTACK: u8 = 1;
+pub const KIND_HEAL: u8 = 2;
+pub const KIND_BUFF: u8 = 3;
+
+/// Innate ability behind `attack_start`.
+pub const BASIC_ATTACK: u32 = 1;
+
+pub fn ability_key(identity: Identity, ability_def_id: u32) -> String {
+    format!("{identity}:{ability_def_id}")
+}
+
+fn main_hand_weapon_type(ctx: &ReducerContext, identity: Identity) -> u8 {
+    ctx.db
+        .equipment_slot()
+        .slot_key()
+        .find(equipment::slot_key(identity, equipment::SLOT_MAIN_HAND))
+        .and_then(|slot| ctx.db.item_instance().item_instance_id().find(slot.item_instance_id))
+        .and_then(|inst| ctx.db.equipment_def().item_def_id().find(inst.item_def_id))
+        .map(|def| def.weapon_type)
+        .unwrap_or(0)
+}
+
+/// Syncs `ability_state` with what the player can currently use: innate
+/// abilities plus those of the main-hand weapon type, filtered by skill level.
+/// Dropped abilities are only marked unavailable, so every ability keeps its
+/// cooldown across loadout changes.
+pub(crate) fn refresh_loadout(ctx: &ReducerContext, identity: Identity) {
+    let weapon_type = main_hand_weapon_type(ctx, identity);
+    let available: HashSet<u32> = ctx
+        .db
+        .ability_def()
+        .iter()
+        .filter(|d| d.weapon_type == 0 || d.weapon_type == weapon_type)
+        .filter(|d| skills::meets_requirement(ctx, identity, d.skill_id, d.skill_level))
+        .map(|d| d.ability_def_id)
+        .collect();
+
+    let current: Vec<AbilityState> =
+        ctx.db.ability_state().iter().filter(|a| a.identity == identity).collect();
+    let mut known: HashSet<u32> = HashSet::new();
+    for mut state in current {
+        known.insert(state.ability_def_id);
+        let usable = available.contains(&state.ability_def_id);
+        if state.available != usable {
+            state.available = usable;
+            ctx.db.ability_state().ability_key().update(state);
+        }
+    }
+    for ability_def_id in available.difference(&known) {
+        ctx.db.ability_state().insert(AbilityState {
+            ability_key: ability_key(identity, *ability_def_id),
+            identity,
+            ability_def_id: *ability_def_id,
+            available: true,
+            cooldown_until: ctx.timestamp,
+            use_count: 0,
+        });
+    }
+}
+
+/// Checks the loadout and server-time cooldown, spends stamina and starts the
+/// cooldown. Returns the def so the caller can resolve the effect.
+pub(crate) fn activate(
+    ctx: &ReducerContext,
+    identity: Identity,
+    ability_def_id: u32,
+) -> Result<AbilityDef, String> {
+    let key = ability_key(identity, ability_def_id);
+    if !ctx.db.ability_state().ability_key().find(key.clone()).is_some_and(|a| a.available) {
+        refresh_loadout(ctx, identity);
+    }
+    let mut state = ctx
+        .db
+        .ability_state()
+        .ability_key()
+        .find(key)
+        .filter(|a| a.available)
+        .ok_or("ability not available".to_string())?;
+    let def = ctx
+        .db
+        .ability_def()
+        .ability_def_id()
+        .find(ability_def_id)
+        .ok_or("ability_def not found".to_string())?;
+
+    if state.cooldown_until > ctx.timestamp {
+        return Err("ability on cooldown".to_string());
+    }
+    health::use_stamina(ctx, identity, def.stamina_cost)?;
+
+    state.cooldown_until = ctx.timestamp + Duration::from_millis(def.cooldown_ms);
+    state.use_count = state.use_count.saturating_add(1);
+    ctx.db.ability_state().ability_key().update(state);
+    Ok(def)
+}
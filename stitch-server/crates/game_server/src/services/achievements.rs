@@ -0,0 +1,146 @@
+use spacetimedb::{Identity, ReducerContext, Table};
+
+use crate::services::{quests, skills};
+use crate::tables::{AchievementDef, AchievementRequirement, AchievementState, KnowledgeState, PlayerUnlock};
+use crate::tables::achievement::{achievement_def, achievement_state, knowledge_state, player_unlock};
+use crate::tables::npc_quest::npc_state;
+
+pub const KNOWLEDGE_ITEM: u8 = 1;
+pub const KNOWLEDGE_RECIPE: u8 = 2;
+pub const KNOWLEDGE_BIOME: u8 = 3;
+pub const KNOWLEDGE_NPC: u8 = 4;
+pub const REQ_QUEST_CHAIN: u8 = 5;
+
+pub const STATUS_DISCOVERED: u8 = 0;
+pub const STATUS_ACQUIRED: u8 = 1;
+
+pub const REWARD_TITLE: u8 = 1;
+pub const REWARD_COSMETIC: u8 = 2;
+
+pub fn knowledge_key(identity: Identity, kind: u8, target_id: u64) -> String {
+    format!("{identity}:{kind}:{target_id}")
+}
+
+pub fn achievement_key(identity: Identity, achievement_id: u64) -> String {
+    format!("{identity}:{achievement_id}")
+}
+
+/// Discovery event hook. Records new knowledge and re-evaluates achievements;
+/// already-known entries and npcs without an `npc_state` row are a cheap no-op.
+pub(crate) fn discover(ctx: &ReducerContext, identity: Identity, kind: u8, target_id: u64) {
+    if kind == KNOWLEDGE_NPC && ctx.db.npc_state().npc_id().find(target_id).is_none() {
+        return;
+    }
+    let key = knowledge_key(identity, kind, target_id);
+    if ctx.db.knowledge_state().knowledge_key().find(key.clone()).is_some() {
+        return;
+    }
+    ctx.db.knowledge_state().insert(KnowledgeState {
+        knowledge_key: key,
+        identity,
+        kind,
+        target_id,
+        discovered_at: ctx.timestamp,
+    });
+    evaluate(ctx, identity);
+}
+
+fn is_acquired(ctx: &ReducerContext, identity: Identity, achievement_id: u64) -> bool {
+    ctx.db
+        .achievement_state()
+        .achievement_key()
+        .find(achievement_key(identity, achievement_id))
+        .is_some_and(|a| a.status == STATUS_ACQUIRED)
+}
+
+fn requirement_met(ctx: &ReducerContext, identity: Identity, req: &AchievementRequirement) -> bool {
+    if req.kind == REQ_QUEST_CHAIN {
+        return quests::is_completed(ctx, identity, req.target_id);
+    }
+    if req.target_id != 0 {
+        return ctx
+            .db
+            .knowledge_state()
+            .knowledge_key()
+            .find(knowledge_key(identity, req.kind, req.target_id))
+            .is_some();
+    }
+    let known = ctx
+        .db
+        .knowledge_state()
+        .iter()
+        .filter(|k| k.identity == identity && k.kind == req.kind)
+        .count();
+    known >= req.count as usize
+}
+
+pub(crate) fn conditions_met(ctx: &ReducerContext, identity: Identity, def: &AchievementDef) -> bool {
+    skills::meets_requirement(ctx, identity, def.skill_id, def.skill_level)
+        && def.requirements.iter().all(|r| requirement_met(ctx, identity, r))
+}
+
+/// Discover/acquire pass: achievements whose prerequisites are acquired become
+/// discovered, and discovered ones whose conditions hold are acquired with
+/// their rewards. Repeats until nothing changes, since an acquisition can
+/// unlock further discoveries.
+pub(crate) fn evaluate(ctx: &ReducerContext, identity: Identity) {
+    let defs: Vec<AchievementDef> = ctx.db.achievement_def().iter().collect();
+    loop {
+        let mut changed = false;
+        for def in &defs {
+            let key = achievement_key(identity, def.achievement_id);
+            let state = ctx.db.achievement_state().achievement_key().find(key.clone());
+            if state.as_ref().is_some_and(|s| s.status == STATUS_ACQUIRED) {
+                continue;
+            }
+            let state = match state {
+                Some(state) => state,
+                None => {
+                    if def.prerequisite_ids.iter().any(|id| !is_acquired(ctx, identity, *id)) {
+                        continue;
+                    }
+                    changed = true;
+                    ctx.db.achievement_state().insert(AchievementState {
+                        achievement_key: key,
+                        identity,
+                        achievement_id: def.achievement_id,
+                        status: STATUS_DISCOVERED,
+                        discovered_at: ctx.timestamp,
+                        acquired_at: None,
+                    })
+                }
+            };
+            if conditions_met(ctx, identity, def) {
+                acquire(ctx, state, def);
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+}
+
+pub(crate) fn acquire(ctx: &ReducerContext, mut state: AchievementState, def: &AchievementDef) {
+    let identity = state.identity;
+    state.status = STATUS_ACQUIRED;
+    state.acquired_at = Some(ctx.timestamp);
+    ctx.db.achievement_state().achievement_key().update(state);
+
+    for reward in &def.rewards {
+        let unlock_key = format!("{identity}:{}:{}", reward.kind, reward.reward_id);
+        if ctx.db.player_unlock().unlock_key().find(unlock_key.clone()).is_some() {
+            continue;
+        }
+        ctx.db.player_unlock().insert(PlayerUnlock {
+            unlock_key,
+            identity,
+            kind: reward.kind,
+            reward_id: reward.reward_id,
+            label: reward.label.clone(),
+            achievement_id: def.achievement_id,
+            unlocked_at: ctx.timestamp,
+        });
+    }
+    log::info!("achievement acquired: identity={identity} achievement_id={}", def.achievement_id);
+}
@@ -0,0 +1,82 @@
+use spacetimedb::ReducerContext;
+
+use crate::tables::balance::balance_params;
+
+/// Total xp for level `n` is `xp_base * n^xp_exponent`.
+pub const SKILL_XP_BASE: &str = "skill.xp_base";
+pub const SKILL_XP_EXPONENT: &str = "skill.xp_exponent";
+pub const GATHER_XP_PER_ACTION: &str = "skill.gather_xp_per_action";
+pub const CONSTRUCTION_XP_PER_STEP: &str = "skill.construction_xp_per_step";
+pub const COMBAT_XP_PER_HIT: &str = "skill.combat_xp_per_hit";
+
+pub const GATHER_STAMINA_COST: &str = "stamina.gather_cost";
+
+/// Extra hp/stamina per regen tick once the passive delay has passed.
+pub const PASSIVE_HP_REGEN: &str = "regen.passive_hp_per_tick";
+pub const PASSIVE_STAMINA_REGEN: &str = "regen.passive_stamina_per_tick";
+pub const PASSIVE_REGEN_DELAY_SECS: &str = "regen.passive_delay_secs";
+pub const SATIATION_DECAY: &str = "regen.satiation_decay_per_tick";
+pub const STARVING_DAMAGE: &str = "regen.starving_damage_per_tick";
+/// Seconds a downed player waits before the regen agent respawns them.
+pub const RESPAWN_SECS: &str = "regen.respawn_secs";
+/// Seconds without an attack before a player drops out of combat.
+pub const COMBAT_EXIT_SECS: &str = "combat.exit_secs";
+
+/// How long a worker holds an npc action request before it is retried.
+pub const NPC_LEASE_SECS: &str = "npc.lease_secs";
+pub const NPC_MAX_ATTEMPTS: &str = "npc.max_attempts";
+/// Seconds a request may wait unclaimed in the queue before it gets the scripted fallback.
+pub const NPC_PENDING_TTL_SECS: &str = "npc.pending_ttl_secs";
+/// Hours finished npc action requests and their results are kept.
+pub const NPC_RESULT_RETENTION_HOURS: &str = "npc.result_retention_hours";
+/// Real seconds per day and night phase of the world clock.
+pub const DAY_SECS: &str = "day_night.day_secs";
+pub const NIGHT_SECS: &str = "day_night.night_secs";
+
+/// Lifetime of cached npc talk replies.
+pub const NPC_CACHE_TTL_SECS: &str = "npc.cache_ttl_secs";
+/// Default daily token cap per region, 0 = unlimited.
+pub const NPC_REGION_DAILY_TOKENS: &str = "npc.region_daily_tokens";
+
+/// Daily per-player caps on what workers may hand out through npcs.
+pub const NPC_DAILY_ITEM_GRANTS: &str = "npc.daily_item_grants";
+pub const NPC_DAILY_QUEST_OFFERS: &str = "npc.daily_quest_offers";
+/// Npc prices stay within this percentage of the regional price index and
+/// move at most `NPC_PRICE_STEP_PCT` per change, once a day.
+pub const NPC_PRICE_BAND_PCT: &str = "npc.price_band_pct";
+pub const NPC_PRICE_STEP_PCT: &str = "npc.price_step_pct";
+
+/// Npc shop spread around the mid price, plus up to `SHOP_SCARCITY_PCT` as
+/// stock runs out (selling) or fills up (buying back).
+pub const SHOP_SELL_MARKUP_PCT: &str = "shop.sell_markup_pct";
+pub const SHOP_BUY_SPREAD_PCT: &str = "shop.buy_spread_pct";
+pub const SHOP_SCARCITY_PCT: &str = "shop.scarcity_pct";
+
+/// Chat token bucket: burst size and seconds to regain one message.
+pub const CHAT_BURST: &str = "chat.burst";
+pub const CHAT_REFILL_SECS: &str = "chat.refill_secs";
+pub const CHAT_RETENTION_HOURS: &str = "chat.retention_hours";
+/// Window in which repeat reports on the same player merge into one.
+pub const REPORT_DEDUP_SECS: &str = "report.dedup_secs";
+/// Party size cap, and the range within which members share kill credit,
+/// `PARTY_KILL_XP` combat xp and loot from `COMBAT_KILL_LOOT_LIST` (0 = none).
+pub const PARTY_MAX_SIZE: &str = "party.max_size";
+pub const PARTY_SHARE_RANGE: &str = "party.share_range";
+pub const PARTY_KILL_XP: &str = "party.kill_xp";
+pub const COMBAT_KILL_LOOT_LIST: &str = "combat.kill_loot_list";
+
+fn raw(ctx: &ReducerContext, key: &str) -> Option<String> {
+    ctx.db.balance_params().key().find(key.to_string()).map(|p| p.value)
+}
+
+/// Missing or unparsable values fall back to `default`.
+pub(crate) fn get_f32(ctx: &ReducerContext, key: &str, default: f32) -> f32 {
+    raw(ctx, key)
+        .and_then(|v| v.trim().parse::<f32>().ok())
+        .filter(|v| v.is_finite())
+        .unwrap_or(default)
+}
+
+pub(crate) fn get_u64(ctx: &ReducerContext, key: &str, default: u64) -> u64 {
+    raw(ctx, key).and_then(|v| v.trim().parse::<u64>().ok()).unwrap_or(default)
+}
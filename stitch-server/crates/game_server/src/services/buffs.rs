@@ -0,0 +1,91 @@
+use std::time::Duration;
+
+use spacetimedb::{Identity, ReducerContext, Table};
+
+use crate::services::stats;
+use crate::tables::BuffState;
+use crate::tables::buff::{buff_state, status_effect};
+
+pub const SOURCE_FOOD: u8 = 1;
+pub const SOURCE_ABILITY: u8 = 2;
+pub const SOURCE_ENVIRONMENT: u8 = 3;
+pub const SOURCE_HUNGER: u8 = 4;
+
+pub fn buff_key(identity: Identity, effect_id: u32) -> String {
+    format!("{identity}:{effect_id}")
+}
+
+pub(crate) fn active_buffs(ctx: &ReducerContext, identity: Identity) -> Vec<BuffState> {
+    ctx.db
+        .buff_state()
+        .iter()
+        .filter(|b| b.identity == identity && b.expires_at.is_none_or(|t| t > ctx.timestamp))
+        .collect()
+}
+
+/// Applies an effect, or adds a stack and refreshes its duration if already
+/// active. A same-category effect with higher priority blocks it; lower or
+/// equal priority ones are replaced.
+pub(crate) fn apply(
+    ctx: &ReducerContext,
+    identity: Identity,
+    effect_id: u32,
+    source: u8,
+) -> Result<(), String> {
+    let def = ctx
+        .db
+        .status_effect()
+        .effect_id()
+        .find(effect_id)
+        .ok_or("status effect not found".to_string())?;
+
+    if def.category != 0 {
+        let rivals: Vec<BuffState> = active_buffs(ctx, identity)
+            .into_iter()
+            .filter(|b| b.category == def.category && b.effect_id != effect_id)
+            .collect();
+        if rivals.iter().any(|b| b.priority > def.priority) {
+            return Err("a stronger effect of the same kind is active".to_string());
+        }
+        for rival in rivals {
+            ctx.db.buff_state().buff_key().delete(rival.buff_key);
+        }
+    }
+
+    let expires_at = (def.duration_ms > 0).then(|| ctx.timestamp + Duration::from_millis(def.duration_ms));
+    let key = buff_key(identity, effect_id);
+    match ctx.db.buff_state().buff_key().find(key.clone()) {
+        Some(mut buff) => {
+            let expired = buff.expires_at.is_some_and(|t| t <= ctx.timestamp);
+            buff.stacks = if expired { 1 } else { (buff.stacks + 1).min(def.max_stacks.max(1)) };
+            buff.source = source;
+            buff.applied_at = ctx.timestamp;
+            buff.expires_at = expires_at;
+            ctx.db.buff_state().buff_key().update(buff);
+        }
+        None => {
+            ctx.db.buff_state().insert(BuffState {
+                buff_key: key,
+                identity,
+                effect_id,
+                category: def.category,
+                priority: def.priority,
+                stacks: 1,
+                source,
+                applied_at: ctx.timestamp,
+                expires_at,
+            });
+        }
+    }
+
+    stats::recompute(ctx, identity);
+    Ok(())
+}
+
+pub(crate) fn remove(ctx: &ReducerContext, identity: Identity, effect_id: u32) -> bool {
+    let removed = ctx.db.buff_state().buff_key().delete(buff_key(identity, effect_id));
+    if removed {
+        stats::recompute(ctx, identity);
+    }
+    removed
+}
@@ -0,0 +1,175 @@
+use std::time::Duration;
+
+use spacetimedb::{Identity, ReducerContext, Table, ViewContext};
+
+use crate::tables::{ChatChannel, ChatFilterLog, ChatMessage, ChatMute};
+use crate::tables::chat::{chat_channel, chat_filter_log, chat_mute};
+use crate::tables::empire::empire_member_state__view;
+use crate::tables::party::party_member__view;
+use crate::tables::session_state::session_state__view;
+use crate::tables::transform_state::transform_state__view;
+
+pub const CHANNEL_REGION: u8 = 1;
+pub const CHANNEL_LOCAL: u8 = 2;
+pub const CHANNEL_PARTY: u8 = 3;
+/// Guild chat is scoped to an empire and its members.
+pub const CHANNEL_GUILD: u8 = 4;
+pub const CHANNEL_WHISPER: u8 = 5;
+pub const CHANNEL_SYSTEM: u8 = 6;
+
+pub const MAX_MESSAGE_CHARS: usize = 280;
+/// Local chat reaches players within this distance of the sender.
+pub const LOCAL_RANGE: f32 = 40.0;
+
+const PROFANITY: [&str; 5] = ["fuck", "shit", "bitch", "cunt", "asshole"];
+
+pub fn channel_key(channel_type: u8, scope_id: u64) -> String {
+    format!("{channel_type}:{scope_id}")
+}
+
+pub(crate) fn ensure_channel(ctx: &ReducerContext, channel_type: u8, scope_id: u64) -> ChatChannel {
+    let key = channel_key(channel_type, scope_id);
+    if let Some(channel) = ctx.db.chat_channel().channel_key().find(key.clone()) {
+        return channel;
+    }
+    ctx.db.chat_channel().insert(ChatChannel {
+        channel_id: 0,
+        channel_key: key,
+        channel_type,
+        scope_id,
+        created_at: ctx.timestamp,
+    })
+}
+
+/// Channel scopes `identity` belongs to. Whisper and system channels are
+/// shared; which of their messages a player sees is decided per message.
+pub(crate) fn member_scopes(ctx: &ViewContext, identity: Identity) -> Vec<(u8, u64)> {
+    let mut scopes = vec![(CHANNEL_WHISPER, 0), (CHANNEL_SYSTEM, 0)];
+    if let Some(session) = ctx.db.session_state().identity().find(identity) {
+        scopes.push((CHANNEL_REGION, session.region_id));
+        scopes.push((CHANNEL_LOCAL, session.region_id));
+    }
+    scopes.extend(
+        ctx.db
+            .empire_member_state()
+            .member_identity()
+            .filter(identity)
+            .map(|m| (CHANNEL_GUILD, m.empire_id)),
+    );
+    if let Some(member) = ctx.db.party_member().member_identity().find(identity) {
+        scopes.push((CHANNEL_PARTY, member.party_id));
+    }
+    scopes
+}
+
+pub(crate) fn is_member(ctx: &ViewContext, identity: Identity, channel_type: u8, scope_id: u64) -> bool {
+    member_scopes(ctx, identity).contains(&(channel_type, scope_id))
+}
+
+/// Per-message visibility inside a channel the viewer belongs to.
+pub(crate) fn can_see(
+    ctx: &ViewContext,
+    viewer: Identity,
+    channel: &ChatChannel,
+    message: &ChatMessage,
+) -> bool {
+    match channel.channel_type {
+        CHANNEL_WHISPER => message.sender_id == viewer || message.recipient_id == Some(viewer),
+        CHANNEL_LOCAL => ctx.db.transform_state().entity_id().find(viewer).is_some_and(|tf| {
+            let dx = tf.position[0] - message.pos_x;
+            let dz = tf.position[2] - message.pos_z;
+            dx * dx + dz * dz <= LOCAL_RANGE * LOCAL_RANGE
+        }),
+        _ => true,
+    }
+}
+
+pub(crate) fn set_mute(ctx: &ReducerContext, identity: Identity, duration: Duration, reason: &str) {
+    ctx.db.chat_mute().identity().delete(identity);
+    ctx.db.chat_mute().insert(ChatMute {
+        identity,
+        muted_until: ctx.timestamp + duration,
+        reason: reason.trim().to_string(),
+        muted_by: ctx.sender,
+        created_at: ctx.timestamp,
+    });
+}
+
+pub(crate) fn lift_mute(ctx: &ReducerContext, identity: Identity) {
+    ctx.db.chat_mute().identity().delete(identity);
+}
+
+/// Errors while `identity` is muted; expired mutes are dropped.
+pub(crate) fn require_unmuted(ctx: &ReducerContext, identity: Identity) -> Result<(), String> {
+    let Some(mute) = ctx.db.chat_mute().identity().find(identity) else {
+        return Ok(());
+    };
+    if mute.muted_until > ctx.timestamp {
+        return Err(format!("muted: {}", mute.reason));
+    }
+    lift_mute(ctx, identity);
+    Ok(())
+}
+
+/// Filter hook run on every message: enforces the length limit and masks
+/// profanity word by word.
+pub(crate) fn filter_text(text: &str) -> Result<String, String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err("message must not be empty".to_string());
+    }
+    if text.chars().count() > MAX_MESSAGE_CHARS {
+        return Err(format!("message must be <= {MAX_MESSAGE_CHARS} chars"));
+    }
+    let words: Vec<String> = text
+        .split(' ')
+        .map(|word| {
+            let bare = word.chars().filter(|c| c.is_alphanumeric()).collect::<String>().to_lowercase();
+            if PROFANITY.contains(&bare.as_str()) {
+                "*".repeat(word.chars().count())
+            } else {
+                word.to_string()
+            }
+        })
+        .collect();
+    Ok(words.join(" "))
+}
+
+/// Keeps the original of a message the filter changed for moderators.
+pub(crate) fn log_filtered(ctx: &ReducerContext, identity: Identity, channel_id: u64, original: &str) {
+    ctx.db.chat_filter_log().insert(ChatFilterLog {
+        log_id: 0,
+        identity,
+        channel_id,
+        original: original.to_string(),
+        created_at: ctx.timestamp,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{filter_text, MAX_MESSAGE_CHARS};
+
+    #[test]
+    fn filter_text_passes_clean_text_trimmed() {
+        assert_eq!(filter_text("  hello there  ").unwrap(), "hello there");
+    }
+
+    #[test]
+    fn filter_text_masks_profanity_ignoring_case_and_punctuation() {
+        assert_eq!(filter_text("oh SHIT, run").unwrap(), "oh ***** run");
+        assert_eq!(filter_text("shitake mushrooms").unwrap(), "shitake mushrooms");
+    }
+
+    #[test]
+    fn filter_text_rejects_empty_messages() {
+        assert!(filter_text("").is_err());
+        assert!(filter_text("   ").is_err());
+    }
+
+    #[test]
+    fn filter_text_counts_chars_not_bytes() {
+        assert!(filter_text(&"가".repeat(MAX_MESSAGE_CHARS)).is_ok());
+        assert!(filter_text(&"a".repeat(MAX_MESSAGE_CHARS + 1)).is_err());
+    }
+}
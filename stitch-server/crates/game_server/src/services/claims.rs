@@ -0,0 +1,278 @@
+use spacetimedb::{Identity, ReducerContext, Table};
+
+use crate::services::permissions;
+use crate::tables::{ClaimLocalState, ClaimMemberState, ClaimState, PermissionState};
+use crate::tables::claim_local_state::claim_local_state;
+use crate::tables::claim_member_state::claim_member_state;
+use crate::tables::claim_state::claim_state;
+use crate::tables::claim_tech::{claim_tech_def, claim_tech_state};
+use crate::tables::permission_state::permission_state;
+use crate::utils::hex::HexCoordinates;
+
+pub const RANK_GUEST: u8 = 0;
+pub const RANK_MEMBER: u8 = 1;
+pub const RANK_OFFICER: u8 = 2;
+pub const RANK_CO_OWNER: u8 = 3;
+pub const RANK_OWNER: u8 = 4;
+
+pub const MEMBER_STATUS_INVITED: u8 = 0;
+pub const MEMBER_STATUS_ACTIVE: u8 = 1;
+
+/// Tile budget before any tech is researched (hex radius 6).
+pub const BASE_MAX_TILES: u32 = 127;
+pub const STARTING_SUPPLIES: u32 = 100;
+
+pub fn member_key(claim_id: u64, identity: Identity) -> String {
+    format!("{claim_id}:{identity}")
+}
+
+pub fn rank_flags(rank: u8) -> u32 {
+    match rank {
+        RANK_GUEST => permissions::PERM_ENTER,
+        RANK_MEMBER => permissions::PERM_ENTER | permissions::PERM_CONTAINER | permissions::PERM_BUILD,
+        RANK_OFFICER => {
+            permissions::PERM_ENTER
+                | permissions::PERM_CONTAINER
+                | permissions::PERM_BUILD
+                | permissions::PERM_MANAGE_MEMBERS
+        }
+        RANK_CO_OWNER | RANK_OWNER => {
+            permissions::PERM_ENTER
+                | permissions::PERM_CONTAINER
+                | permissions::PERM_BUILD
+                | permissions::PERM_MANAGE_MEMBERS
+                | permissions::PERM_ADMIN
+        }
+        _ => 0,
+    }
+}
+
+/// The claim whose hex area (the same `hex_tile_count(radius)` tiles upkeep charges for) holds the
+/// given hex, if any.
+pub(crate) fn claim_covering(ctx: &ReducerContext, region_id: u64, x: i32, z: i32) -> Option<ClaimState> {
+    let hex = HexCoordinates::new(x, z);
+    ctx.db.claim_state().iter().find(|c| {
+        c.region_id == region_id
+            && HexCoordinates::new(c.center_x, c.center_z).distance_to(&hex) <= c.radius as i32
+    })
+}
+
+/// Rank of `identity` in the claim, counting only accepted memberships.
+/// The claim owner is always `RANK_OWNER`, even without a member row.
+pub(crate) fn active_rank(ctx: &ReducerContext, claim: &ClaimState, identity: Identity) -> Option<u8> {
+    if claim.owner_identity == identity {
+        return Some(RANK_OWNER);
+    }
+    ctx.db
+        .claim_member_state()
+        .member_key()
+        .find(member_key(claim.claim_id, identity))
+        .filter(|m| m.status == MEMBER_STATUS_ACTIVE)
+        .map(|m| m.rank)
+}
+
+pub(crate) fn member_flags(ctx: &ReducerContext, claim_id: u64, identity: Identity) -> u32 {
+    let Some(claim) = ctx.db.claim_state().claim_id().find(claim_id) else {
+        return 0;
+    };
+    active_rank(ctx, &claim, identity).map(rank_flags).unwrap_or(0)
+}
+
+pub(crate) fn upsert_member(
+    ctx: &ReducerContext,
+    claim_id: u64,
+    identity: Identity,
+    rank: u8,
+    status: u8,
+    invited_by: Identity,
+) {
+    let key = member_key(claim_id, identity);
+    if let Some(mut existing) = ctx.db.claim_member_state().member_key().find(key.clone()) {
+        existing.rank = rank;
+        existing.status = status;
+        existing.invited_by = invited_by;
+        existing.updated_at = ctx.timestamp;
+        ctx.db.claim_member_state().member_key().update(existing);
+        return;
+    }
+
+    ctx.db.claim_member_state().insert(ClaimMemberState {
+        member_key: key,
+        claim_id,
+        member_identity: identity,
+        rank,
+        status,
+        invited_by,
+        last_active_at: ctx.timestamp,
+        joined_at: ctx.timestamp,
+        updated_at: ctx.timestamp,
+    });
+}
+
+/// Hands the claim to `new_owner`; the previous owner stays on as co-owner.
+pub(crate) fn transfer_ownership(ctx: &ReducerContext, mut claim: ClaimState, new_owner: Identity) {
+    let previous_owner = claim.owner_identity;
+    let claim_id = claim.claim_id;
+
+    upsert_member(ctx, claim_id, previous_owner, RANK_CO_OWNER, MEMBER_STATUS_ACTIVE, new_owner);
+    upsert_member(ctx, claim_id, new_owner, RANK_OWNER, MEMBER_STATUS_ACTIVE, previous_owner);
+
+    ctx.db
+        .permission_state()
+        .permission_key()
+        .delete(permissions::permission_key(1, claim_id, previous_owner));
+    let key = permissions::permission_key(1, claim_id, new_owner);
+    if ctx.db.permission_state().permission_key().find(key.clone()).is_none() {
+        ctx.db.permission_state().insert(PermissionState {
+            permission_key: key,
+            target_kind: 1,
+            target_id: claim_id,
+            subject_identity: new_owner,
+            flags: permissions::PERM_BUILD | permissions::PERM_ADMIN,
+        });
+    }
+
+    claim.owner_identity = new_owner;
+    claim.updated_at = ctx.timestamp;
+    ctx.db.claim_state().claim_id().update(claim);
+
+    log::info!("claim ownership transferred: claim_id={claim_id} from={previous_owner} to={new_owner}");
+}
+
+pub(crate) fn touch_member_activity(ctx: &ReducerContext, identity: Identity) {
+    let rows: Vec<ClaimMemberState> = ctx
+        .db
+        .claim_member_state()
+        .iter()
+        .filter(|m| m.member_identity == identity && m.status == MEMBER_STATUS_ACTIVE)
+        .collect();
+    for mut row in rows {
+        row.last_active_at = ctx.timestamp;
+        ctx.db.claim_member_state().member_key().update(row);
+    }
+}
+
+/// Number of hex tiles covered by a claim of the given radius.
+pub fn hex_tile_count(radius: u32) -> u32 {
+    3u32.saturating_mul(radius)
+        .saturating_mul(radius.saturating_add(1))
+        .saturating_add(1)
+}
+
+pub fn tech_key(claim_id: u64, tech_id: u64) -> String {
+    format!("{claim_id}:{tech_id}")
+}
+
+pub(crate) fn has_tech(ctx: &ReducerContext, claim_id: u64, tech_id: u64) -> bool {
+    ctx.db.claim_tech_state().tech_key().find(tech_key(claim_id, tech_id)).is_some()
+}
+
+pub(crate) fn max_tiles(ctx: &ReducerContext, claim_id: u64) -> u32 {
+    ctx.db
+        .claim_tech_state()
+        .iter()
+        .filter(|t| t.claim_id == claim_id)
+        .filter_map(|t| ctx.db.claim_tech_def().tech_id().find(t.tech_id))
+        .map(|def| def.max_tiles)
+        .fold(BASE_MAX_TILES, u32::max)
+}
+
+/// Building types without an unlocking tech are always allowed; tech-unlocked types only inside a
+/// claim that has researched one of their techs.
+pub(crate) fn building_type_unlocked(
+    ctx: &ReducerContext,
+    claim_id: Option<u64>,
+    building_type: u32,
+) -> bool {
+    let mut unlocking = ctx
+        .db
+        .claim_tech_def()
+        .iter()
+        .filter(|def| def.unlocks_building_type != 0 && def.unlocks_building_type == building_type)
+        .peekable();
+    if unlocking.peek().is_none() {
+        return true;
+    }
+    claim_id.is_some_and(|claim_id| unlocking.any(|def| has_tech(ctx, claim_id, def.tech_id)))
+}
+
+pub(crate) fn ensure_local_state(ctx: &ReducerContext, claim: &ClaimState) -> ClaimLocalState {
+    if let Some(local) = ctx.db.claim_local_state().claim_id().find(claim.claim_id) {
+        return local;
+    }
+    ctx.db.claim_local_state().insert(ClaimLocalState {
+        claim_id: claim.claim_id,
+        supplies: STARTING_SUPPLIES,
+        population: 1,
+        num_tiles: hex_tile_count(claim.radius),
+        protected: true,
+        last_upkeep_at: ctx.timestamp,
+        updated_at: ctx.timestamp,
+    })
+}
+
+/// Claims that ran out of supplies no longer restrict building by non-members.
+pub(crate) fn is_protected(ctx: &ReducerContext, claim_id: u64) -> bool {
+    ctx.db
+        .claim_local_state()
+        .claim_id()
+        .find(claim_id)
+        .map(|local| local.protected)
+        .unwrap_or(true)
+}
+
+pub(crate) fn active_member_count(ctx: &ReducerContext, claim_id: u64) -> u32 {
+    ctx.db
+        .claim_member_state()
+        .iter()
+        .filter(|m| m.claim_id == claim_id && m.status == MEMBER_STATUS_ACTIVE && m.rank >= RANK_MEMBER)
+        .count() as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::permissions::{PERM_ADMIN, PERM_BUILD, PERM_ENTER, PERM_MANAGE_MEMBERS};
+    use crate::utils::hex::HexCoordinates;
+
+    #[test]
+    fn rank_flags_grow_with_rank() {
+        let ranks = [RANK_GUEST, RANK_MEMBER, RANK_OFFICER, RANK_CO_OWNER, RANK_OWNER];
+        for pair in ranks.windows(2) {
+            let (lower, higher) = (rank_flags(pair[0]), rank_flags(pair[1]));
+            assert_eq!(lower & higher, lower, "rank {} loses flags of rank {}", pair[1], pair[0]);
+        }
+    }
+
+    #[test]
+    fn rank_flags_gate_building_management_and_admin() {
+        assert_eq!(rank_flags(RANK_GUEST), PERM_ENTER);
+        assert_ne!(rank_flags(RANK_MEMBER) & PERM_BUILD, 0);
+        assert_eq!(rank_flags(RANK_MEMBER) & PERM_MANAGE_MEMBERS, 0);
+        assert_ne!(rank_flags(RANK_OFFICER) & PERM_MANAGE_MEMBERS, 0);
+        assert_eq!(rank_flags(RANK_OFFICER) & PERM_ADMIN, 0);
+        assert_eq!(rank_flags(RANK_CO_OWNER), rank_flags(RANK_OWNER));
+        assert_eq!(rank_flags(RANK_OWNER + 1), 0);
+    }
+
+    #[test]
+    fn hex_tile_count_matches_known_sizes() {
+        assert_eq!(hex_tile_count(0), 1);
+        assert_eq!(hex_tile_count(1), 7);
+        assert_eq!(hex_tile_count(3), 37);
+        assert_eq!(hex_tile_count(6), BASE_MAX_TILES);
+        assert_eq!(hex_tile_count(u32::MAX), u32::MAX);
+    }
+
+    #[test]
+    fn hex_tile_count_equals_hexes_within_radius() {
+        let center = HexCoordinates::new(0, 0);
+        for radius in 0..8i32 {
+            let covered = (-radius..=radius)
+                .flat_map(|x| (-radius..=radius).map(move |z| HexCoordinates::new(x, z)))
+                .filter(|hex| center.distance_to(hex) <= radius)
+                .count() as u32;
+            assert_eq!(covered, hex_tile_count(radius as u32), "radius {radius}");
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use spacetimedb::{Identity, ReducerContext, Table};
+
+use crate::tables::{CombatState, ThreatState};
+use crate::tables::combat::{combat_state, threat_state};
+
+/// Flags the player as in combat, creating their combat row if needed.
+pub(crate) fn enter_combat(ctx: &ReducerContext, identity: Identity, region_id: u64) {
+    match ctx.db.combat_state().identity().find(identity) {
+        Some(mut combat) => {
+            combat.in_combat = true;
+            combat.region_id = region_id;
+            combat.updated_at = ctx.timestamp;
+            ctx.db.combat_state().identity().update(combat);
+        }
+        None => {
+            ctx.db.combat_state().insert(CombatState {
+                identity,
+                region_id,
+                in_combat: true,
+                current_hp: 0,
+                last_attack_client_ts_ms: 0,
+                updated_at: ctx.timestamp,
+            });
+        }
+    }
+}
+
+pub(crate) fn add_threat(ctx: &ReducerContext, attacker: Identity, target: Identity, amount: i32) {
+    let threat_key = format!("{attacker}:{target}");
+    if let Some(mut threat) = ctx.db.threat_state().threat_key().find(threat_key.clone()) {
+        threat.threat += amount;
+        threat.updated_at = ctx.timestamp;
+        ctx.db.threat_state().threat_key().update(threat);
+    } else {
+        ctx.db.threat_state().insert(ThreatState {
+            threat_key,
+            attacker_identity: attacker,
+            target_identity: target,
+            threat: amount,
+            updated_at: ctx.timestamp,
+        });
+    }
+}
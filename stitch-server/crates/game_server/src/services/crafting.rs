@@ -0,0 +1,58 @@
+use spacetimedb::{Identity, ReducerContext};
+
+use crate::services::{achievements, claims, inventory, permissions, quests, skills};
+use crate::tables::{BuildingState, CraftJob, RecipeDef, RecipeItem};
+use crate::tables::crafting::craft_job;
+
+pub const JOB_STATUS_PROCESSING: u8 = 0;
+pub const JOB_STATUS_READY: u8 = 1;
+pub const MAX_ACTIVE_JOBS: usize = 3;
+pub const STATION_RANGE_SQ: f32 = 9.0;
+
+pub fn stacks(items: &[RecipeItem]) -> Vec<(u64, u32)> {
+    items.iter().map(|i| (i.item_def_id, i.quantity)).collect()
+}
+
+/// Stations inside a claim need claim entry rights; unclaimed stations are public.
+pub(crate) fn can_use_station(ctx: &ReducerContext, identity: Identity, building: &BuildingState) -> bool {
+    if building.owner_identity == identity {
+        return true;
+    }
+    match claims::claim_covering(ctx, building.region_id, building.hex_x, building.hex_z) {
+        Some(_) => {
+            permissions::has_permission_for(ctx, identity, 2, building.entity_id, permissions::PERM_ENTER)
+        }
+        None => true,
+    }
+}
+
+/// Grants the recipe outputs and skill xp, then removes the job. Returns
+/// `false` (leaving the job untouched) when the outputs do not fit yet.
+pub(crate) fn try_deliver(ctx: &ReducerContext, job: &CraftJob, recipe: &RecipeDef) -> Result<bool, String> {
+    let outputs = stacks(&recipe.outputs);
+    if !inventory::can_fit_all(ctx, job.owner_identity, &outputs)? {
+        return Ok(false);
+    }
+    inventory::add_all(ctx, job.owner_identity, &outputs)?;
+    skills::award_xp(ctx, job.owner_identity, recipe.skill_id, recipe.xp_reward);
+    quests::record(ctx, job.owner_identity, quests::COND_CRAFT, recipe.recipe_id, 1);
+    achievements::discover(ctx, job.owner_identity, achievements::KNOWLEDGE_RECIPE, recipe.recipe_id);
+    ctx.db.craft_job().job_id().delete(job.job_id);
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stacks_keep_recipe_order_and_repeated_items() {
+        let items = [
+            RecipeItem { item_def_id: 3, quantity: 2 },
+            RecipeItem { item_def_id: 1, quantity: 5 },
+            RecipeItem { item_def_id: 3, quantity: 1 },
+        ];
+        assert_eq!(stacks(&items), vec![(3, 2), (1, 5), (3, 1)]);
+        assert!(stacks(&[]).is_empty());
+    }
+}
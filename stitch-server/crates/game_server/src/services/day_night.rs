@@ -0,0 +1,68 @@
+use std::time::Duration;
+
+use spacetimedb::{ReducerContext, Table};
+
+use crate::services::balance;
+use crate::tables::DayNightState;
+use crate::tables::day_night::day_night_state;
+
+pub const DAY_START_HOUR: u8 = 6;
+pub const NIGHT_START_HOUR: u8 = 18;
+/// Hour reported before the clock row exists.
+const DEFAULT_HOUR: u8 = 12;
+
+pub(crate) fn phase_duration(ctx: &ReducerContext, is_day: bool) -> Duration {
+    let key = if is_day { balance::DAY_SECS } else { balance::NIGHT_SECS };
+    Duration::from_secs(balance::get_u64(ctx, key, 900).max(1))
+}
+
+/// Each phase spans 12 clock hours, however long it lasts in real time.
+pub(crate) fn hour_in_phase(is_day: bool, elapsed: Duration, duration: Duration) -> u8 {
+    let offset = (elapsed.as_secs_f64() / duration.as_secs_f64() * 12.0).min(11.0) as u8;
+    let start = if is_day { DAY_START_HOUR } else { NIGHT_START_HOUR };
+    (start + offset) % 24
+}
+
+pub(crate) fn state(ctx: &ReducerContext) -> Option<DayNightState> {
+    ctx.db.day_night_state().id().find(0)
+}
+
+pub(crate) fn current_hour(ctx: &ReducerContext) -> u8 {
+    state(ctx).map(|s| s.hour).unwrap_or(DEFAULT_HOUR)
+}
+
+/// Starts the clock at dawn of cycle 1 when it is missing.
+pub(crate) fn ensure_state(ctx: &ReducerContext) {
+    if state(ctx).is_some() {
+        return;
+    }
+    ctx.db.day_night_state().insert(DayNightState {
+        id: 0,
+        is_day: true,
+        hour: DAY_START_HOUR,
+        cycle_number: 1,
+        phase_started_at: ctx.timestamp,
+        updated_at: ctx.timestamp,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn phases_map_onto_twelve_clock_hours() {
+        let phase = Duration::from_secs(600);
+        assert_eq!(hour_in_phase(true, Duration::ZERO, phase), DAY_START_HOUR);
+        assert_eq!(hour_in_phase(true, Duration::from_secs(300), phase), 12);
+        assert_eq!(hour_in_phase(false, Duration::from_secs(50), phase), NIGHT_START_HOUR + 1);
+    }
+
+    #[test]
+    fn night_wraps_past_midnight_and_overruns_clamp() {
+        let phase = Duration::from_secs(120);
+        assert_eq!(hour_in_phase(false, Duration::from_secs(70), phase), 1);
+        assert_eq!(hour_in_phase(false, Duration::from_secs(500), phase), 5);
+        assert_eq!(hour_in_phase(true, Duration::from_secs(500), phase), 17);
+    }
+}
@@ -1,7 +1,16 @@
-use spacetimedb::ReducerContext;
+use spacetimedb::{ReducerContext, Table};
 
+use crate::tables::PriceIndex;
 use crate::tables::item_def::item_def;
 use crate::tables::item_stack::item_stack;
+use crate::tables::trade_market::price_index;
+
+/// Volume beyond which older fills stop weighing on the price index.
+const PRICE_INDEX_WINDOW: u64 = 1000;
+
+pub fn price_index_key(region_id: u64, item_def_id: u64) -> String {
+    format!("{region_id}:{item_def_id}")
+}
 
 pub(crate) fn slot_can_accept(
     ctx: &ReducerContext,
@@ -36,3 +45,49 @@ pub(crate) fn slot_can_accept(
     let used = (item_def.volume as i64) * (total as i64);
     Ok(used <= slot_volume as i64)
 }
+
+/// Supply points gained from turning `quantity` units of an item into claim or siege supplies.
+pub(crate) fn supply_value(ctx: &ReducerContext, item_def_id: u64, quantity: u32) -> Result<u32, String> {
+    let def = ctx
+        .db
+        .item_def()
+        .item_def_id()
+        .find(item_def_id)
+        .ok_or("item_def not found".to_string())?;
+    if def.volume <= 0 {
+        return Err("item has no supply value".to_string());
+    }
+    Ok((def.volume as u32).saturating_mul(quantity))
+}
+
+/// Folds a market fill into the regional price index.
+pub(crate) fn record_fill(
+    ctx: &ReducerContext,
+    region_id: u64,
+    item_def_id: u64,
+    unit_price: u64,
+    quantity: u32,
+) {
+    let key = price_index_key(region_id, item_def_id);
+    match ctx.db.price_index().index_key().find(key.clone()) {
+        Some(mut index) => {
+            let weight = index.volume.min(PRICE_INDEX_WINDOW) as u128;
+            let qty = quantity as u128;
+            let total = index.price_avg as u128 * weight + unit_price as u128 * qty;
+            index.price_avg = (total / (weight + qty).max(1)) as u64;
+            index.volume = index.volume.saturating_add(quantity as u64);
+            index.updated_at = ctx.timestamp;
+            ctx.db.price_index().index_key().update(index);
+        }
+        None => {
+            ctx.db.price_index().insert(PriceIndex {
+                index_key: key,
+                region_id,
+                item_def_id,
+                price_avg: unit_price,
+                volume: quantity as u64,
+                updated_at: ctx.timestamp,
+            });
+        }
+    }
+}
@@ -0,0 +1,210 @@
+use spacetimedb::{Identity, ReducerContext, Table};
+
+use crate::services::permissions;
+use crate::tables::{EmpireMemberState, EmpireNodeState, EmpireRankState, PermissionState};
+use crate::tables::building_state::building_state;
+use crate::tables::empire::{empire_claim_state, empire_member_state, empire_node_state, empire_rank_state};
+use crate::tables::permission_state::permission_state;
+use crate::utils::hex::HexCoordinates;
+
+pub const RANK_EMPEROR: u8 = 0;
+pub const RANK_CITIZEN: u8 = 4;
+
+// Low byte mirrors claim permission flags granted on every member claim;
+// the upper bits gate empire-level actions.
+pub const EMPIRE_PERM_CLAIM_MASK: u32 = 0x00FF;
+pub const EMPIRE_PERM_MANAGE_MEMBERS: u32 = 0x0100;
+pub const EMPIRE_PERM_MANAGE_CLAIMS: u32 = 0x0200;
+pub const EMPIRE_PERM_NODES: u32 = 0x0400;
+pub const EMPIRE_PERM_SIEGE: u32 = 0x0800;
+
+pub const EMPIRE_MIN_CLAIM_TIER: u32 = 2;
+pub const NODE_START_ENERGY: u32 = 100;
+pub const NODE_UPKEEP: u32 = 2;
+pub const NODE_INFLUENCE_RADIUS: u32 = 24;
+/// The only building type that can become an empire node.
+pub const WATCHTOWER_BUILDING_TYPE: u32 = 10;
+
+pub fn rank_key(empire_id: u64, rank: u8) -> String {
+    format!("{empire_id}:{rank}")
+}
+
+pub fn claim_invite_key(empire_id: u64, claim_id: u64) -> String {
+    format!("{empire_id}:{claim_id}")
+}
+
+pub fn member_key(empire_id: u64, identity: Identity) -> String {
+    format!("{empire_id}:{identity}")
+}
+
+pub fn default_rank_permissions(rank: u8) -> (&'static str, u32) {
+    let all_claim = permissions::PERM_ENTER | permissions::PERM_CONTAINER | permissions::PERM_BUILD;
+    match rank {
+        0 => (
+            "Emperor",
+            all_claim
+                | EMPIRE_PERM_MANAGE_MEMBERS
+                | EMPIRE_PERM_MANAGE_CLAIMS
+                | EMPIRE_PERM_NODES
+                | EMPIRE_PERM_SIEGE,
+        ),
+        1 => (
+            "Noble",
+            all_claim | EMPIRE_PERM_MANAGE_MEMBERS | EMPIRE_PERM_NODES | EMPIRE_PERM_SIEGE,
+        ),
+        2 => ("Knight", all_claim | EMPIRE_PERM_NODES | EMPIRE_PERM_SIEGE),
+        3 => ("Squire", permissions::PERM_ENTER | permissions::PERM_CONTAINER),
+        _ => ("Citizen", permissions::PERM_ENTER),
+    }
+}
+
+pub(crate) fn insert_default_ranks(ctx: &ReducerContext, empire_id: u64) {
+    for rank in RANK_EMPEROR..=RANK_CITIZEN {
+        let (title, perms) = default_rank_permissions(rank);
+        ctx.db.empire_rank_state().insert(EmpireRankState {
+            rank_key: rank_key(empire_id, rank),
+            empire_id,
+            rank,
+            title: title.to_string(),
+            permissions: perms,
+        });
+    }
+}
+
+pub(crate) fn member(ctx: &ReducerContext, empire_id: u64, identity: Identity) -> Option<EmpireMemberState> {
+    ctx.db.empire_member_state().member_key().find(member_key(empire_id, identity))
+}
+
+pub(crate) fn upsert_member(ctx: &ReducerContext, empire_id: u64, identity: Identity, rank: u8) {
+    if let Some(mut existing) = member(ctx, empire_id, identity) {
+        existing.rank = rank;
+        existing.updated_at = ctx.timestamp;
+        ctx.db.empire_member_state().member_key().update(existing);
+        return;
+    }
+    ctx.db.empire_member_state().insert(EmpireMemberState {
+        member_key: member_key(empire_id, identity),
+        empire_id,
+        member_identity: identity,
+        rank,
+        joined_at: ctx.timestamp,
+        updated_at: ctx.timestamp,
+    });
+}
+
+pub(crate) fn member_permissions(ctx: &ReducerContext, empire_id: u64, identity: Identity) -> u32 {
+    let Some(member) = member(ctx, empire_id, identity) else {
+        return 0;
+    };
+    ctx.db
+        .empire_rank_state()
+        .rank_key()
+        .find(rank_key(empire_id, member.rank))
+        .map(|r| r.permissions)
+        .unwrap_or(0)
+}
+
+pub(crate) fn require_permission(
+    ctx: &ReducerContext,
+    empire_id: u64,
+    required: u32,
+) -> Result<EmpireMemberState, String> {
+    let member = member(ctx, empire_id, ctx.sender).ok_or("not an empire member".to_string())?;
+    if member_permissions(ctx, empire_id, ctx.sender) & required != required {
+        return Err("insufficient empire rank permission".to_string());
+    }
+    Ok(member)
+}
+
+pub(crate) fn claim_empire_id(ctx: &ReducerContext, claim_id: u64) -> Option<u64> {
+    ctx.db.empire_claim_state().claim_id().find(claim_id).map(|c| c.empire_id)
+}
+
+/// Claim permission flags an empire member receives on claims belonging to their empire.
+pub(crate) fn claim_flags(ctx: &ReducerContext, claim_id: u64, identity: Identity) -> u32 {
+    match claim_empire_id(ctx, claim_id) {
+        Some(empire_id) => member_permissions(ctx, empire_id, identity) & EMPIRE_PERM_CLAIM_MASK,
+        None => 0,
+    }
+}
+
+/// The empire whose active watchtower projects influence over the given hex, if any.
+pub(crate) fn influence_at(ctx: &ReducerContext, region_id: u64, x: i32, z: i32) -> Option<EmpireNodeState> {
+    let hex = HexCoordinates::new(x, z);
+    ctx.db.empire_node_state().iter().find(|n| {
+        n.active
+            && n.region_id == region_id
+            && HexCoordinates::new(n.hex_x, n.hex_z).distance_to(&hex) <= n.influence_radius as i32
+    })
+}
+
+pub(crate) fn transfer_node(
+    ctx: &ReducerContext,
+    mut node: EmpireNodeState,
+    empire_id: u64,
+    new_owner: Identity,
+) {
+    if let Some(mut building) = ctx.db.building_state().entity_id().find(node.node_id) {
+        building.owner_identity = new_owner;
+        building.updated_at = ctx.timestamp;
+        ctx.db.building_state().entity_id().update(building);
+
+        // grants on the captured building go with it; the new owner gets the owner grant
+        let grants: Vec<String> = ctx
+            .db
+            .permission_state()
+            .iter()
+            .filter(|p| p.target_kind == 2 && p.target_id == node.node_id)
+            .map(|p| p.permission_key)
+            .collect();
+        for key in grants {
+            ctx.db.permission_state().permission_key().delete(key);
+        }
+        ctx.db.permission_state().insert(PermissionState {
+            permission_key: permissions::permission_key(2, node.node_id, new_owner),
+            target_kind: 2,
+            target_id: node.node_id,
+            subject_identity: new_owner,
+            flags: permissions::PERM_BUILD | permissions::PERM_ADMIN,
+        });
+    }
+
+    log::info!(
+        "empire node captured: node_id={} from_empire={} to_empire={}",
+        node.node_id,
+        node.empire_id,
+        empire_id
+    );
+    node.empire_id = empire_id;
+    node.energy = NODE_START_ENERGY / 2;
+    node.active = true;
+    node.updated_at = ctx.timestamp;
+    ctx.db.empire_node_state().node_id().update(node);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_the_emperor_manages_claims() {
+        for rank in 0..=5u8 {
+            let (_, flags) = default_rank_permissions(rank);
+            assert_eq!(flags & EMPIRE_PERM_MANAGE_CLAIMS != 0, rank == 0, "rank {rank}");
+        }
+    }
+
+    #[test]
+    fn lower_ranks_hold_a_subset_of_higher_rank_permissions() {
+        for rank in 0..4u8 {
+            let (_, higher) = default_rank_permissions(rank);
+            let (_, lower) = default_rank_permissions(rank + 1);
+            assert_eq!(lower & higher, lower, "rank {} exceeds rank {rank}", rank + 1);
+        }
+    }
+
+    #[test]
+    fn unknown_ranks_are_citizens() {
+        assert_eq!(default_rank_permissions(200), ("Citizen", permissions::PERM_ENTER));
+    }
+}
@@ -0,0 +1,36 @@
+use crate::services::world_gen::{BIOME_DESERT, BIOME_MOUNTAIN, BIOME_SWAMP, BIOME_TUNDRA};
+
+pub const HAZARD_COLD: u8 = 1;
+pub const HAZARD_HEAT: u8 = 2;
+pub const HAZARD_POISON: u8 = 3;
+
+/// Hazard tags carried by each biome.
+pub fn biome_hazards(biome_id: u16) -> &'static [u8] {
+    match biome_id {
+        BIOME_TUNDRA | BIOME_MOUNTAIN => &[HAZARD_COLD],
+        BIOME_DESERT => &[HAZARD_HEAT],
+        BIOME_SWAMP => &[HAZARD_POISON],
+        _ => &[],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::world_gen::{BIOME_FOREST, BIOME_GRASSLAND, BIOME_OCEAN};
+
+    #[test]
+    fn harsh_biomes_carry_their_hazard() {
+        assert_eq!(biome_hazards(BIOME_TUNDRA), &[HAZARD_COLD]);
+        assert_eq!(biome_hazards(BIOME_MOUNTAIN), &[HAZARD_COLD]);
+        assert_eq!(biome_hazards(BIOME_DESERT), &[HAZARD_HEAT]);
+        assert_eq!(biome_hazards(BIOME_SWAMP), &[HAZARD_POISON]);
+    }
+
+    #[test]
+    fn mild_and_unknown_biomes_are_safe() {
+        for biome in [BIOME_OCEAN, BIOME_GRASSLAND, BIOME_FOREST, 99] {
+            assert!(biome_hazards(biome).is_empty(), "biome {biome}");
+        }
+    }
+}
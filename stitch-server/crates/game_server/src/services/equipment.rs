@@ -0,0 +1,38 @@
+use spacetimedb::{Identity, ReducerContext, Table};
+
+use crate::tables::EquipmentSlot;
+use crate::tables::equipment::equipment_slot;
+
+pub const SLOT_MAIN_HAND: u8 = 1;
+pub const SLOT_OFF_HAND: u8 = 2;
+pub const SLOT_HEAD: u8 = 3;
+pub const SLOT_CHEST: u8 = 4;
+pub const SLOT_LEGS: u8 = 5;
+pub const SLOT_FEET: u8 = 6;
+pub const SLOT_BACK: u8 = 7;
+
+pub fn is_valid_slot(slot: u8) -> bool {
+    (SLOT_MAIN_HAND..=SLOT_BACK).contains(&slot)
+}
+
+pub fn slot_key(identity: Identity, slot: u8) -> String {
+    format!("{identity}:{slot}")
+}
+
+pub(crate) fn equipped(ctx: &ReducerContext, identity: Identity) -> Vec<EquipmentSlot> {
+    ctx.db.equipment_slot().iter().filter(|e| e.identity == identity).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_defined_slots_are_valid() {
+        for slot in SLOT_MAIN_HAND..=SLOT_BACK {
+            assert!(is_valid_slot(slot));
+        }
+        assert!(!is_valid_slot(0));
+        assert!(!is_valid_slot(SLOT_BACK + 1));
+    }
+}
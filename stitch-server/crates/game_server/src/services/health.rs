@@ -0,0 +1,122 @@
+use spacetimedb::{Identity, ReducerContext, Table};
+
+use crate::services::{stats, terrain};
+use crate::tables::{CharacterStats, ResourceState};
+use crate::tables::combat::combat_state;
+use crate::tables::movement::movement_actor_state;
+use crate::tables::resource_state::resource_state;
+use crate::tables::transform_state::transform_state;
+
+/// Status effect kept on players whose satiation has hit zero.
+pub const STARVING_EFFECT_ID: u32 = 6;
+
+/// The player's vitals, created at full values on first access.
+pub(crate) fn ensure(ctx: &ReducerContext, identity: Identity) -> ResourceState {
+    if let Some(state) = ctx.db.resource_state().identity().find(identity) {
+        return state;
+    }
+    let stats = stats::get(ctx, identity);
+    ctx.db.resource_state().insert(ResourceState {
+        identity,
+        hp: stats.max_hp,
+        stamina: stats.max_stamina,
+        satiation: stats.max_satiation,
+        last_damage_at: ctx.timestamp,
+        last_stamina_use_at: ctx.timestamp,
+        updated_at: ctx.timestamp,
+    })
+}
+
+pub(crate) fn is_downed(ctx: &ReducerContext, identity: Identity) -> bool {
+    ensure(ctx, identity).hp == 0
+}
+
+/// Deals damage from any source and returns the hp left. Resets the passive
+/// regeneration delay; a downed player takes no more damage, so hits cannot
+/// hold off their respawn.
+pub(crate) fn damage(ctx: &ReducerContext, identity: Identity, amount: i32) -> i32 {
+    let mut state = ensure(ctx, identity);
+    if state.hp == 0 {
+        return 0;
+    }
+    state.hp = (state.hp - amount.max(0)).max(0);
+    state.last_damage_at = ctx.timestamp;
+    state.updated_at = ctx.timestamp;
+    let hp = state.hp;
+    ctx.db.resource_state().identity().update(state);
+    if hp == 0 {
+        log::info!("player downed: identity={identity}");
+    }
+    hp
+}
+
+/// Spends stamina, failing without change if there is not enough.
+pub(crate) fn use_stamina(ctx: &ReducerContext, identity: Identity, amount: i32) -> Result<(), String> {
+    if amount <= 0 {
+        return Ok(());
+    }
+    let mut state = ensure(ctx, identity);
+    if state.hp == 0 {
+        return Err("you are incapacitated".to_string());
+    }
+    if state.stamina < amount {
+        return Err("not enough stamina".to_string());
+    }
+    state.stamina -= amount;
+    state.last_stamina_use_at = ctx.timestamp;
+    state.updated_at = ctx.timestamp;
+    ctx.db.resource_state().identity().update(state);
+    Ok(())
+}
+
+/// Clamps vitals after max values drop (unequipping, debuffs).
+pub(crate) fn clamp_to_stats(ctx: &ReducerContext, stats: &CharacterStats) {
+    let Some(mut state) = ctx.db.resource_state().identity().find(stats.identity) else {
+        return;
+    };
+    if state.hp <= stats.max_hp
+        && state.stamina <= stats.max_stamina
+        && state.satiation <= stats.max_satiation
+    {
+        return;
+    }
+    state.hp = state.hp.min(stats.max_hp);
+    state.stamina = state.stamina.min(stats.max_stamina);
+    state.satiation = state.satiation.min(stats.max_satiation);
+    state.updated_at = ctx.timestamp;
+    ctx.db.resource_state().identity().update(state);
+}
+
+/// Brings a downed player back at their region's spawn point with full hp and
+/// stamina and half a stomach, out of combat.
+pub(crate) fn respawn(ctx: &ReducerContext, identity: Identity) -> Result<(), String> {
+    let mut state = ensure(ctx, identity);
+    if state.hp > 0 {
+        return Err("player is not downed".to_string());
+    }
+    let mut transform = ctx
+        .db
+        .transform_state()
+        .entity_id()
+        .find(identity)
+        .ok_or("transform missing".to_string())?;
+    let stats = stats::get(ctx, identity);
+    state.hp = stats.max_hp;
+    state.stamina = stats.max_stamina;
+    state.satiation = state.satiation.max(stats.max_satiation / 2);
+    state.updated_at = ctx.timestamp;
+    ctx.db.resource_state().identity().update(state);
+
+    transform.position = terrain::spawn_position(ctx, transform.region_id);
+    transform.updated_at = ctx.timestamp;
+    ctx.db.transform_state().entity_id().update(transform);
+    // the jump to the spawn point is not a move; the next request starts fresh
+    ctx.db.movement_actor_state().identity().delete(identity);
+    if let Some(mut combat) = ctx.db.combat_state().identity().find(identity) {
+        combat.in_combat = false;
+        combat.updated_at = ctx.timestamp;
+        ctx.db.combat_state().identity().update(combat);
+    }
+    log::info!("player respawned: identity={identity}");
+    Ok(())
+}
@@ -0,0 +1,146 @@
+use spacetimedb::{Identity, ReducerContext, Table};
+
+use crate::services::permissions;
+use crate::tables::{DimensionDesc, DimensionNetwork, MovementActorState, TransformState};
+use crate::tables::building_state::building_state;
+use crate::tables::housing::{dimension_desc, dimension_network};
+use crate::tables::movement::movement_actor_state;
+use crate::tables::session_state::session_state;
+use crate::tables::transform_state::transform_state;
+
+/// Interior dimensions live in their own region id space above this base.
+pub const INTERIOR_REGION_BASE: u64 = 1 << 32;
+pub const INTERIOR_SPAWN: (f32, f32) = (0.0, 0.0);
+
+pub fn interior_region_id(dimension_id: u64) -> u64 {
+    INTERIOR_REGION_BASE + dimension_id
+}
+
+pub fn dimension_of_region(region_id: u64) -> Option<u64> {
+    region_id.checked_sub(INTERIOR_REGION_BASE)
+}
+
+pub(crate) fn create_interior(ctx: &ReducerContext, owner: Identity, building_id: u64) -> (u64, u64) {
+    let network_id = ctx
+        .db
+        .dimension_network()
+        .iter()
+        .map(|n| n.network_id)
+        .max()
+        .unwrap_or(0)
+        .saturating_add(1);
+    ctx.db.dimension_network().insert(DimensionNetwork {
+        network_id,
+        building_id,
+        owner_identity: owner,
+        collapse_respawn_at: None,
+    });
+    let dimension_id = spawn_dimension(ctx, network_id);
+    (network_id, dimension_id)
+}
+
+pub(crate) fn spawn_dimension(ctx: &ReducerContext, network_id: u64) -> u64 {
+    let dimension_id = ctx
+        .db
+        .dimension_desc()
+        .iter()
+        .map(|d| d.dimension_id)
+        .max()
+        .unwrap_or(0)
+        .saturating_add(1);
+    ctx.db.dimension_desc().insert(DimensionDesc {
+        dimension_id,
+        network_id,
+        spawn_x: INTERIOR_SPAWN.0,
+        spawn_z: INTERIOR_SPAWN.1,
+        collapse_at: None,
+    });
+    dimension_id
+}
+
+pub(crate) fn occupant_count(ctx: &ReducerContext, dimension_id: u64) -> usize {
+    let region_id = interior_region_id(dimension_id);
+    ctx.db.transform_state().iter().filter(|t| t.region_id == region_id).count()
+}
+
+pub(crate) fn interior_building_count(ctx: &ReducerContext, dimension_id: u64) -> usize {
+    let region_id = interior_region_id(dimension_id);
+    ctx.db
+        .building_state()
+        .iter()
+        .filter(|b| b.region_id == region_id && b.state != 2)
+        .count()
+}
+
+/// Interiors inherit permissions from their entrance building (and through it, the claim).
+pub(crate) fn dimension_permission(
+    ctx: &ReducerContext,
+    subject: Identity,
+    dimension_id: u64,
+    required: u32,
+) -> bool {
+    let Some(network) = ctx
+        .db
+        .dimension_desc()
+        .dimension_id()
+        .find(dimension_id)
+        .and_then(|d| ctx.db.dimension_network().network_id().find(d.network_id))
+    else {
+        return false;
+    };
+    if network.owner_identity == subject {
+        return true;
+    }
+    permissions::has_permission_for(ctx, subject, 2, network.building_id, required)
+}
+
+/// Teleports an identity, keeping session and movement history consistent so the
+/// next `move_to` is validated from the new position.
+pub(crate) fn relocate(ctx: &ReducerContext, identity: Identity, region_id: u64, position: Vec<f32>) {
+    let next_transform = TransformState {
+        entity_id: identity,
+        region_id,
+        position: position.clone(),
+        rotation: vec![0.0, 0.0, 0.0, 1.0],
+        updated_at: ctx.timestamp,
+    };
+    if ctx.db.transform_state().entity_id().find(identity).is_some() {
+        ctx.db.transform_state().entity_id().update(next_transform);
+    } else {
+        ctx.db.transform_state().insert(next_transform);
+    }
+
+    if let Some(mut session) = ctx.db.session_state().identity().find(identity) {
+        session.region_id = region_id;
+        session.last_active_at = ctx.timestamp;
+        ctx.db.session_state().identity().update(session);
+    }
+
+    if let Some(actor) = ctx.db.movement_actor_state().identity().find(identity) {
+        ctx.db.movement_actor_state().identity().update(MovementActorState {
+            region_id,
+            last_position: position,
+            updated_at: ctx.timestamp,
+            ..actor
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interior_region_ids_round_trip_to_their_dimension() {
+        for dimension_id in [0, 1, 42, u32::MAX as u64] {
+            assert_eq!(dimension_of_region(interior_region_id(dimension_id)), Some(dimension_id));
+        }
+    }
+
+    #[test]
+    fn overworld_regions_have_no_interior_dimension() {
+        assert_eq!(dimension_of_region(0), None);
+        assert_eq!(dimension_of_region(1), None);
+        assert_eq!(dimension_of_region(INTERIOR_REGION_BASE - 1), None);
+    }
+}
@@ -0,0 +1,347 @@
+use std::collections::BTreeMap;
+
+use spacetimedb::{Identity, ReducerContext, Table};
+
+use crate::reducers::inventory::inventory_bootstrap::next_item_instance_id;
+use crate::reducers::inventory::inventory_lock::ensure_not_locked;
+use crate::services::{achievements, stats};
+use crate::tables::{InventoryContainer, InventorySlot, ItemInstance, ItemStack};
+use crate::tables::inventory_container::inventory_container;
+use crate::tables::inventory_slot::inventory_slot;
+use crate::tables::item_def::item_def;
+use crate::tables::item_instance::item_instance;
+use crate::tables::item_stack::item_stack;
+use crate::tables::trade_market::{trade_offer, trade_session};
+
+pub(crate) fn main_container(ctx: &ReducerContext, owner: Identity) -> Result<InventoryContainer, String> {
+    ctx.db
+        .inventory_container()
+        .iter()
+        .find(|c| c.owner_identity == owner && c.inventory_index == 0)
+        .ok_or("main inventory container not found".to_string())
+}
+
+fn occupied_slots(ctx: &ReducerContext, container_id: u64) -> Vec<InventorySlot> {
+    let mut slots: Vec<InventorySlot> = ctx
+        .db
+        .inventory_slot()
+        .iter()
+        .filter(|s| s.container_id == container_id && s.item_instance_id != 0)
+        .collect();
+    slots.sort_by_key(|s| s.slot_index);
+    slots
+}
+
+/// `(item_def_id, quantity)` held in each occupied main-inventory slot.
+pub(crate) fn main_inventory_stacks(ctx: &ReducerContext, owner: Identity) -> Vec<(u64, u32)> {
+    let Ok(container) = main_container(ctx, owner) else {
+        return Vec::new();
+    };
+    occupied_slots(ctx, container.container_id)
+        .into_iter()
+        .filter_map(|slot| {
+            let inst = ctx.db.item_instance().item_instance_id().find(slot.item_instance_id)?;
+            let stack = ctx.db.item_stack().item_instance_id().find(slot.item_instance_id)?;
+            Some((inst.item_def_id, stack.quantity))
+        })
+        .collect()
+}
+
+pub(crate) fn count_items(ctx: &ReducerContext, owner: Identity, item_def_id: u64) -> u32 {
+    main_inventory_stacks(ctx, owner)
+        .into_iter()
+        .filter(|(def_id, _)| *def_id == item_def_id)
+        .fold(0u32, |acc, (_, qty)| acc.saturating_add(qty))
+}
+
+/// Total `volume * quantity` of `items`.
+pub(crate) fn volume_of(ctx: &ReducerContext, items: &[(u64, u32)]) -> Result<i64, String> {
+    let mut total = 0i64;
+    for (item_def_id, quantity) in items {
+        let item_def = ctx
+            .db
+            .item_def()
+            .item_def_id()
+            .find(*item_def_id)
+            .ok_or("item_def not found".to_string())?;
+        total += item_def.volume.max(0) as i64 * *quantity as i64;
+    }
+    Ok(total)
+}
+
+/// Carry volume left before the owner's `character_stats.carry_volume` is reached.
+pub(crate) fn volume_headroom(ctx: &ReducerContext, owner: Identity) -> Result<i64, String> {
+    let used = volume_of(ctx, &main_inventory_stacks(ctx, owner))?;
+    Ok(stats::get(ctx, owner).carry_volume as i64 - used)
+}
+
+/// How many more units of `item_def_id` fit: top-ups of existing stacks plus empty slots.
+pub(crate) fn free_capacity(ctx: &ReducerContext, owner: Identity, item_def_id: u64) -> Result<u32, String> {
+    let container = main_container(ctx, owner)?;
+    let item_def = ctx
+        .db
+        .item_def()
+        .item_def_id()
+        .find(item_def_id)
+        .ok_or("item_def not found".to_string())?;
+
+    let mut capacity = 0u32;
+    for slot in ctx.db.inventory_slot().iter().filter(|s| s.container_id == container.container_id) {
+        if slot.item_instance_id == 0 {
+            capacity = capacity.saturating_add(item_def.max_stack);
+            continue;
+        }
+        let Some(inst) = ctx.db.item_instance().item_instance_id().find(slot.item_instance_id) else {
+            continue;
+        };
+        if inst.item_def_id != item_def_id {
+            continue;
+        }
+        let held = ctx
+            .db
+            .item_stack()
+            .item_instance_id()
+            .find(slot.item_instance_id)
+            .map(|s| s.quantity)
+            .unwrap_or(0);
+        capacity = capacity.saturating_add(item_def.max_stack.saturating_sub(held));
+    }
+    Ok(capacity)
+}
+
+/// Item instances the owner has offered in a trade that has not completed or been cancelled.
+fn offered_instances(ctx: &ReducerContext, owner: Identity) -> Vec<u64> {
+    ctx.db
+        .trade_offer()
+        .iter()
+        .filter(|o| o.owner_identity == owner)
+        .filter(|o| {
+            ctx.db
+                .trade_session()
+                .session_id()
+                .find(o.session_id.clone())
+                .is_some_and(|s| s.phase <= 1)
+        })
+        .map(|o| o.item_instance_id)
+        .collect()
+}
+
+/// Removes items from the owner's main inventory. Refuses while the container is locked and
+/// never spends stacks offered in an open trade.
+pub(crate) fn consume_items(
+    ctx: &ReducerContext,
+    owner: Identity,
+    item_def_id: u64,
+    quantity: u32,
+) -> Result<(), String> {
+    let container = main_container(ctx, owner)?;
+    ensure_not_locked(ctx, container.container_id)?;
+
+    let offered = offered_instances(ctx, owner);
+    let slots: Vec<InventorySlot> = occupied_slots(ctx, container.container_id)
+        .into_iter()
+        .filter(|s| !offered.contains(&s.item_instance_id))
+        .collect();
+    let available = slots
+        .iter()
+        .filter(|s| {
+            ctx.db
+                .item_instance()
+                .item_instance_id()
+                .find(s.item_instance_id)
+                .is_some_and(|inst| inst.item_def_id == item_def_id)
+        })
+        .filter_map(|s| ctx.db.item_stack().item_instance_id().find(s.item_instance_id))
+        .fold(0u32, |acc, stack| acc.saturating_add(stack.quantity));
+    if available < quantity {
+        return Err("not enough materials in inventory".to_string());
+    }
+
+    let mut remaining = quantity;
+    for slot in slots {
+        if remaining == 0 {
+            break;
+        }
+
+        let inst = match ctx.db.item_instance().item_instance_id().find(slot.item_instance_id) {
+            Some(v) => v,
+            None => continue,
+        };
+        if inst.item_def_id != item_def_id {
+            continue;
+        }
+
+        let mut stack = match ctx.db.item_stack().item_instance_id().find(slot.item_instance_id) {
+            Some(v) => v,
+            None => continue,
+        };
+
+        let taken = stack.quantity.min(remaining);
+        stack.quantity -= taken;
+        remaining -= taken;
+
+        if stack.quantity == 0 {
+            ctx.db.item_stack().item_instance_id().delete(slot.item_instance_id);
+            ctx.db.item_instance().item_instance_id().delete(slot.item_instance_id);
+
+            let mut next_slot = slot;
+            next_slot.item_instance_id = 0;
+            ctx.db.inventory_slot().slot_key().update(next_slot);
+        } else {
+            ctx.db.item_stack().item_instance_id().update(stack);
+        }
+    }
+
+    Ok(())
+}
+
+/// Adds items to the owner's main inventory. Fails without touching any row
+/// when the full quantity does not fit.
+pub(crate) fn add_items(
+    ctx: &ReducerContext,
+    owner: Identity,
+    item_def_id: u64,
+    quantity: u32,
+) -> Result<(), String> {
+    if free_capacity(ctx, owner, item_def_id)? < quantity {
+        return Err("no inventory space".to_string());
+    }
+    if volume_of(ctx, &[(item_def_id, quantity)])? > volume_headroom(ctx, owner)? {
+        return Err("carrying too much".to_string());
+    }
+    let container = main_container(ctx, owner)?;
+    let item_def = ctx
+        .db
+        .item_def()
+        .item_def_id()
+        .find(item_def_id)
+        .ok_or("item_def not found".to_string())?;
+
+    let mut remaining = quantity;
+
+    // merge into existing stacks first
+    for slot in occupied_slots(ctx, container.container_id) {
+        if remaining == 0 {
+            break;
+        }
+        let inst = match ctx.db.item_instance().item_instance_id().find(slot.item_instance_id) {
+            Some(v) => v,
+            None => continue,
+        };
+        if inst.item_def_id != item_def_id {
+            continue;
+        }
+
+        let mut stack = match ctx.db.item_stack().item_instance_id().find(slot.item_instance_id) {
+            Some(v) => v,
+            None => continue,
+        };
+
+        if stack.quantity >= item_def.max_stack {
+            continue;
+        }
+
+        let can_add = item_def.max_stack - stack.quantity;
+        let delta = can_add.min(remaining);
+        stack.quantity += delta;
+        remaining -= delta;
+        ctx.db.item_stack().item_instance_id().update(stack);
+    }
+
+    let mut empty_slots: Vec<InventorySlot> = ctx
+        .db
+        .inventory_slot()
+        .iter()
+        .filter(|s| s.container_id == container.container_id && s.item_instance_id == 0)
+        .collect();
+    empty_slots.sort_by_key(|s| s.slot_index);
+
+    for mut slot in empty_slots {
+        if remaining == 0 {
+            break;
+        }
+
+        let put = remaining.min(item_def.max_stack);
+        let new_instance = next_item_instance_id(ctx);
+        ctx.db.item_instance().insert(ItemInstance {
+            item_instance_id: new_instance,
+            item_def_id,
+            item_type: 0,
+            durability: 100,
+            bound: false,
+        });
+        ctx.db.item_stack().insert(ItemStack {
+            item_instance_id: new_instance,
+            quantity: put,
+        });
+
+        slot.item_instance_id = new_instance;
+        ctx.db.inventory_slot().slot_key().update(slot);
+        remaining -= put;
+    }
+
+    achievements::discover(ctx, owner, achievements::KNOWLEDGE_ITEM, item_def_id);
+    Ok(())
+}
+
+/// Whether every `(item_def_id, quantity)` fits at once. Different items
+/// compete for the same empty slots, so per-item capacity is not enough.
+pub(crate) fn can_fit_all(
+    ctx: &ReducerContext,
+    owner: Identity,
+    items: &[(u64, u32)],
+) -> Result<bool, String> {
+    let container = main_container(ctx, owner)?;
+    if volume_of(ctx, items)? > volume_headroom(ctx, owner)? {
+        return Ok(false);
+    }
+    let mut wanted: BTreeMap<u64, u32> = BTreeMap::new();
+    for (item_def_id, quantity) in items {
+        let entry = wanted.entry(*item_def_id).or_insert(0);
+        *entry = entry.saturating_add(*quantity);
+    }
+
+    let slots: Vec<InventorySlot> = ctx
+        .db
+        .inventory_slot()
+        .iter()
+        .filter(|s| s.container_id == container.container_id)
+        .collect();
+    let empty_slots = slots.iter().filter(|s| s.item_instance_id == 0).count() as u32;
+
+    let mut needed_slots = 0u32;
+    for (item_def_id, quantity) in wanted {
+        let item_def = ctx
+            .db
+            .item_def()
+            .item_def_id()
+            .find(item_def_id)
+            .ok_or("item_def not found".to_string())?;
+        let max_stack = item_def.max_stack.max(1);
+        let top_up: u32 = slots
+            .iter()
+            .filter(|s| s.item_instance_id != 0)
+            .filter(|s| {
+                ctx.db
+                    .item_instance()
+                    .item_instance_id()
+                    .find(s.item_instance_id)
+                    .is_some_and(|inst| inst.item_def_id == item_def_id)
+            })
+            .filter_map(|s| ctx.db.item_stack().item_instance_id().find(s.item_instance_id))
+            .fold(0u32, |acc, stack| acc.saturating_add(max_stack.saturating_sub(stack.quantity)));
+        needed_slots = needed_slots.saturating_add(quantity.saturating_sub(top_up).div_ceil(max_stack));
+    }
+    Ok(needed_slots <= empty_slots)
+}
+
+/// All-or-nothing grant of several item stacks.
+pub(crate) fn add_all(ctx: &ReducerContext, owner: Identity, items: &[(u64, u32)]) -> Result<(), String> {
+    if !can_fit_all(ctx, owner, items)? {
+        return Err("no inventory space".to_string());
+    }
+    for (item_def_id, quantity) in items {
+        add_items(ctx, owner, *item_def_id, *quantity)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,23 @@
+use spacetimedb::ReducerContext;
+
+use crate::tables::item_list::item_list_def;
+
+/// Rolls every entry of an item list once; returns `(item_def_id, quantity)`
+/// pairs for the entries that hit.
+pub(crate) fn roll_item_list(ctx: &ReducerContext, item_list_id: u64) -> Vec<(u64, u32)> {
+    let Some(list) = ctx.db.item_list_def().item_list_id().find(item_list_id) else {
+        return Vec::new();
+    };
+    let mut drops = Vec::new();
+    for entry in list.entries {
+        if ctx.random::<f32>() >= entry.probability {
+            continue;
+        }
+        let span = entry.max_qty.saturating_sub(entry.min_qty);
+        let qty = entry.min_qty + if span == 0 { 0 } else { ctx.random::<u32>() % (span + 1) };
+        if qty > 0 {
+            drops.push((entry.item_def_id, qty));
+        }
+    }
+    drops
+}
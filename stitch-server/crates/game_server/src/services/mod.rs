@@ -1,2 +1,35 @@
+pub mod abilities;
+pub mod achievements;
+pub mod balance;
+pub mod buffs;
+pub mod chat;
+pub mod claims;
+pub mod combat;
+pub mod crafting;
+pub mod day_night;
 pub mod economy;
+pub mod empires;
+pub mod environment;
+pub mod equipment;
+pub mod health;
+pub mod housing;
+pub mod inventory;
+pub mod loot;
+pub mod moderation;
+pub mod movement;
+pub mod npc_agent;
+pub mod npc_cost;
+pub mod npc_memory;
+pub mod npc_policy;
+pub mod npc_schedule;
+pub mod npc_shop;
+pub mod party;
+pub mod pathfinding;
 pub mod permissions;
+pub mod quests;
+pub mod resources;
+pub mod skills;
+pub mod stats;
+pub mod terrain;
+pub mod wallet;
+pub mod world_gen;
@@ -0,0 +1,273 @@
+use std::time::Duration;
+
+use spacetimedb::{Identity, ReducerContext, Table, ViewContext};
+
+use crate::services::{chat, permissions};
+use crate::tables::{BanList, ModerationAction, ReportQueue};
+use crate::tables::account::account;
+use crate::tables::chat::{chat_channel, chat_message};
+use crate::tables::moderation::{ban_list, moderation_action, report_queue};
+use crate::tables::movement::movement_violation;
+use crate::tables::session_state::session_state;
+use crate::tables::trade_market::{market_fill, trade_session};
+
+pub const CATEGORY_CHAT: u8 = 1;
+pub const CATEGORY_CHEATING: u8 = 2;
+pub const CATEGORY_TRADE: u8 = 3;
+pub const CATEGORY_OTHER: u8 = 4;
+
+pub const REPORT_OPEN: u8 = 0;
+pub const REPORT_TRIAGED: u8 = 1;
+pub const REPORT_RESOLVED: u8 = 2;
+pub const REPORT_DISMISSED: u8 = 3;
+
+pub const PRIORITY_LOW: u8 = 1;
+pub const PRIORITY_HIGH: u8 = 3;
+
+pub const ACTION_WARN: u8 = 1;
+pub const ACTION_MUTE: u8 = 2;
+pub const ACTION_BAN: u8 = 3;
+/// Lifts any mute and ban on the target.
+pub const ACTION_RESTORE: u8 = 4;
+
+pub const MAX_DETAILS_CHARS: usize = 500;
+const SNAPSHOT_CHAT_LINES: usize = 10;
+const SNAPSHOT_TRADES: usize = 5;
+
+pub(crate) fn is_moderator(ctx: &ReducerContext) -> bool {
+    permissions::is_admin(ctx) || permissions::has_permission(ctx, 0, 0, permissions::PERM_MODERATE)
+}
+
+/// Admins and moderators; only admins may act on them.
+pub(crate) fn is_staff(ctx: &ViewContext, identity: Identity) -> bool {
+    permissions::global_flags(ctx, identity) & (permissions::PERM_ADMIN | permissions::PERM_MODERATE) != 0
+}
+
+/// Fails for a banned identity, lifting the ban first if it has run out.
+/// Covers reducers that work without a session.
+pub(crate) fn require_not_banned(ctx: &ReducerContext, identity: Identity) -> Result<(), String> {
+    lift_expired_ban(ctx, identity);
+    if ctx.db.ban_list().identity().find(identity).is_some() {
+        return Err("account banned".to_string());
+    }
+    Ok(())
+}
+
+pub(crate) fn is_pending(report: &ReportQueue) -> bool {
+    report.status == REPORT_OPEN || report.status == REPORT_TRIAGED
+}
+
+/// A pending report by `reporter` on `target` filed within `window`.
+pub(crate) fn recent_report(
+    ctx: &ReducerContext,
+    reporter: Identity,
+    target: Identity,
+    window: Duration,
+) -> Option<ReportQueue> {
+    ctx.db.report_queue().iter().find(|r| {
+        r.reporter_id == reporter
+            && r.target_id == target
+            && is_pending(r)
+            && ctx.timestamp.duration_since(r.created_at).is_none_or(|age| age < window)
+    })
+}
+
+/// Starts at low priority and rises with each other player who has a
+/// pending report on the same target.
+pub(crate) fn initial_priority(ctx: &ReducerContext, reporter: Identity, target: Identity) -> u8 {
+    let mut others: Vec<Identity> = ctx
+        .db
+        .report_queue()
+        .iter()
+        .filter(|r| r.target_id == target && r.reporter_id != reporter && is_pending(r))
+        .map(|r| r.reporter_id)
+        .collect();
+    others.sort();
+    others.dedup();
+    (PRIORITY_LOW as usize + others.len()).min(PRIORITY_HIGH as usize) as u8
+}
+
+/// The target's latest chat lines that the reporter was able to read.
+pub(crate) fn chat_snapshot(ctx: &ReducerContext, reporter: Identity, target: Identity) -> String {
+    let view = ctx.as_read_only();
+    let mut lines: Vec<_> = ctx
+        .db
+        .chat_message()
+        .iter()
+        .filter(|m| m.sender_id == target)
+        .filter(|m| {
+            ctx.db.chat_channel().channel_id().find(m.channel_id).is_some_and(|channel| {
+                chat::is_member(&view, reporter, channel.channel_type, channel.scope_id)
+                    && chat::can_see(&view, reporter, &channel, m)
+            })
+        })
+        .collect();
+    lines.sort_by_key(|m| m.sent_at);
+    let start = lines.len().saturating_sub(SNAPSHOT_CHAT_LINES);
+    lines[start..].iter().map(|m| format!("[{}] {}", m.channel_id, m.text)).collect::<Vec<_>>().join("\n")
+}
+
+pub(crate) fn movement_violations(ctx: &ReducerContext, target: Identity) -> u32 {
+    ctx.db.movement_violation().iter().filter(|v| v.identity == target).count() as u32
+}
+
+/// The target's latest trade sessions plus a count of their market fills.
+pub(crate) fn trade_snapshot(ctx: &ReducerContext, target: Identity) -> String {
+    let mut sessions: Vec<_> = ctx
+        .db
+        .trade_session()
+        .iter()
+        .filter(|s| s.initiator_identity == target || s.partner_identity == target)
+        .collect();
+    sessions.sort_by_key(|s| s.updated_at);
+    let start = sessions.len().saturating_sub(SNAPSHOT_TRADES);
+    let mut lines: Vec<String> = sessions[start..]
+        .iter()
+        .map(|s| {
+            let initiated = s.initiator_identity == target;
+            let partner = if initiated { s.partner_identity } else { s.initiator_identity };
+            format!("trade {} with {partner} phase={}", s.session_id, s.phase)
+        })
+        .collect();
+    let fills = ctx
+        .db
+        .market_fill()
+        .iter()
+        .filter(|f| f.buyer_identity == target || f.seller_identity == target)
+        .count();
+    lines.push(format!("market_fills={fills}"));
+    lines.join("\n")
+}
+
+/// Records a moderation action and carries it out: mutes go to `chat_mute`,
+/// bans block the account and end its session, restores lift both.
+pub(crate) fn apply_action(
+    ctx: &ReducerContext,
+    target: Identity,
+    action: u8,
+    duration_secs: u64,
+    reason: &str,
+) -> Result<ModerationAction, String> {
+    if !(ACTION_WARN..=ACTION_RESTORE).contains(&action) {
+        return Err("unknown moderation action".to_string());
+    }
+    if action == ACTION_MUTE && duration_secs == 0 {
+        return Err("mute needs a duration".to_string());
+    }
+    if is_staff(&ctx.as_read_only(), target) && !permissions::is_admin(ctx) {
+        return Err("only admins can act on admins and moderators".to_string());
+    }
+    let record = ctx.db.moderation_action().insert(ModerationAction {
+        action_id: 0,
+        target_id: target,
+        action,
+        reason: reason.trim().to_string(),
+        duration_secs,
+        moderator_id: ctx.sender,
+        created_at: ctx.timestamp,
+    });
+    match action {
+        ACTION_MUTE => {
+            chat::set_mute(ctx, target, Duration::from_secs(duration_secs), reason);
+        }
+        ACTION_BAN => {
+            let duration = Duration::from_secs(duration_secs);
+            let banned_until = (duration_secs > 0).then(|| ctx.timestamp + duration);
+            ctx.db.ban_list().identity().delete(target);
+            ctx.db.ban_list().insert(BanList {
+                identity: target,
+                banned_until,
+                reason: record.reason.clone(),
+                action_id: record.action_id,
+                created_at: ctx.timestamp,
+            });
+            set_account_status(ctx, target, 1);
+            ctx.db.session_state().identity().delete(target);
+        }
+        ACTION_RESTORE => {
+            chat::lift_mute(ctx, target);
+            lift_ban(ctx, target);
+        }
+        _ => {}
+    }
+    log::info!("moderation action {action} on {target} by {}", ctx.sender);
+    Ok(record)
+}
+
+fn set_account_status(ctx: &ReducerContext, identity: Identity, status: u8) {
+    if let Some(mut account) = ctx.db.account().identity().find(identity) {
+        account.status = status;
+        ctx.db.account().identity().update(account);
+    }
+}
+
+fn lift_ban(ctx: &ReducerContext, identity: Identity) {
+    if ctx.db.ban_list().identity().delete(identity) {
+        set_account_status(ctx, identity, 0);
+    }
+}
+
+/// Lifts a timed ban once it has run out; called on sign-in.
+pub(crate) fn lift_expired_ban(ctx: &ReducerContext, identity: Identity) {
+    let expired = ctx
+        .db
+        .ban_list()
+        .identity()
+        .find(identity)
+        .and_then(|ban| ban.banned_until)
+        .is_some_and(|until| until <= ctx.timestamp);
+    if expired {
+        lift_ban(ctx, identity);
+    }
+}
+
+/// Closes a pending report with a resolution and an optional linked action.
+pub(crate) fn close_report(
+    ctx: &ReducerContext,
+    mut report: ReportQueue,
+    status: u8,
+    action_id: u64,
+    resolution: &str,
+) {
+    report.status = status;
+    report.action_id = action_id;
+    report.resolution = resolution.trim().to_string();
+    report.assigned_to = report.assigned_to.or(Some(ctx.sender));
+    report.updated_at = ctx.timestamp;
+    ctx.db.report_queue().report_id().update(report);
+}
+
+#[cfg(test)]
+mod tests {
+    use spacetimedb::Timestamp;
+
+    use super::*;
+
+    fn report(status: u8) -> ReportQueue {
+        ReportQueue {
+            report_id: 1,
+            reporter_id: Identity::ZERO,
+            target_id: Identity::ZERO,
+            category: CATEGORY_CHAT,
+            details: String::new(),
+            chat_snapshot: String::new(),
+            movement_violations: 0,
+            trade_snapshot: String::new(),
+            status,
+            priority: PRIORITY_LOW,
+            assigned_to: None,
+            action_id: 0,
+            resolution: String::new(),
+            created_at: Timestamp::UNIX_EPOCH,
+            updated_at: Timestamp::UNIX_EPOCH,
+        }
+    }
+
+    #[test]
+    fn open_and_triaged_reports_are_pending() {
+        assert!(is_pending(&report(REPORT_OPEN)));
+        assert!(is_pending(&report(REPORT_TRIAGED)));
+        assert!(!is_pending(&report(REPORT_RESOLVED)));
+        assert!(!is_pending(&report(REPORT_DISMISSED)));
+    }
+}
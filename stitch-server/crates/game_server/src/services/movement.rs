@@ -0,0 +1,11 @@
+use spacetimedb::{Identity, ReducerContext};
+
+use crate::services::stats;
+
+/// World units per second on foot, before equipment and buff modifiers.
+pub const BASE_MOVE_SPEED: f32 = 6.0;
+
+/// Current movement speed, read from the cached character stats.
+pub(crate) fn movement_speed(ctx: &ReducerContext, identity: Identity) -> f32 {
+    stats::get(ctx, identity).move_speed
+}
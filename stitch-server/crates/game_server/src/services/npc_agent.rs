@@ -0,0 +1,193 @@
+use std::time::Duration;
+
+use spacetimedb::{Identity, ReducerContext, Table};
+
+use crate::services::{balance, inventory, npc_cost, npc_memory, npc_policy, pathfinding, terrain};
+use crate::tables::{NpcActionProposal, NpcActionRequest, NpcActionResult, NpcState};
+use crate::tables::npc_action::{npc_action_lease, npc_action_request, npc_action_result, npc_worker};
+use crate::tables::npc_quest::npc_state;
+use crate::utils::hex::HexCoordinates;
+
+pub const ACTION_MOVE: u8 = 1;
+pub const ACTION_TALK: u8 = 2;
+pub const ACTION_TRADE: u8 = 3;
+pub const ACTION_QUEST: u8 = 4;
+/// Proposal-only actions, checked by `npc_policy`.
+pub const ACTION_GIVE_ITEM: u8 = 5;
+pub const ACTION_SET_PRICE: u8 = 6;
+
+pub const REQUEST_PENDING: u8 = 0;
+pub const REQUEST_LEASED: u8 = 1;
+pub const REQUEST_DONE: u8 = 2;
+pub const REQUEST_FAILED: u8 = 3;
+
+pub const RESULT_APPLIED: u8 = 1;
+pub const RESULT_REJECTED: u8 = 2;
+pub const RESULT_FAILED: u8 = 3;
+
+pub const MAX_REPLY_CHARS: usize = 500;
+pub const MAX_MOVE_HEXES: i32 = 32;
+/// Scripted line used when no worker answers in time.
+pub const FALLBACK_REPLY: &str = "The villager seems lost in thought.";
+
+pub(crate) fn is_worker(ctx: &ReducerContext, identity: Identity) -> bool {
+    ctx.db.npc_worker().identity().find(identity).is_some()
+}
+
+pub(crate) fn lease_duration(ctx: &ReducerContext) -> Duration {
+    Duration::from_secs(balance::get_u64(ctx, balance::NPC_LEASE_SECS, 30))
+}
+
+pub(crate) fn max_attempts(ctx: &ReducerContext) -> u32 {
+    balance::get_u64(ctx, balance::NPC_MAX_ATTEMPTS, 3) as u32
+}
+
+/// Queues a request for the worker. Talk requests whose normalized input
+/// has a cached reply are answered from `npc_response_cache`, and regions over
+/// their token budget get the scripted fallback; neither reaches the worker.
+/// Session requests answer the session's latest turn.
+pub(crate) fn enqueue(
+    ctx: &ReducerContext,
+    npc: &NpcState,
+    requested_by: Identity,
+    action_type: u8,
+    session_id: u64,
+    input: &str,
+) {
+    // in-session replies depend on the player's dialog and memory, so only greetings are shared
+    let cache_key = if action_type == ACTION_TALK && session_id == 0 {
+        npc_cost::cache_key(npc.npc_id, action_type, input)
+    } else {
+        String::new()
+    };
+    let request = ctx.db.npc_action_request().insert(NpcActionRequest {
+        request_id: 0,
+        npc_id: npc.npc_id,
+        action_type,
+        requested_by,
+        region_id: npc.region_id,
+        session_id,
+        context: npc_memory::build_context(ctx, npc, requested_by, action_type, session_id, input),
+        cache_key,
+        status: REQUEST_PENDING,
+        attempts: 0,
+        lease_owner: None,
+        lease_expires_at: None,
+        created_at: ctx.timestamp,
+        updated_at: ctx.timestamp,
+    });
+    if session_id != 0 {
+        npc_memory::link_request(ctx, session_id, request.request_id);
+    }
+
+    if let Some(reply) = npc_cost::cached_reply(ctx, &request.cache_key) {
+        npc_cost::count_request(ctx, npc, true, false);
+        npc_memory::apply_proposal(ctx, &request, &scripted_talk(&reply));
+        finish(ctx, request, RESULT_APPLIED, reply, "cache hit".to_string());
+    } else if npc_cost::over_budget(ctx, npc.region_id) {
+        npc_cost::count_request(ctx, npc, false, true);
+        let detail = "region npc budget exceeded".to_string();
+        finish(ctx, request, RESULT_FAILED, FALLBACK_REPLY.to_string(), detail);
+    } else {
+        npc_cost::count_request(ctx, npc, false, false);
+    }
+}
+
+/// Proposal for a reply the module produced itself, without side effects.
+fn scripted_talk(reply: &str) -> NpcActionProposal {
+    NpcActionProposal {
+        action_type: ACTION_TALK,
+        reply: reply.to_string(),
+        target_x: 0,
+        target_z: 0,
+        quest_chain_id: 0,
+        item_def_id: 0,
+        quantity: 0,
+        unit_price: 0,
+        memory_summary: String::new(),
+        memory_tag: String::new(),
+        affinity_delta: 0,
+    }
+}
+
+/// Carries out a proposal that passed `npc_policy::check` and returns a short detail for the result row.
+pub(crate) fn apply(
+    ctx: &ReducerContext,
+    request: &NpcActionRequest,
+    proposal: &NpcActionProposal,
+) -> Result<String, String> {
+    let detail = match proposal.action_type {
+        ACTION_MOVE => {
+            let npc = ctx.db.npc_state().npc_id().find(request.npc_id).ok_or("npc not found".to_string())?;
+            let start = terrain::hex_at(npc.pos_x, npc.pos_z);
+            let goal = HexCoordinates::new(proposal.target_x, proposal.target_z);
+            let limit = pathfinding::DEFAULT_NODE_LIMIT;
+            let path = pathfinding::find_region_path(ctx, npc.region_id, start, goal, limit)?;
+            let nav_path = pathfinding::store_npc_path(ctx, npc.npc_id, npc.region_id, &path);
+            format!("path_id={}", nav_path.path_id)
+        }
+        ACTION_QUEST if proposal.quest_chain_id != 0 => {
+            let tag = format!("offered quest chain {}", proposal.quest_chain_id);
+            npc_memory::append_long_memory(ctx, request.npc_id, request.requested_by, &tag);
+            npc_policy::note_quest_offer(ctx, request.requested_by);
+            format!("quest_chain_id={}", proposal.quest_chain_id)
+        }
+        ACTION_GIVE_ITEM => {
+            inventory::add_items(ctx, request.requested_by, proposal.item_def_id, proposal.quantity)?;
+            npc_policy::note_item_grant(ctx, request.requested_by, proposal.quantity);
+            format!("item_def_id={} quantity={}", proposal.item_def_id, proposal.quantity)
+        }
+        ACTION_SET_PRICE => {
+            npc_policy::set_price(ctx, request.npc_id, proposal.item_def_id, proposal.unit_price);
+            format!("item_def_id={} unit_price={}", proposal.item_def_id, proposal.unit_price)
+        }
+        _ => String::new(),
+    };
+    npc_memory::apply_proposal(ctx, request, proposal);
+    if proposal.action_type == ACTION_TALK {
+        npc_cost::store_reply(ctx, &request.cache_key, request.npc_id, &proposal.reply);
+    }
+    Ok(detail)
+}
+
+/// Closes a request with a result row and drops its lease.
+pub(crate) fn finish(
+    ctx: &ReducerContext,
+    mut request: NpcActionRequest,
+    result_status: u8,
+    reply: String,
+    detail: String,
+) {
+    ctx.db.npc_action_lease().request_id().delete(request.request_id);
+    ctx.db.npc_action_result().insert(NpcActionResult {
+        request_id: request.request_id,
+        npc_id: request.npc_id,
+        requested_by: request.requested_by,
+        action_type: request.action_type,
+        status: result_status,
+        reply,
+        detail,
+        applied_at: ctx.timestamp,
+    });
+    request.status = if result_status == RESULT_APPLIED { REQUEST_DONE } else { REQUEST_FAILED };
+    request.lease_owner = None;
+    request.lease_expires_at = None;
+    request.updated_at = ctx.timestamp;
+    ctx.db.npc_action_request().request_id().update(request);
+}
+
+/// Returns a leased request to the queue, or fails it with the scripted
+/// fallback once it has used up its attempts.
+pub(crate) fn release(ctx: &ReducerContext, mut request: NpcActionRequest, reason: &str) {
+    if request.attempts >= max_attempts(ctx) {
+        log::info!("npc action failed: request_id={} reason={reason}", request.request_id);
+        finish(ctx, request, RESULT_FAILED, FALLBACK_REPLY.to_string(), reason.to_string());
+        return;
+    }
+    ctx.db.npc_action_lease().request_id().delete(request.request_id);
+    request.status = REQUEST_PENDING;
+    request.lease_owner = None;
+    request.lease_expires_at = None;
+    request.updated_at = ctx.timestamp;
+    ctx.db.npc_action_request().request_id().update(request);
+}
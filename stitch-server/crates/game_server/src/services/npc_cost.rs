@@ -0,0 +1,183 @@
+use std::time::Duration;
+
+use spacetimedb::{ReducerContext, Table};
+
+use crate::services::balance;
+use crate::tables::{NpcActionUsage, NpcCostMetrics, NpcResponseCache, NpcState};
+use crate::tables::npc_cost::{npc_cost_metrics, npc_region_budget, npc_response_cache};
+use crate::utils::time::day_of;
+
+/// Share of the region budget at which usage gets logged as a warning.
+const BUDGET_ALERT_PCT: u64 = 80;
+
+pub fn metric_key(npc_id: u64, day: u32) -> String {
+    format!("{npc_id}:{day}")
+}
+
+/// Lowercase words only, so "Hello there!" and "hello,  there" share a key.
+fn normalize(input: &str) -> String {
+    input
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// FNV-1a; stable across module versions, unlike `DefaultHasher`.
+fn fnv1a(text: &str) -> u64 {
+    text.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, b| (hash ^ b as u64).wrapping_mul(0x0100_0000_01b3))
+}
+
+pub fn cache_key(npc_id: u64, action_type: u8, input: &str) -> String {
+    format!("{:016x}", fnv1a(&format!("{npc_id}|{action_type}|{}", normalize(input))))
+}
+
+/// Live cached reply for `key`; an expired entry is dropped on the way.
+pub(crate) fn cached_reply(ctx: &ReducerContext, key: &str) -> Option<String> {
+    if key.is_empty() {
+        return None;
+    }
+    let mut entry = ctx.db.npc_response_cache().cache_key().find(key.to_string())?;
+    if entry.expires_at <= ctx.timestamp {
+        ctx.db.npc_response_cache().cache_key().delete(entry.cache_key);
+        return None;
+    }
+    entry.hits = entry.hits.saturating_add(1);
+    let reply = entry.reply.clone();
+    ctx.db.npc_response_cache().cache_key().update(entry);
+    Some(reply)
+}
+
+pub(crate) fn store_reply(ctx: &ReducerContext, key: &str, npc_id: u64, reply: &str) {
+    if key.is_empty() || reply.trim().is_empty() {
+        return;
+    }
+    let ttl = Duration::from_secs(balance::get_u64(ctx, balance::NPC_CACHE_TTL_SECS, 300));
+    ctx.db.npc_response_cache().cache_key().delete(key.to_string());
+    ctx.db.npc_response_cache().insert(NpcResponseCache {
+        cache_key: key.to_string(),
+        npc_id,
+        reply: reply.to_string(),
+        hits: 0,
+        created_at: ctx.timestamp,
+        expires_at: ctx.timestamp + ttl,
+    });
+}
+
+pub(crate) fn purge_expired_cache(ctx: &ReducerContext) {
+    let expired: Vec<String> = ctx
+        .db
+        .npc_response_cache()
+        .iter()
+        .filter(|c| c.expires_at <= ctx.timestamp)
+        .map(|c| c.cache_key)
+        .collect();
+    for key in expired {
+        ctx.db.npc_response_cache().cache_key().delete(key);
+    }
+}
+
+fn bump(ctx: &ReducerContext, npc_id: u64, region_id: u64, update: impl FnOnce(&mut NpcCostMetrics)) {
+    let day = day_of(ctx.timestamp);
+    match ctx.db.npc_cost_metrics().metric_key().find(metric_key(npc_id, day)) {
+        Some(mut metrics) => {
+            update(&mut metrics);
+            metrics.updated_at = ctx.timestamp;
+            ctx.db.npc_cost_metrics().metric_key().update(metrics);
+        }
+        None => {
+            let mut metrics = NpcCostMetrics {
+                metric_key: metric_key(npc_id, day),
+                npc_id,
+                region_id,
+                day,
+                requests: 0,
+                cache_hits: 0,
+                fallbacks: 0,
+                tokens_in: 0,
+                tokens_out: 0,
+                latency_ms_total: 0,
+                latency_ms_max: 0,
+                updated_at: ctx.timestamp,
+            };
+            update(&mut metrics);
+            ctx.db.npc_cost_metrics().insert(metrics);
+        }
+    }
+}
+
+pub(crate) fn count_request(ctx: &ReducerContext, npc: &NpcState, cache_hit: bool, fallback: bool) {
+    bump(ctx, npc.npc_id, npc.region_id, |m| {
+        m.requests = m.requests.saturating_add(1);
+        m.cache_hits = m.cache_hits.saturating_add(cache_hit as u32);
+        m.fallbacks = m.fallbacks.saturating_add(fallback as u32);
+    });
+}
+
+pub(crate) fn record_usage(ctx: &ReducerContext, npc_id: u64, region_id: u64, usage: &NpcActionUsage) {
+    let cap = region_cap(ctx, region_id);
+    let before = region_tokens_today(ctx, region_id);
+    bump(ctx, npc_id, region_id, |m| {
+        m.tokens_in = m.tokens_in.saturating_add(usage.tokens_in as u64);
+        m.tokens_out = m.tokens_out.saturating_add(usage.tokens_out as u64);
+        m.latency_ms_total = m.latency_ms_total.saturating_add(usage.latency_ms as u64);
+        m.latency_ms_max = m.latency_ms_max.max(usage.latency_ms);
+    });
+    let after = before + usage.tokens_in as u64 + usage.tokens_out as u64;
+    let alert_at = cap * BUDGET_ALERT_PCT / 100;
+    if cap > 0 && before < alert_at && after >= alert_at {
+        log::warn!("npc token budget at {BUDGET_ALERT_PCT}%: region_id={region_id} used={after} cap={cap}");
+    }
+}
+
+fn region_cap(ctx: &ReducerContext, region_id: u64) -> u64 {
+    match ctx.db.npc_region_budget().region_id().find(region_id) {
+        Some(budget) => budget.daily_token_cap,
+        None => balance::get_u64(ctx, balance::NPC_REGION_DAILY_TOKENS, 0),
+    }
+}
+
+fn region_tokens_today(ctx: &ReducerContext, region_id: u64) -> u64 {
+    let day = day_of(ctx.timestamp);
+    ctx.db
+        .npc_cost_metrics()
+        .iter()
+        .filter(|m| m.region_id == region_id && m.day == day)
+        .map(|m| m.tokens_in + m.tokens_out)
+        .sum()
+}
+
+/// Whether the region spent its daily token cap; its npcs then answer with
+/// the scripted fallback instead of queueing worker requests.
+pub(crate) fn over_budget(ctx: &ReducerContext, region_id: u64) -> bool {
+    let cap = region_cap(ctx, region_id);
+    cap > 0 && region_tokens_today(ctx, region_id) >= cap
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_drops_case_and_punctuation() {
+        assert_eq!(normalize("Hello there!"), "hello there");
+        assert_eq!(normalize("  hello,  THERE "), "hello there");
+        assert_eq!(normalize("?!"), "");
+    }
+
+    #[test]
+    fn fnv1a_matches_the_reference_vectors() {
+        assert_eq!(fnv1a(""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a("a"), 0xaf63_dc4c_8601_ec8c);
+    }
+
+    #[test]
+    fn cache_key_shares_rephrasings_but_not_npcs_or_actions() {
+        let key = cache_key(1, 2, "Hello there!");
+        assert_eq!(key.len(), 16);
+        assert_eq!(key, cache_key(1, 2, "hello,  there"));
+        assert_ne!(key, cache_key(2, 2, "Hello there!"));
+        assert_ne!(key, cache_key(1, 3, "Hello there!"));
+    }
+}
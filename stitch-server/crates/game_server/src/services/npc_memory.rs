@@ -0,0 +1,313 @@
+use spacetimedb::{Identity, ReducerContext, Table};
+
+use crate::tables::{
+    NpcActionProposal, NpcActionRequest, NpcConversationSession, NpcConversationTurn, NpcMemoryLong,
+    NpcMemoryShort, NpcRelation, NpcState,
+};
+use crate::tables::npc_conversation::{
+    npc_conversation_session, npc_conversation_turn, npc_memory_long, npc_memory_short, npc_relation,
+};
+
+pub const SESSION_ACTIVE: u8 = 1;
+pub const SESSION_CLOSED: u8 = 2;
+pub const SESSION_TIMED_OUT: u8 = 3;
+
+pub const SESSION_IDLE_SECS: u64 = 600;
+/// Turns folded into the short-term memory at a time.
+pub const SUMMARY_EVERY_TURNS: u32 = 8;
+pub const MAX_INPUT_CHARS: usize = 280;
+pub const MAX_SHORT_MEMORY_CHARS: usize = 600;
+pub const MAX_LONG_MEMORY_CHARS: usize = 1000;
+pub const MAX_CONTEXT_CHARS: usize = 2000;
+pub const MAX_AFFINITY_DELTA: i32 = 5;
+const RECENT_TURNS_IN_CONTEXT: usize = 4;
+
+pub fn memory_key(npc_id: u64, identity: Identity) -> String {
+    format!("{npc_id}:{identity}")
+}
+
+pub fn turn_key(session_id: u64, turn_index: u32) -> String {
+    format!("{session_id}:{turn_index}")
+}
+
+/// First `max` chars of `text`, trimmed.
+pub fn truncate(text: &str, max: usize) -> String {
+    text.trim().chars().take(max).collect()
+}
+
+/// Last `max` chars of `text`; memories keep their most recent part.
+fn keep_tail(text: &str, max: usize) -> String {
+    let len = text.chars().count();
+    text.chars().skip(len.saturating_sub(max)).collect()
+}
+
+pub(crate) fn relation(ctx: &ReducerContext, npc_id: u64, identity: Identity) -> Option<NpcRelation> {
+    ctx.db.npc_relation().relation_key().find(memory_key(npc_id, identity))
+}
+
+pub(crate) fn adjust_relation(
+    ctx: &ReducerContext,
+    npc_id: u64,
+    identity: Identity,
+    affinity_delta: i32,
+    trust_delta: i32,
+) {
+    match relation(ctx, npc_id, identity) {
+        Some(mut rel) => {
+            rel.affinity = (rel.affinity + affinity_delta).clamp(-100, 100);
+            rel.trust = (rel.trust + trust_delta).clamp(-100, 100);
+            rel.interactions = rel.interactions.saturating_add(1);
+            rel.updated_at = ctx.timestamp;
+            ctx.db.npc_relation().relation_key().update(rel);
+        }
+        None => {
+            ctx.db.npc_relation().insert(NpcRelation {
+                relation_key: memory_key(npc_id, identity),
+                npc_id,
+                identity,
+                affinity: affinity_delta.clamp(-100, 100),
+                trust: trust_delta.clamp(-100, 100),
+                interactions: 1,
+                updated_at: ctx.timestamp,
+            });
+        }
+    }
+}
+
+pub(crate) fn active_session(
+    ctx: &ReducerContext,
+    npc_id: u64,
+    identity: Identity,
+) -> Option<NpcConversationSession> {
+    ctx.db
+        .npc_conversation_session()
+        .iter()
+        .find(|s| s.npc_id == npc_id && s.identity == identity && s.status == SESSION_ACTIVE)
+}
+
+fn session_turns(ctx: &ReducerContext, session_id: u64) -> Vec<NpcConversationTurn> {
+    let mut turns: Vec<NpcConversationTurn> =
+        ctx.db.npc_conversation_turn().iter().filter(|t| t.session_id == session_id).collect();
+    turns.sort_by_key(|t| t.turn_index);
+    turns
+}
+
+fn turn_line(turn: &NpcConversationTurn) -> String {
+    format!("P: {} / N: {}", turn.input_summary, turn.output_summary)
+}
+
+fn set_short_memory(ctx: &ReducerContext, npc_id: u64, identity: Identity, summary: String) {
+    let key = memory_key(npc_id, identity);
+    let summary = keep_tail(&summary, MAX_SHORT_MEMORY_CHARS);
+    match ctx.db.npc_memory_short().memory_key().find(key.clone()) {
+        Some(mut mem) => {
+            mem.summary = summary;
+            mem.updated_at = ctx.timestamp;
+            ctx.db.npc_memory_short().memory_key().update(mem);
+        }
+        None => {
+            ctx.db.npc_memory_short().insert(NpcMemoryShort {
+                memory_key: key,
+                npc_id,
+                identity,
+                summary,
+                updated_at: ctx.timestamp,
+            });
+        }
+    }
+}
+
+pub(crate) fn append_long_memory(ctx: &ReducerContext, npc_id: u64, identity: Identity, tag: &str) {
+    let key = memory_key(npc_id, identity);
+    let tag = truncate(tag, MAX_INPUT_CHARS);
+    match ctx.db.npc_memory_long().memory_key().find(key.clone()) {
+        Some(mut mem) => {
+            mem.summary = keep_tail(&format!("{}; {tag}", mem.summary), MAX_LONG_MEMORY_CHARS);
+            mem.updated_at = ctx.timestamp;
+            ctx.db.npc_memory_long().memory_key().update(mem);
+        }
+        None => {
+            ctx.db.npc_memory_long().insert(NpcMemoryLong {
+                memory_key: key,
+                npc_id,
+                identity,
+                summary: tag,
+                updated_at: ctx.timestamp,
+            });
+        }
+    }
+}
+
+/// Summarization hook: appends the session's turns from `from_index` on to
+/// the short-term memory. Runs every `SUMMARY_EVERY_TURNS` answered turns
+/// and when a session ends.
+pub(crate) fn fold_turns(ctx: &ReducerContext, session: &NpcConversationSession, from_index: u32) {
+    let recent: Vec<String> = session_turns(ctx, session.session_id)
+        .iter()
+        .filter(|t| t.turn_index >= from_index)
+        .map(turn_line)
+        .collect();
+    if recent.is_empty() {
+        return;
+    }
+    let previous = ctx
+        .db
+        .npc_memory_short()
+        .memory_key()
+        .find(memory_key(session.npc_id, session.identity))
+        .map(|m| m.summary)
+        .unwrap_or_default();
+    let joined = recent.join("\n");
+    let summary = if previous.is_empty() { joined } else { format!("{previous}\n{joined}") };
+    set_short_memory(ctx, session.npc_id, session.identity, summary);
+}
+
+pub(crate) fn open_session(ctx: &ReducerContext, npc_id: u64, identity: Identity) -> NpcConversationSession {
+    if let Some(existing) = active_session(ctx, npc_id, identity) {
+        return existing;
+    }
+    adjust_relation(ctx, npc_id, identity, 1, 0);
+    ctx.db.npc_conversation_session().insert(NpcConversationSession {
+        session_id: 0,
+        npc_id,
+        identity,
+        status: SESSION_ACTIVE,
+        turn_count: 0,
+        opened_at: ctx.timestamp,
+        last_active_at: ctx.timestamp,
+        closed_at: None,
+    })
+}
+
+/// Ends a session: folds its remaining turns into memory and drops them.
+pub(crate) fn close_session(ctx: &ReducerContext, mut session: NpcConversationSession, status: u8) {
+    fold_turns(ctx, &session, session.turn_count / SUMMARY_EVERY_TURNS * SUMMARY_EVERY_TURNS);
+    for turn in session_turns(ctx, session.session_id) {
+        ctx.db.npc_conversation_turn().turn_key().delete(turn.turn_key);
+    }
+    session.status = status;
+    session.closed_at = Some(ctx.timestamp);
+    ctx.db.npc_conversation_session().session_id().update(session);
+}
+
+/// Records the player's side of a turn.
+pub(crate) fn record_input(ctx: &ReducerContext, mut session: NpcConversationSession, text: &str) {
+    let turn_index = session.turn_count;
+    ctx.db.npc_conversation_turn().insert(NpcConversationTurn {
+        turn_key: turn_key(session.session_id, turn_index),
+        session_id: session.session_id,
+        turn_index,
+        request_id: 0,
+        input_summary: truncate(text, MAX_INPUT_CHARS),
+        output_summary: String::new(),
+        created_at: ctx.timestamp,
+    });
+    session.turn_count += 1;
+    session.last_active_at = ctx.timestamp;
+    ctx.db.npc_conversation_session().session_id().update(session);
+}
+
+/// Ties a queued request to the session's latest turn, which it answers.
+pub(crate) fn link_request(ctx: &ReducerContext, session_id: u64, request_id: u64) {
+    let Some(session) = ctx.db.npc_conversation_session().session_id().find(session_id) else {
+        return;
+    };
+    let Some(turn_index) = session.turn_count.checked_sub(1) else {
+        return;
+    };
+    if let Some(mut turn) = ctx.db.npc_conversation_turn().turn_key().find(turn_key(session_id, turn_index)) {
+        turn.request_id = request_id;
+        ctx.db.npc_conversation_turn().turn_key().update(turn);
+    }
+}
+
+/// Applies the memory side of an accepted proposal: the npc's reply lands in
+/// the turn, worker-provided summaries replace or extend memory, and the
+/// relation moves by at most `MAX_AFFINITY_DELTA`.
+pub(crate) fn apply_proposal(ctx: &ReducerContext, request: &NpcActionRequest, proposal: &NpcActionProposal) {
+    let (npc_id, identity) = (request.npc_id, request.requested_by);
+    if request.session_id != 0 {
+        let turn = ctx
+            .db
+            .npc_conversation_turn()
+            .iter()
+            .find(|t| t.session_id == request.session_id && t.request_id == request.request_id);
+        if let Some(mut turn) = turn {
+            turn.output_summary = truncate(&proposal.reply, MAX_INPUT_CHARS);
+            let answered = turn.turn_index + 1;
+            ctx.db.npc_conversation_turn().turn_key().update(turn);
+            let session = ctx.db.npc_conversation_session().session_id().find(request.session_id);
+            if let Some(session) = session.filter(|_| answered % SUMMARY_EVERY_TURNS == 0) {
+                fold_turns(ctx, &session, answered - SUMMARY_EVERY_TURNS);
+            }
+        }
+    }
+    if !proposal.memory_summary.trim().is_empty() {
+        set_short_memory(ctx, npc_id, identity, truncate(&proposal.memory_summary, MAX_SHORT_MEMORY_CHARS));
+    }
+    if !proposal.memory_tag.trim().is_empty() {
+        append_long_memory(ctx, npc_id, identity, &proposal.memory_tag);
+    }
+    let delta = proposal.affinity_delta.clamp(-MAX_AFFINITY_DELTA, MAX_AFFINITY_DELTA);
+    if delta != 0 {
+        adjust_relation(ctx, npc_id, identity, delta, 0);
+    }
+}
+
+/// Worker context assembled from npc state, relation, memories and the
+/// session's latest turns, capped at `MAX_CONTEXT_CHARS`.
+pub(crate) fn build_context(
+    ctx: &ReducerContext,
+    npc: &NpcState,
+    caller: Identity,
+    action_type: u8,
+    session_id: u64,
+    input: &str,
+) -> String {
+    let key = memory_key(npc.npc_id, caller);
+    let (affinity, trust) =
+        relation(ctx, npc.npc_id, caller).map(|r| (r.affinity, r.trust)).unwrap_or((0, 0));
+    let long = ctx.db.npc_memory_long().memory_key().find(key.clone()).map(|m| m.summary).unwrap_or_default();
+    let short = ctx.db.npc_memory_short().memory_key().find(key).map(|m| m.summary).unwrap_or_default();
+
+    let mut lines = vec![
+        format!("npc_id={}", npc.npc_id),
+        format!("region_id={}", npc.region_id),
+        format!("npc_pos={:.1},{:.1}", npc.pos_x, npc.pos_z),
+        format!("caller={caller}"),
+        format!("action_type={action_type}"),
+        format!("affinity={affinity} trust={trust}"),
+        format!("input={}", truncate(input, MAX_INPUT_CHARS)),
+        format!("long_memory={}", keep_tail(&long, MAX_LONG_MEMORY_CHARS / 2)),
+        format!("short_memory={}", keep_tail(&short, MAX_SHORT_MEMORY_CHARS / 2)),
+    ];
+    if session_id != 0 {
+        let turns = session_turns(ctx, session_id);
+        let start = turns.len().saturating_sub(RECENT_TURNS_IN_CONTEXT);
+        lines.extend(turns[start..].iter().filter(|t| !t.output_summary.is_empty()).map(turn_line));
+    }
+    truncate(&lines.join("\n"), MAX_CONTEXT_CHARS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncate_trims_then_keeps_the_head() {
+        assert_eq!(truncate("  hello world  ", 5), "hello");
+        assert_eq!(truncate("short", 50), "short");
+    }
+
+    #[test]
+    fn keep_tail_keeps_the_most_recent_chars() {
+        assert_eq!(keep_tail("old news; new news", 8), "new news");
+        assert_eq!(keep_tail("tiny", 50), "tiny");
+    }
+
+    #[test]
+    fn limits_count_chars_not_bytes() {
+        assert_eq!(truncate("안녕하세요", 2), "안녕");
+        assert_eq!(keep_tail("안녕하세요", 2), "세요");
+    }
+}
@@ -0,0 +1,368 @@
+use std::time::Duration;
+
+use spacetimedb::{Identity, ReducerContext, Table};
+
+use crate::services::npc_agent::{
+    ACTION_GIVE_ITEM, ACTION_MOVE, ACTION_QUEST, ACTION_SET_PRICE, ACTION_TALK, ACTION_TRADE, MAX_MOVE_HEXES,
+    MAX_REPLY_CHARS,
+};
+use crate::services::{balance, npc_shop, quests, skills, terrain};
+use crate::tables::{
+    NpcActionProposal, NpcActionRequest, NpcPolicyLedger, NpcPolicyViolation, NpcPriceState, NpcState,
+};
+use crate::tables::item_def::item_def;
+use crate::tables::npc_policy::{npc_policy_ledger, npc_policy_violation, npc_price_state, npc_role_item_rule};
+use crate::tables::npc_quest::{npc_state, quest_chain_def, quest_stage_def};
+use crate::utils::hex::HexCoordinates;
+use crate::utils::time::day_of;
+
+pub const ROLE_VILLAGER: u8 = 1;
+pub const ROLE_MERCHANT: u8 = 2;
+pub const ROLE_QUEST_GIVER: u8 = 3;
+pub const ROLE_GUARD: u8 = 4;
+
+pub const SEVERITY_LOW: u8 = 1;
+pub const SEVERITY_MEDIUM: u8 = 2;
+pub const SEVERITY_HIGH: u8 = 3;
+
+/// Highest item rarity a quest may require or reward, as
+/// (minimum best skill level, max rarity). Levels 0-5 stay at common/uncommon.
+const RARITY_TIERS: [(u32, u8); 4] = [(0, 2), (6, 3), (16, 4), (31, 5)];
+
+/// Meta talk an npc reply must never contain.
+const BLOCKED_TERMS: [&str; 6] =
+    ["system prompt", "as an ai", "language model", "server admin", "admin password", "api key"];
+
+const PRICE_CHANGE_COOLDOWN: Duration = Duration::from_secs(86_400);
+
+/// A proposal that broke a hard limit; recorded in `npc_policy_violation`.
+pub(crate) struct Violation {
+    pub reason: String,
+    pub severity: u8,
+}
+
+fn violation(severity: u8, reason: &str) -> Violation {
+    Violation { reason: reason.to_string(), severity }
+}
+
+pub fn rule_key(role: u8, item_def_id: u64) -> String {
+    format!("{role}:{item_def_id}")
+}
+
+pub fn ledger_key(identity: Identity, day: u32) -> String {
+    format!("{identity}:{day}")
+}
+
+pub fn price_key(npc_id: u64, item_def_id: u64) -> String {
+    format!("{npc_id}:{item_def_id}")
+}
+
+/// Requests may also be answered with a narrower action: a talk with a gift,
+/// a trade with a price change.
+fn request_allows(request_type: u8, action_type: u8) -> bool {
+    action_type == request_type
+        || (request_type == ACTION_TALK && action_type == ACTION_GIVE_ITEM)
+        || (request_type == ACTION_TRADE && action_type == ACTION_SET_PRICE)
+}
+
+/// Gifts are open to every role; the item whitelist decides what they can be.
+fn role_allows(role: u8, action_type: u8) -> bool {
+    match action_type {
+        ACTION_MOVE | ACTION_TALK | ACTION_GIVE_ITEM => true,
+        ACTION_TRADE | ACTION_SET_PRICE => role == ROLE_MERCHANT,
+        ACTION_QUEST => role == ROLE_QUEST_GIVER,
+        _ => false,
+    }
+}
+
+fn max_rarity(ctx: &ReducerContext, identity: Identity) -> u8 {
+    let level = skills::highest_level(ctx, identity);
+    RARITY_TIERS.iter().rev().find(|(min_level, _)| level >= *min_level).map(|(_, r)| *r).unwrap_or(1)
+}
+
+fn today_ledger(ctx: &ReducerContext, identity: Identity) -> Option<NpcPolicyLedger> {
+    ctx.db.npc_policy_ledger().ledger_key().find(ledger_key(identity, day_of(ctx.timestamp)))
+}
+
+/// Server-side guardrails for a worker proposal: schema and text limits,
+/// role whitelists, daily grant caps, quest tiers and price bands.
+pub(crate) fn check(
+    ctx: &ReducerContext,
+    request: &NpcActionRequest,
+    proposal: &NpcActionProposal,
+) -> Result<(), Violation> {
+    if !request_allows(request.action_type, proposal.action_type) {
+        return Err(violation(SEVERITY_LOW, "proposal action_type not allowed for request"));
+    }
+    if proposal.reply.chars().count() > MAX_REPLY_CHARS {
+        return Err(violation(SEVERITY_LOW, "reply too long"));
+    }
+    let reply = proposal.reply.to_lowercase();
+    if BLOCKED_TERMS.iter().any(|term| reply.contains(term)) {
+        return Err(violation(SEVERITY_MEDIUM, "reply contains blocked content"));
+    }
+    let npc = ctx
+        .db
+        .npc_state()
+        .npc_id()
+        .find(request.npc_id)
+        .ok_or(violation(SEVERITY_LOW, "npc not found"))?;
+    if !role_allows(npc.role, proposal.action_type) {
+        return Err(violation(SEVERITY_MEDIUM, "action not allowed for npc role"));
+    }
+    match proposal.action_type {
+        ACTION_MOVE => {
+            let from = terrain::hex_at(npc.pos_x, npc.pos_z);
+            let to = HexCoordinates::new(proposal.target_x, proposal.target_z);
+            if from.distance_to(&to) > MAX_MOVE_HEXES {
+                return Err(violation(SEVERITY_MEDIUM, "move target too far"));
+            }
+            Ok(())
+        }
+        ACTION_QUEST => check_quest_offer(ctx, request.requested_by, proposal.quest_chain_id),
+        ACTION_GIVE_ITEM => check_item_grant(ctx, request.requested_by, &npc, proposal),
+        ACTION_SET_PRICE => check_price(ctx, &npc, proposal),
+        ACTION_TALK | ACTION_TRADE => Ok(()),
+        _ => Err(violation(SEVERITY_LOW, "unknown action_type")),
+    }
+}
+
+/// Quest offers: at most `NPC_DAILY_QUEST_OFFERS` a day, and nothing above
+/// the player's rarity tier among required or rewarded items.
+fn check_quest_offer(ctx: &ReducerContext, identity: Identity, chain_id: u64) -> Result<(), Violation> {
+    if chain_id == 0 {
+        return Ok(());
+    }
+    let def = ctx
+        .db
+        .quest_chain_def()
+        .chain_id()
+        .find(chain_id)
+        .ok_or(violation(SEVERITY_MEDIUM, "offered quest chain does not exist"))?;
+    if quests::is_completed(ctx, identity, chain_id) {
+        return Err(violation(SEVERITY_LOW, "offered quest chain already completed"));
+    }
+    let offered = today_ledger(ctx, identity).map(|l| l.quests_offered).unwrap_or(0);
+    if offered as u64 >= balance::get_u64(ctx, balance::NPC_DAILY_QUEST_OFFERS, 3) {
+        return Err(violation(SEVERITY_MEDIUM, "daily quest offer cap reached"));
+    }
+
+    let mut items: Vec<u64> = def
+        .rewards
+        .iter()
+        .filter(|r| r.kind == quests::REWARD_ITEM)
+        .map(|r| r.target_id)
+        .collect();
+    for stage_id in &def.stage_ids {
+        if let Some(stage) = ctx.db.quest_stage_def().stage_id().find(*stage_id) {
+            let collected = stage.conditions.iter().filter(|c| c.kind == quests::COND_COLLECT);
+            items.extend(collected.map(|c| c.target_id));
+        }
+    }
+    let cap = max_rarity(ctx, identity);
+    let over_tier = items
+        .iter()
+        .any(|id| ctx.db.item_def().item_def_id().find(*id).is_some_and(|d| d.rarity > cap));
+    if over_tier {
+        return Err(violation(SEVERITY_HIGH, "quest exceeds player tier"));
+    }
+    Ok(())
+}
+
+/// Gifts: whitelisted for the npc's role, within the per-grant quantity and
+/// the player's `NPC_DAILY_ITEM_GRANTS` for the day.
+fn check_item_grant(
+    ctx: &ReducerContext,
+    identity: Identity,
+    npc: &NpcState,
+    proposal: &NpcActionProposal,
+) -> Result<(), Violation> {
+    if proposal.quantity == 0 {
+        return Err(violation(SEVERITY_LOW, "quantity must be > 0"));
+    }
+    let rule = ctx
+        .db
+        .npc_role_item_rule()
+        .rule_key()
+        .find(rule_key(npc.role, proposal.item_def_id))
+        .ok_or(violation(SEVERITY_HIGH, "item not whitelisted for npc role"))?;
+    if proposal.quantity > rule.max_quantity {
+        return Err(violation(SEVERITY_HIGH, "grant exceeds per-grant quantity"));
+    }
+    let granted = today_ledger(ctx, identity).map(|l| l.items_granted).unwrap_or(0);
+    let cap = balance::get_u64(ctx, balance::NPC_DAILY_ITEM_GRANTS, 5);
+    if granted as u64 + proposal.quantity as u64 > cap {
+        return Err(violation(SEVERITY_HIGH, "daily item grant cap reached"));
+    }
+    Ok(())
+}
+
+/// Inclusive `(low, high)` prices within `band_pct` percent of `reference`; the band is capped at 100%.
+pub fn price_band(reference: u64, band_pct: u64) -> (u64, u64) {
+    let band = band_pct.min(100);
+    let low = reference.saturating_mul(100 - band) / 100;
+    let high = reference.saturating_mul(100 + band) / 100;
+    (low, high)
+}
+
+/// Whether moving a price from `current` to `proposed` stays within `step_pct` percent of `current`.
+pub fn within_step(current: u64, proposed: u64, step_pct: u64) -> bool {
+    proposed.abs_diff(current).saturating_mul(100) <= current.saturating_mul(step_pct)
+}
+
+/// Price changes: only for stocked items, inside the regional band around
+/// `price_index` (or the base price before the item has traded), once a day
+/// per npc and item, by at most `NPC_PRICE_STEP_PCT`.
+fn check_price(ctx: &ReducerContext, npc: &NpcState, proposal: &NpcActionProposal) -> Result<(), Violation> {
+    if proposal.unit_price == 0 {
+        return Err(violation(SEVERITY_LOW, "unit_price must be > 0"));
+    }
+    if npc_shop::stock(ctx, npc.npc_id, proposal.item_def_id).is_none() {
+        return Err(violation(SEVERITY_MEDIUM, "npc does not stock item"));
+    }
+    let reference = npc_shop::reference_price(ctx, npc, proposal.item_def_id)
+        .ok_or(violation(SEVERITY_MEDIUM, "no reference price for item"))?;
+    let (low, high) = price_band(reference, balance::get_u64(ctx, balance::NPC_PRICE_BAND_PCT, 20));
+    if proposal.unit_price < low || proposal.unit_price > high {
+        return Err(violation(SEVERITY_MEDIUM, "price outside regional band"));
+    }
+
+    let Some(current) = ctx.db.npc_price_state().price_key().find(price_key(npc.npc_id, proposal.item_def_id))
+    else {
+        return Ok(());
+    };
+    let changed_recently = ctx
+        .timestamp
+        .duration_since(current.changed_at)
+        .is_none_or(|d| d < PRICE_CHANGE_COOLDOWN);
+    if changed_recently {
+        return Err(violation(SEVERITY_MEDIUM, "price already changed today"));
+    }
+    let step = balance::get_u64(ctx, balance::NPC_PRICE_STEP_PCT, 5);
+    if !within_step(current.unit_price, proposal.unit_price, step) {
+        return Err(violation(SEVERITY_MEDIUM, "price change exceeds daily step"));
+    }
+    Ok(())
+}
+
+fn bump_ledger(ctx: &ReducerContext, identity: Identity, items: u32, quests: u32) {
+    match today_ledger(ctx, identity) {
+        Some(mut ledger) => {
+            ledger.items_granted = ledger.items_granted.saturating_add(items);
+            ledger.quests_offered = ledger.quests_offered.saturating_add(quests);
+            ledger.updated_at = ctx.timestamp;
+            ctx.db.npc_policy_ledger().ledger_key().update(ledger);
+        }
+        None => {
+            let day = day_of(ctx.timestamp);
+            ctx.db.npc_policy_ledger().insert(NpcPolicyLedger {
+                ledger_key: ledger_key(identity, day),
+                identity,
+                day,
+                items_granted: items,
+                quests_offered: quests,
+                updated_at: ctx.timestamp,
+            });
+        }
+    }
+}
+
+pub(crate) fn note_item_grant(ctx: &ReducerContext, identity: Identity, quantity: u32) {
+    bump_ledger(ctx, identity, quantity, 0);
+}
+
+pub(crate) fn note_quest_offer(ctx: &ReducerContext, identity: Identity) {
+    bump_ledger(ctx, identity, 0, 1);
+}
+
+pub(crate) fn set_price(ctx: &ReducerContext, npc_id: u64, item_def_id: u64, unit_price: u64) {
+    match ctx.db.npc_price_state().price_key().find(price_key(npc_id, item_def_id)) {
+        Some(mut price) => {
+            price.unit_price = unit_price;
+            price.changed_at = ctx.timestamp;
+            ctx.db.npc_price_state().price_key().update(price);
+        }
+        None => {
+            ctx.db.npc_price_state().insert(NpcPriceState {
+                price_key: price_key(npc_id, item_def_id),
+                npc_id,
+                item_def_id,
+                unit_price,
+                changed_at: ctx.timestamp,
+            });
+        }
+    }
+}
+
+pub(crate) fn record_violation(
+    ctx: &ReducerContext,
+    request: &NpcActionRequest,
+    proposal: &NpcActionProposal,
+    violation: &Violation,
+) {
+    log::warn!(
+        "npc policy violation: request_id={} npc_id={} severity={} reason={}",
+        request.request_id,
+        request.npc_id,
+        violation.severity,
+        violation.reason
+    );
+    ctx.db.npc_policy_violation().insert(NpcPolicyViolation {
+        violation_id: 0,
+        request_id: request.request_id,
+        session_id: request.session_id,
+        npc_id: request.npc_id,
+        identity: request.requested_by,
+        action_type: proposal.action_type,
+        reason: violation.reason.clone(),
+        severity: violation.severity,
+        created_at: ctx.timestamp,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn price_band_spans_band_pct_around_reference() {
+        assert_eq!(price_band(100, 20), (80, 120));
+        assert_eq!(price_band(100, 0), (100, 100));
+        assert_eq!(price_band(7, 20), (5, 8));
+    }
+
+    #[test]
+    fn price_band_caps_band_at_100_pct() {
+        assert_eq!(price_band(100, 250), (0, 200));
+        assert_eq!(price_band(u64::MAX, 20).1, u64::MAX / 100);
+    }
+
+    #[test]
+    fn within_step_allows_moves_up_to_step_pct() {
+        assert!(within_step(100, 105, 5));
+        assert!(within_step(100, 95, 5));
+        assert!(within_step(100, 100, 0));
+        assert!(!within_step(100, 106, 5));
+        assert!(!within_step(100, 94, 5));
+        assert!(!within_step(0, 1, 5));
+    }
+
+    #[test]
+    fn requests_allow_their_own_or_a_narrower_action() {
+        assert!(request_allows(ACTION_TALK, ACTION_TALK));
+        assert!(request_allows(ACTION_TALK, ACTION_GIVE_ITEM));
+        assert!(request_allows(ACTION_TRADE, ACTION_SET_PRICE));
+        assert!(!request_allows(ACTION_TALK, ACTION_SET_PRICE));
+        assert!(!request_allows(ACTION_QUEST, ACTION_GIVE_ITEM));
+    }
+
+    #[test]
+    fn only_merchants_trade_and_only_quest_givers_offer_quests() {
+        assert!(role_allows(ROLE_MERCHANT, ACTION_SET_PRICE));
+        assert!(!role_allows(ROLE_VILLAGER, ACTION_TRADE));
+        assert!(role_allows(ROLE_QUEST_GIVER, ACTION_QUEST));
+        assert!(!role_allows(ROLE_GUARD, ACTION_QUEST));
+        assert!(role_allows(ROLE_GUARD, ACTION_GIVE_ITEM));
+        assert!(!role_allows(ROLE_MERCHANT, 99));
+    }
+}
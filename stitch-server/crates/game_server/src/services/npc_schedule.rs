@@ -0,0 +1,121 @@
+use spacetimedb::{ReducerContext, Table};
+
+use crate::services::npc_agent::{ACTION_TALK, ACTION_TRADE};
+use crate::services::{pathfinding, terrain};
+use crate::tables::{NpcActionSchedule, NpcState};
+use crate::tables::npc_quest::npc_state;
+use crate::tables::npc_schedule::npc_action_schedule;
+use crate::utils::hex::HexCoordinates;
+
+pub const SCHEDULE_STATIC: u8 = 0;
+pub const SCHEDULE_ROUTINE: u8 = 1;
+
+pub const ACTIVITY_IDLE: u8 = 0;
+pub const ACTIVITY_HOME: u8 = 1;
+pub const ACTIVITY_WORK: u8 = 2;
+pub const ACTIVITY_MARKET: u8 = 3;
+pub const ACTIVITY_WANDER: u8 = 4;
+pub const ACTIVITY_SLEEP: u8 = 5;
+
+pub fn schedule_key(npc_id: u64, start_hour: u8) -> String {
+    format!("{npc_id}:{start_hour}")
+}
+
+fn activity_label(activity: u8) -> &'static str {
+    match activity {
+        ACTIVITY_HOME => "at home",
+        ACTIVITY_WORK => "working",
+        ACTIVITY_MARKET => "at the market",
+        ACTIVITY_WANDER => "wandering",
+        ACTIVITY_SLEEP => "asleep",
+        _ => "idle",
+    }
+}
+
+/// Npcs only talk while at home or wandering, trade only at work or the
+/// market, offer quests only at work, and do nothing while asleep.
+fn allows(activity: u8, action_type: u8) -> bool {
+    match activity {
+        ACTIVITY_SLEEP => false,
+        ACTIVITY_HOME | ACTIVITY_WANDER => action_type == ACTION_TALK,
+        ACTIVITY_MARKET => action_type == ACTION_TALK || action_type == ACTION_TRADE,
+        _ => true,
+    }
+}
+
+/// Fails with e.g. "npc is unavailable while asleep".
+pub(crate) fn require_available(npc: &NpcState, action_type: u8) -> Result<(), String> {
+    if allows(npc.activity, action_type) {
+        return Ok(());
+    }
+    Err(format!("npc is unavailable while {}", activity_label(npc.activity)))
+}
+
+/// The latest entry starting at or before `hour`; before the day's first
+/// entry, yesterday's last one still runs.
+pub(crate) fn active_entry(ctx: &ReducerContext, npc_id: u64, hour: u8) -> Option<NpcActionSchedule> {
+    let mut entries: Vec<NpcActionSchedule> =
+        ctx.db.npc_action_schedule().iter().filter(|e| e.npc_id == npc_id).collect();
+    if entries.is_empty() {
+        return None;
+    }
+    entries.sort_by_key(|e| e.start_hour);
+    let index = entries.iter().rposition(|e| e.start_hour <= hour).unwrap_or(entries.len() - 1);
+    Some(entries.swap_remove(index))
+}
+
+/// Switches a routine npc to the entry for `hour` and sends it walking to the
+/// entry's hex. Npcs already on that entry are left alone.
+pub(crate) fn advance(ctx: &ReducerContext, mut npc: NpcState, hour: u8) {
+    let Some(entry) = active_entry(ctx, npc.npc_id, hour) else {
+        if npc.activity != ACTIVITY_IDLE || !npc.schedule_key.is_empty() {
+            npc.activity = ACTIVITY_IDLE;
+            npc.schedule_key = String::new();
+            npc.updated_at = ctx.timestamp;
+            ctx.db.npc_state().npc_id().update(npc);
+        }
+        return;
+    };
+    if npc.schedule_key == entry.schedule_key {
+        return;
+    }
+
+    let (npc_id, region_id) = (npc.npc_id, npc.region_id);
+    let start = terrain::hex_at(npc.pos_x, npc.pos_z);
+    let goal = HexCoordinates::new(entry.target_x, entry.target_z);
+    npc.activity = entry.activity;
+    npc.schedule_key = entry.schedule_key;
+    npc.updated_at = ctx.timestamp;
+    ctx.db.npc_state().npc_id().update(npc);
+    if start == goal {
+        return;
+    }
+    match pathfinding::find_region_path(ctx, region_id, start, goal, pathfinding::DEFAULT_NODE_LIMIT) {
+        Ok(path) => {
+            pathfinding::store_npc_path(ctx, npc_id, region_id, &path);
+        }
+        Err(reason) => log::warn!("npc routine path failed: npc_id={npc_id} reason={reason}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::npc_agent::ACTION_QUEST;
+
+    #[test]
+    fn activities_gate_npc_actions() {
+        assert!(!allows(ACTIVITY_SLEEP, ACTION_TALK));
+        assert!(allows(ACTIVITY_HOME, ACTION_TALK));
+        assert!(!allows(ACTIVITY_WANDER, ACTION_TRADE));
+        assert!(allows(ACTIVITY_MARKET, ACTION_TRADE));
+        assert!(!allows(ACTIVITY_MARKET, ACTION_QUEST));
+        assert!(allows(ACTIVITY_WORK, ACTION_QUEST));
+    }
+
+    #[test]
+    fn unknown_activities_read_as_idle() {
+        assert_eq!(activity_label(ACTIVITY_SLEEP), "asleep");
+        assert_eq!(activity_label(42), "idle");
+    }
+}
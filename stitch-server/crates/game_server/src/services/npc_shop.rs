@@ -0,0 +1,204 @@
+use spacetimedb::{Identity, ReducerContext, Table};
+
+use crate::services::{balance, economy, inventory, npc_policy, wallet};
+use crate::tables::{NpcShopStock, NpcState};
+use crate::tables::npc_policy::npc_price_state;
+use crate::tables::npc_shop::npc_shop_stock;
+use crate::tables::trade_market::price_index;
+
+pub const SIDE_BUY: u8 = 0; // player buys from the npc
+pub const SIDE_SELL: u8 = 1; // player sells to the npc
+
+pub fn stock_key(npc_id: u64, item_def_id: u64) -> String {
+    format!("{npc_id}:{item_def_id}")
+}
+
+pub(crate) fn stock(ctx: &ReducerContext, npc_id: u64, item_def_id: u64) -> Option<NpcShopStock> {
+    ctx.db.npc_shop_stock().stock_key().find(stock_key(npc_id, item_def_id))
+}
+
+/// The regional price index for the item, or the vendor's base price where
+/// the market has not traded it yet.
+pub(crate) fn reference_price(ctx: &ReducerContext, npc: &NpcState, item_def_id: u64) -> Option<u64> {
+    ctx.db
+        .price_index()
+        .index_key()
+        .find(economy::price_index_key(npc.region_id, item_def_id))
+        .map(|index| index.price_avg)
+        .or_else(|| stock(ctx, npc.npc_id, item_def_id).map(|s| s.base_price))
+}
+
+/// Price the spread is applied around: a worker-set npc price when there is
+/// one, otherwise `blend_price` of the base price and the price index.
+pub(crate) fn mid_price(ctx: &ReducerContext, npc: &NpcState, stock: &NpcShopStock) -> u64 {
+    let key = npc_policy::price_key(npc.npc_id, stock.item_def_id);
+    if let Some(price) = ctx.db.npc_price_state().price_key().find(key) {
+        return price.unit_price;
+    }
+    blend_price(stock.base_price, reference_price(ctx, npc, stock.item_def_id))
+}
+
+/// Base price and reference averaged, capped to half/double the base price.
+pub fn blend_price(base: u64, reference: Option<u64>) -> u64 {
+    match reference {
+        Some(reference) => (base.saturating_add(reference) / 2).clamp(base / 2, base.saturating_mul(2)),
+        None => base,
+    }
+}
+
+/// How full the stock is, 0-100; an unbounded (`max_quantity = 0`) stock counts as full.
+pub fn fill_pct(quantity: u32, max_quantity: u32) -> u64 {
+    if max_quantity == 0 {
+        return 100;
+    }
+    (quantity.min(max_quantity) as u64) * 100 / max_quantity as u64
+}
+
+/// `mid` plus the markup and the scarcity premium for the empty part of the stock; at least 1.
+pub fn sell_unit_price(mid: u64, markup_pct: u64, scarcity_pct: u64, fill_pct: u64) -> u64 {
+    let scarcity = scarcity_pct.saturating_mul(100 - fill_pct.min(100)) / 100;
+    (mid.saturating_mul(100u64.saturating_add(markup_pct).saturating_add(scarcity)) / 100).max(1)
+}
+
+/// `mid` minus the spread and the scarcity discount for the full part of the stock; may reach 0.
+pub fn buy_unit_price(mid: u64, spread_pct: u64, scarcity_pct: u64, fill_pct: u64) -> u64 {
+    let scarcity = scarcity_pct.saturating_mul(fill_pct.min(100)) / 100;
+    let pct = 100u64.saturating_sub(spread_pct.saturating_add(scarcity));
+    mid.saturating_mul(pct) / 100
+}
+
+/// Unit price the npc charges; rises as its stock runs out.
+pub(crate) fn sell_price(ctx: &ReducerContext, npc: &NpcState, stock: &NpcShopStock) -> u64 {
+    sell_unit_price(
+        mid_price(ctx, npc, stock),
+        balance::get_u64(ctx, balance::SHOP_SELL_MARKUP_PCT, 10),
+        balance::get_u64(ctx, balance::SHOP_SCARCITY_PCT, 25),
+        fill_pct(stock.quantity, stock.max_quantity),
+    )
+}
+
+/// Unit price the npc pays; drops as its stock fills up.
+pub(crate) fn buy_price(ctx: &ReducerContext, npc: &NpcState, stock: &NpcShopStock) -> u64 {
+    buy_unit_price(
+        mid_price(ctx, npc, stock),
+        balance::get_u64(ctx, balance::SHOP_BUY_SPREAD_PCT, 30),
+        balance::get_u64(ctx, balance::SHOP_SCARCITY_PCT, 25),
+        fill_pct(stock.quantity, stock.max_quantity),
+    )
+}
+
+/// Player buys `quantity` from the npc at no more than `max_unit_price`.
+/// Returns a short detail for the interaction log.
+pub(crate) fn buy(
+    ctx: &ReducerContext,
+    npc: &NpcState,
+    buyer: Identity,
+    item_def_id: u64,
+    quantity: u32,
+    max_unit_price: u64,
+) -> Result<String, String> {
+    let mut stock = stock(ctx, npc.npc_id, item_def_id).ok_or("npc does not sell this item".to_string())?;
+    if stock.quantity < quantity {
+        return Err("npc is out of stock".to_string());
+    }
+    let unit_price = sell_price(ctx, npc, &stock);
+    if unit_price > max_unit_price {
+        return Err("npc price is above your limit".to_string());
+    }
+    let total = unit_price.saturating_mul(quantity as u64);
+
+    wallet::debit(ctx, buyer, total, wallet::REASON_NPC_PURCHASE)?;
+    inventory::add_items(ctx, buyer, item_def_id, quantity)?;
+    stock.quantity -= quantity;
+    stock.updated_at = ctx.timestamp;
+    ctx.db.npc_shop_stock().stock_key().update(stock);
+    Ok(format!("bought item_def_id={item_def_id} quantity={quantity} total={total}"))
+}
+
+/// Player sells `quantity` to the npc for at least `min_unit_price`, as long
+/// as the npc has room in its stock.
+pub(crate) fn sell(
+    ctx: &ReducerContext,
+    npc: &NpcState,
+    seller: Identity,
+    item_def_id: u64,
+    quantity: u32,
+    min_unit_price: u64,
+) -> Result<String, String> {
+    let mut stock = stock(ctx, npc.npc_id, item_def_id).ok_or("npc does not buy this item".to_string())?;
+    if stock.quantity.saturating_add(quantity) > stock.max_quantity {
+        return Err("npc has no room for that many".to_string());
+    }
+    let unit_price = buy_price(ctx, npc, &stock);
+    if unit_price < min_unit_price {
+        return Err("npc offer is below your limit".to_string());
+    }
+    let total = unit_price.saturating_mul(quantity as u64);
+
+    inventory::consume_items(ctx, seller, item_def_id, quantity)?;
+    wallet::credit(ctx, seller, total, wallet::REASON_NPC_SALE);
+    stock.quantity += quantity;
+    stock.updated_at = ctx.timestamp;
+    ctx.db.npc_shop_stock().stock_key().update(stock);
+    Ok(format!("sold item_def_id={item_def_id} quantity={quantity} total={total}"))
+}
+
+/// Refills every vendor by its `restock_amount`, never past `max_quantity`.
+pub(crate) fn restock(ctx: &ReducerContext) {
+    let low: Vec<NpcShopStock> = ctx
+        .db
+        .npc_shop_stock()
+        .iter()
+        .filter(|s| s.restock_amount > 0 && s.quantity < s.max_quantity)
+        .collect();
+    for mut stock in low {
+        stock.quantity = stock.quantity.saturating_add(stock.restock_amount).min(stock.max_quantity);
+        stock.updated_at = ctx.timestamp;
+        ctx.db.npc_shop_stock().stock_key().update(stock);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blend_price_averages_within_half_and_double_base() {
+        assert_eq!(blend_price(100, None), 100);
+        assert_eq!(blend_price(100, Some(140)), 120);
+        assert_eq!(blend_price(100, Some(1_000)), 200);
+        assert_eq!(blend_price(100, Some(0)), 50);
+    }
+
+    #[test]
+    fn fill_pct_clamps_and_treats_unbounded_stock_as_full() {
+        assert_eq!(fill_pct(0, 10), 0);
+        assert_eq!(fill_pct(5, 10), 50);
+        assert_eq!(fill_pct(15, 10), 100);
+        assert_eq!(fill_pct(3, 0), 100);
+    }
+
+    #[test]
+    fn sell_price_adds_markup_and_scarcity_premium() {
+        assert_eq!(sell_unit_price(100, 10, 25, 100), 110);
+        assert_eq!(sell_unit_price(100, 10, 25, 0), 135);
+        assert_eq!(sell_unit_price(100, 10, 25, 60), 120);
+        assert_eq!(sell_unit_price(0, 10, 25, 0), 1);
+    }
+
+    #[test]
+    fn buy_price_subtracts_spread_and_scarcity_discount() {
+        assert_eq!(buy_unit_price(100, 30, 25, 0), 70);
+        assert_eq!(buy_unit_price(100, 30, 25, 100), 45);
+        assert_eq!(buy_unit_price(100, 90, 25, 100), 0);
+    }
+
+    #[test]
+    fn npc_never_buys_above_its_sell_price() {
+        for fill in [0, 25, 50, 75, 100] {
+            for mid in [1, 7, 100, 12_345] {
+                assert!(buy_unit_price(mid, 30, 25, fill) <= sell_unit_price(mid, 10, 25, fill));
+            }
+        }
+    }
+}
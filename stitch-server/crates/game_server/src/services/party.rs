@@ -0,0 +1,173 @@
+use std::time::Duration;
+
+use spacetimedb::{Identity, ReducerContext, Table};
+
+use crate::services::{balance, inventory, loot, quests, skills};
+use crate::tables::{PartyMember, PartyState};
+use crate::tables::party::{party_invitation, party_member, party_permission, party_state};
+use crate::tables::session_state::session_state;
+use crate::tables::transform_state::transform_state;
+
+pub const LOOT_FREE_FOR_ALL: u8 = 0;
+pub const LOOT_ROUND_ROBIN: u8 = 1;
+
+pub const INVITE_TTL: Duration = Duration::from_secs(300);
+
+pub fn invite_key(party_id: u64, invitee: Identity) -> String {
+    format!("{party_id}:{invitee}")
+}
+
+pub fn permission_key(target_kind: u8, target_id: u64, party_id: u64) -> String {
+    format!("{target_kind}:{target_id}:{party_id}")
+}
+
+pub(crate) fn max_size(ctx: &ReducerContext) -> usize {
+    balance::get_u64(ctx, balance::PARTY_MAX_SIZE, 5) as usize
+}
+
+pub(crate) fn party_of(ctx: &ReducerContext, identity: Identity) -> Option<PartyState> {
+    let member = ctx.db.party_member().member_identity().find(identity)?;
+    ctx.db.party_state().party_id().find(member.party_id)
+}
+
+/// Members in join order.
+pub(crate) fn members(ctx: &ReducerContext, party_id: u64) -> Vec<PartyMember> {
+    let mut members: Vec<PartyMember> = ctx.db.party_member().party_id().filter(party_id).collect();
+    members.sort_by_key(|m| m.joined_at);
+    members
+}
+
+pub(crate) fn require_leader(ctx: &ReducerContext) -> Result<PartyState, String> {
+    let party = party_of(ctx, ctx.sender).ok_or("not in a party".to_string())?;
+    if party.leader_identity != ctx.sender {
+        return Err("only the party leader can do that".to_string());
+    }
+    Ok(party)
+}
+
+/// Removes `identity` from its party. The longest-standing member takes
+/// over from a leaving leader; the last member out disbands the party.
+pub(crate) fn remove_member(ctx: &ReducerContext, mut party: PartyState, identity: Identity) {
+    ctx.db.party_member().member_identity().delete(identity);
+    let remaining = members(ctx, party.party_id);
+    let Some(successor) = remaining.first() else {
+        disband(ctx, party.party_id);
+        return;
+    };
+    if party.leader_identity == identity {
+        party.leader_identity = successor.member_identity;
+    }
+    party.updated_at = ctx.timestamp;
+    ctx.db.party_state().party_id().update(party);
+}
+
+pub(crate) fn purge_expired_invites(ctx: &ReducerContext) {
+    let expired: Vec<String> = ctx
+        .db
+        .party_invitation()
+        .iter()
+        .filter(|i| i.expires_at <= ctx.timestamp)
+        .map(|i| i.invite_key)
+        .collect();
+    for key in expired {
+        ctx.db.party_invitation().invite_key().delete(key);
+    }
+}
+
+fn disband(ctx: &ReducerContext, party_id: u64) {
+    let invites: Vec<String> =
+        ctx.db.party_invitation().iter().filter(|i| i.party_id == party_id).map(|i| i.invite_key).collect();
+    for key in invites {
+        ctx.db.party_invitation().invite_key().delete(key);
+    }
+    let grants: Vec<String> =
+        ctx.db.party_permission().party_id().filter(party_id).map(|p| p.permission_key).collect();
+    for key in grants {
+        ctx.db.party_permission().permission_key().delete(key);
+    }
+    ctx.db.party_state().party_id().delete(party_id);
+}
+
+/// Flags the target granted to `subject`'s party.
+pub(crate) fn granted_flags(ctx: &ReducerContext, subject: Identity, target_kind: u8, target_id: u64) -> u32 {
+    let Some(member) = ctx.db.party_member().member_identity().find(subject) else {
+        return 0;
+    };
+    ctx.db
+        .party_permission()
+        .permission_key()
+        .find(permission_key(target_kind, target_id, member.party_id))
+        .map(|p| p.flags)
+        .unwrap_or(0)
+}
+
+/// Other members of `identity`'s party in the same region and within
+/// `party.share_range`.
+pub(crate) fn members_in_range(ctx: &ReducerContext, identity: Identity) -> Vec<Identity> {
+    let (Some(member), Some(session), Some(tf)) = (
+        ctx.db.party_member().member_identity().find(identity),
+        ctx.db.session_state().identity().find(identity),
+        ctx.db.transform_state().entity_id().find(identity),
+    ) else {
+        return Vec::new();
+    };
+    let range = balance::get_f32(ctx, balance::PARTY_SHARE_RANGE, 40.0);
+    members(ctx, member.party_id)
+        .into_iter()
+        .map(|m| m.member_identity)
+        .filter(|other| *other != identity)
+        .filter(|other| {
+            ctx.db.session_state().identity().find(*other).is_some_and(|s| s.region_id == session.region_id)
+        })
+        .filter(|other| {
+            ctx.db.transform_state().entity_id().find(*other).is_some_and(|o| {
+                let dx = o.position[0] - tf.position[0];
+                let dz = o.position[2] - tf.position[2];
+                dx * dx + dz * dz <= range * range
+            })
+        })
+        .collect()
+}
+
+/// Kill hook for combat: credits the kill to the killer and to party members
+/// in range, who also earn `party.kill_xp` combat xp, then hands out kill loot
+/// by the party's loot rule. Downing a party member earns nothing.
+pub(crate) fn on_kill(ctx: &ReducerContext, killer: Identity, victim: Identity) {
+    let party_id = |identity| ctx.db.party_member().member_identity().find(identity).map(|m| m.party_id);
+    let killer_party = party_id(killer);
+    if killer_party.is_some() && killer_party == party_id(victim) {
+        return;
+    }
+    quests::record(ctx, killer, quests::COND_KILL, 0, 1);
+    let nearby: Vec<Identity> = members_in_range(ctx, killer).into_iter().filter(|m| *m != victim).collect();
+    let kill_xp = balance::get_u64(ctx, balance::PARTY_KILL_XP, 5);
+    for member in &nearby {
+        quests::record(ctx, *member, quests::COND_KILL, 0, 1);
+        skills::award_xp(ctx, *member, skills::SKILL_COMBAT, kill_xp);
+    }
+
+    let loot_list = balance::get_u64(ctx, balance::COMBAT_KILL_LOOT_LIST, 0);
+    if loot_list == 0 {
+        return;
+    }
+    let mut eligible = vec![killer];
+    eligible.extend(nearby);
+    for (item_def_id, quantity) in loot::roll_item_list(ctx, loot_list) {
+        let recipient = loot_recipient(ctx, killer, &eligible);
+        if let Err(err) = inventory::add_items(ctx, recipient, item_def_id, quantity) {
+            log::info!("kill loot dropped: identity={recipient} item_def_id={item_def_id} reason={err}");
+        }
+    }
+}
+
+/// Free-for-all loot goes to the killer; round robin walks the eligible
+/// members from the party's cursor.
+fn loot_recipient(ctx: &ReducerContext, killer: Identity, eligible: &[Identity]) -> Identity {
+    let Some(mut party) = party_of(ctx, killer).filter(|p| p.loot_rule == LOOT_ROUND_ROBIN) else {
+        return killer;
+    };
+    let recipient = eligible[party.loot_cursor as usize % eligible.len()];
+    party.loot_cursor = party.loot_cursor.wrapping_add(1);
+    ctx.db.party_state().party_id().update(party);
+    recipient
+}
@@ -0,0 +1,180 @@
+use std::collections::{HashMap, HashSet};
+
+use spacetimedb::{ReducerContext, Table};
+
+use crate::services::terrain::{self, TerrainSampler};
+use crate::services::world_gen::{WATER_RIVER, WATER_SWAMP};
+use crate::tables::{NavPath, NavWaypoint};
+use crate::tables::building_state::building_state;
+use crate::tables::navigation::{nav_cell_cost, nav_obstacle, nav_path, nav_waypoint};
+use crate::utils::hex::HexCoordinates;
+use crate::utils::pathfinding;
+
+/// Cost units per 1.0 of design-side movement cost.
+pub const COST_SCALE: f32 = 10.0;
+pub const BASE_STEP_COST: u32 = 10;
+pub const WADING_COST: u32 = 10;
+pub const CLIMB_COST_PER_LEVEL: u32 = 4;
+pub const DEFAULT_NODE_LIMIT: u32 = 1024;
+pub const MAX_NODE_LIMIT: u32 = 8192;
+pub const MAX_PATH_LENGTH: usize = 256;
+
+pub const PATH_STATUS_MOVING: u8 = 0;
+pub const PATH_STATUS_ARRIVED: u8 = 1;
+pub const PATH_STATUS_FAILED: u8 = 2;
+
+pub fn cell_key(region_id: u64, hex_x: i32, hex_z: i32) -> String {
+    format!("{region_id}:{hex_x}:{hex_z}")
+}
+
+/// Movement cost model for one region, loaded once per search:
+/// `g = base + terrain + wading + climb`, with buildings, obstacles,
+/// unwalkable water and cliffs impassable.
+pub(crate) struct NavGrid<'a> {
+    sampler: TerrainSampler<'a>,
+    blocked: HashSet<(i32, i32)>,
+    extra_costs: HashMap<(i32, i32), u32>,
+}
+
+impl<'a> NavGrid<'a> {
+    pub(crate) fn load(ctx: &'a ReducerContext, region_id: u64) -> Self {
+        let mut blocked = HashSet::new();
+        let mut extra_costs = HashMap::new();
+        for building in ctx.db.building_state().iter() {
+            if building.region_id == region_id && building.state != 2 {
+                blocked.insert((building.hex_x, building.hex_z));
+            }
+        }
+        for obstacle in ctx.db.nav_obstacle().iter() {
+            if obstacle.region_id == region_id && obstacle.blocked {
+                blocked.insert((obstacle.hex_x, obstacle.hex_z));
+            }
+        }
+        for cost in ctx.db.nav_cell_cost().iter() {
+            if cost.region_id != region_id {
+                continue;
+            }
+            if cost.blocked {
+                blocked.insert((cost.hex_x, cost.hex_z));
+            } else if cost.terrain_cost.is_finite() && cost.terrain_cost > 0.0 {
+                extra_costs.insert((cost.hex_x, cost.hex_z), (cost.terrain_cost * COST_SCALE) as u32);
+            }
+        }
+        NavGrid {
+            sampler: TerrainSampler::for_region(ctx, region_id),
+            blocked,
+            extra_costs,
+        }
+    }
+
+    pub(crate) fn is_blocked(&self, hex: HexCoordinates) -> bool {
+        self.blocked.contains(&(hex.x, hex.z))
+    }
+
+    pub(crate) fn step_cost(&mut self, from: HexCoordinates, to: HexCoordinates) -> Option<u32> {
+        if self.is_blocked(to) {
+            return None;
+        }
+        let mut cost = BASE_STEP_COST + self.extra_costs.get(&(to.x, to.z)).copied().unwrap_or(0);
+        if let Some(cell) = self.sampler.cell(to) {
+            if !terrain::is_walkable(&cell) {
+                return None;
+            }
+            if cell.water_body_type == WATER_RIVER || cell.water_body_type == WATER_SWAMP {
+                cost += WADING_COST;
+            }
+            if let Some(prev) = self.sampler.cell(from) {
+                let climb = cell.elevation - prev.elevation;
+                if climb > terrain::MAX_CLIMB_PER_STEP {
+                    return None;
+                }
+                cost += climb.unsigned_abs() as u32 * CLIMB_COST_PER_LEVEL;
+            }
+        }
+        Some(cost)
+    }
+}
+
+pub(crate) fn find_region_path(
+    ctx: &ReducerContext,
+    region_id: u64,
+    start: HexCoordinates,
+    goal: HexCoordinates,
+    node_limit: u32,
+) -> Result<Vec<HexCoordinates>, String> {
+    let mut grid = NavGrid::load(ctx, region_id);
+    if grid.is_blocked(goal) {
+        return Err("target cell is blocked".to_string());
+    }
+    let limit = node_limit.clamp(1, MAX_NODE_LIMIT) as usize;
+    let result = pathfinding::find_hex_path(start, goal, limit, BASE_STEP_COST, |from, to| {
+        grid.step_cost(from, to)
+    })
+    .ok_or_else(|| "no path within node limit".to_string())?;
+    if result.path.len() > MAX_PATH_LENGTH {
+        return Err("path exceeds max length".to_string());
+    }
+    Ok(result.path)
+}
+
+/// Replaces any path the NPC is following with a fresh one.
+pub(crate) fn store_npc_path(
+    ctx: &ReducerContext,
+    npc_id: u64,
+    region_id: u64,
+    path: &[HexCoordinates],
+) -> NavPath {
+    clear_npc_path(ctx, npc_id);
+
+    let target = path.last().copied().unwrap_or(HexCoordinates::new(0, 0));
+    // Waypoint 0 is the cell the NPC already stands on.
+    let nav_path = ctx.db.nav_path().insert(NavPath {
+        path_id: 0,
+        npc_id,
+        region_id,
+        target_x: target.x,
+        target_z: target.z,
+        next_seq: 1,
+        waypoint_count: path.len() as u32,
+        status: if path.len() <= 1 { PATH_STATUS_ARRIVED } else { PATH_STATUS_MOVING },
+        created_at: ctx.timestamp,
+        updated_at: ctx.timestamp,
+    });
+    for (seq, hex) in path.iter().enumerate() {
+        ctx.db.nav_waypoint().insert(NavWaypoint {
+            waypoint_id: 0,
+            path_id: nav_path.path_id,
+            seq: seq as u32,
+            hex_x: hex.x,
+            hex_z: hex.z,
+        });
+    }
+    nav_path
+}
+
+pub(crate) fn clear_waypoints(ctx: &ReducerContext, path_id: u64) {
+    let ids: Vec<u64> = ctx
+        .db
+        .nav_waypoint()
+        .path_id()
+        .filter(path_id)
+        .map(|w| w.waypoint_id)
+        .collect();
+    for id in ids {
+        ctx.db.nav_waypoint().waypoint_id().delete(id);
+    }
+}
+
+pub(crate) fn clear_npc_path(ctx: &ReducerContext, npc_id: u64) {
+    let paths: Vec<u64> = ctx
+        .db
+        .nav_path()
+        .iter()
+        .filter(|p| p.npc_id == npc_id)
+        .map(|p| p.path_id)
+        .collect();
+    for path_id in paths {
+        clear_waypoints(ctx, path_id);
+        ctx.db.nav_path().path_id().delete(path_id);
+    }
+}
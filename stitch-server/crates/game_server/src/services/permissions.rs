@@ -1,18 +1,92 @@
-use spacetimedb::{Identity, ReducerContext};
+use spacetimedb::{Identity, ReducerContext, ViewContext};
 
-use crate::tables::permission_state::permission_state;
+use crate::services::{claims, empires, housing, party};
+use crate::tables::building_state::building_state;
+use crate::tables::permission_state::{permission_state, permission_state__view};
 
+pub const PERM_ENTER: u32 = 0x0001;
+pub const PERM_CONTAINER: u32 = 0x0002;
 pub const PERM_BUILD: u32 = 0x0004;
+pub const PERM_MANAGE_MEMBERS: u32 = 0x0008;
 pub const PERM_ADMIN: u32 = 0x0020;
+/// Global (0, 0) grant for handling reports and moderation actions.
+pub const PERM_MODERATE: u32 = 0x0040;
 
 pub fn permission_key(target_kind: u8, target_id: u64, subject: Identity) -> String {
     format!("{target_kind}:{target_id}:{subject}")
 }
 
+/// The server identity or a holder of the global admin grant.
+pub fn is_admin(ctx: &ReducerContext) -> bool {
+    ctx.sender == Identity::ZERO || has_permission(ctx, 0, 0, PERM_ADMIN)
+}
+
+/// Fails admin-only reducers for everyone else; `action` names the reducer in the error.
+pub fn require_admin(ctx: &ReducerContext, action: &str) -> Result<(), String> {
+    if !is_admin(ctx) {
+        return Err(format!("{action} requires server/admin authorization"));
+    }
+    Ok(())
+}
+
+/// The subject's global (0, 0) grant flags; readable from views.
+pub fn global_flags(ctx: &ViewContext, subject: Identity) -> u32 {
+    ctx.db
+        .permission_state()
+        .permission_key()
+        .find(permission_key(0, 0, subject))
+        .map_or(0, |row| row.flags)
+}
+
 pub fn has_permission(ctx: &ReducerContext, target_kind: u8, target_id: u64, required: u32) -> bool {
-    let key = permission_key(target_kind, target_id, ctx.sender);
+    has_permission_for(ctx, ctx.sender, target_kind, target_id, required)
+}
+
+pub fn has_permission_for(
+    ctx: &ReducerContext,
+    subject: Identity,
+    target_kind: u8,
+    target_id: u64,
+    required: u32,
+) -> bool {
+    if target_kind == 3 {
+        return housing::dimension_permission(ctx, subject, target_id, required);
+    }
+
+    let key = permission_key(target_kind, target_id, subject);
     if let Some(row) = ctx.db.permission_state().permission_key().find(key) {
-        return row.flags & required == required;
+        if row.flags & required == required {
+            return true;
+        }
+    }
+    let flags = membership_flags(ctx, subject, target_kind, target_id);
+    (flags | party_flags(ctx, subject, target_kind, target_id)) & required == required
+}
+
+/// Grants made to the subject's party; global (kind 0) rights never come
+/// from a party.
+fn party_flags(ctx: &ReducerContext, subject: Identity, target_kind: u8, target_id: u64) -> u32 {
+    if target_kind == 0 {
+        return 0;
     }
-    false
+    party::granted_flags(ctx, subject, target_kind, target_id)
+}
+
+fn membership_flags(ctx: &ReducerContext, subject: Identity, target_kind: u8, target_id: u64) -> u32 {
+    let claim_id = match target_kind {
+        1 => target_id,
+        2 => {
+            let Some(building) = ctx.db.building_state().entity_id().find(target_id) else {
+                return 0;
+            };
+            match claims::claim_covering(ctx, building.region_id, building.hex_x, building.hex_z) {
+                Some(claim) => claim.claim_id,
+                None => return 0,
+            }
+        }
+        _ => return 0,
+    };
+    claims::member_flags(ctx, claim_id, subject)
+        | empires::claim_flags(ctx, claim_id, subject)
+        | party::granted_flags(ctx, subject, 1, claim_id)
 }
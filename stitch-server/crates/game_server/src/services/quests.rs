@@ -0,0 +1,261 @@
+use spacetimedb::{Identity, ReducerContext, Table};
+
+use crate::services::{achievements, inventory, skills};
+use crate::tables::{QuestChainDef, QuestChainState, QuestCondition, QuestStageState};
+use crate::tables::npc_quest::{quest_chain_def, quest_chain_state, quest_stage_def, quest_stage_state};
+use crate::tables::session_state::session_state;
+use crate::tables::transform_state::transform_state;
+
+pub const COND_COLLECT: u8 = 1;
+pub const COND_CRAFT: u8 = 2;
+pub const COND_REACH: u8 = 3;
+pub const COND_TALK: u8 = 4;
+pub const COND_KILL: u8 = 5;
+
+pub const REWARD_ITEM: u8 = 1;
+pub const REWARD_XP: u8 = 2;
+
+pub const CHAIN_STATUS_STARTED: u8 = 0;
+pub const CHAIN_STATUS_COMPLETED: u8 = 1;
+pub const STAGE_STATUS_IN_PROGRESS: u8 = 0;
+pub const STAGE_STATUS_COMPLETED: u8 = 1;
+
+pub fn chain_key(identity: Identity, chain_id: u64) -> String {
+    format!("{identity}:{chain_id}")
+}
+
+pub fn stage_key(chain_key: &str, stage_index: u32) -> String {
+    format!("{chain_key}:{stage_index}")
+}
+
+pub(crate) fn is_completed(ctx: &ReducerContext, identity: Identity, chain_id: u64) -> bool {
+    ctx.db
+        .quest_chain_state()
+        .chain_key()
+        .find(chain_key(identity, chain_id))
+        .is_some_and(|c| c.status == CHAIN_STATUS_COMPLETED)
+}
+
+/// Starts a chain once its prerequisites and skill requirement are met.
+/// Starting an active chain again is a no-op; completed chains cannot restart.
+pub(crate) fn start(ctx: &ReducerContext, identity: Identity, chain_id: u64) -> Result<(), String> {
+    let def = ctx
+        .db
+        .quest_chain_def()
+        .chain_id()
+        .find(chain_id)
+        .ok_or("quest chain not found".to_string())?;
+    let key = chain_key(identity, chain_id);
+    if let Some(existing) = ctx.db.quest_chain_state().chain_key().find(key.clone()) {
+        if existing.status == CHAIN_STATUS_COMPLETED {
+            return Err("quest chain already completed".to_string());
+        }
+        return Ok(());
+    }
+    if def.prerequisite_chain_ids.iter().any(|id| !is_completed(ctx, identity, *id)) {
+        return Err("prerequisite quest chain not completed".to_string());
+    }
+    skills::require(ctx, identity, def.skill_id, def.skill_level)?;
+    let first_stage = *def.stage_ids.first().ok_or("quest chain has no stages".to_string())?;
+
+    ctx.db.quest_chain_state().insert(QuestChainState {
+        chain_key: key.clone(),
+        identity,
+        chain_id,
+        status: CHAIN_STATUS_STARTED,
+        current_stage_index: 0,
+        started_at: ctx.timestamp,
+        updated_at: ctx.timestamp,
+    });
+    open_stage(ctx, &key, 0, first_stage)
+}
+
+fn open_stage(ctx: &ReducerContext, chain_key: &str, stage_index: u32, stage_id: u64) -> Result<(), String> {
+    let stage_def = ctx
+        .db
+        .quest_stage_def()
+        .stage_id()
+        .find(stage_id)
+        .ok_or("quest_stage_def missing".to_string())?;
+    ctx.db.quest_stage_state().insert(QuestStageState {
+        stage_key: stage_key(chain_key, stage_index),
+        chain_key: chain_key.to_string(),
+        stage_index,
+        stage_id,
+        status: STAGE_STATUS_IN_PROGRESS,
+        progress: vec![0; stage_def.conditions.len()],
+        updated_at: ctx.timestamp,
+    });
+    Ok(())
+}
+
+/// Event hook for the other subsystems (crafting, npc talk, kills). Bumps the
+/// matching conditions of every active stage and advances stages that are
+/// now satisfied. Never fails the calling reducer.
+pub(crate) fn record(ctx: &ReducerContext, identity: Identity, kind: u8, target_id: u64, amount: u32) {
+    let chains: Vec<QuestChainState> = ctx
+        .db
+        .quest_chain_state()
+        .iter()
+        .filter(|c| c.identity == identity && c.status == CHAIN_STATUS_STARTED)
+        .collect();
+    for chain in chains {
+        let key = stage_key(&chain.chain_key, chain.current_stage_index);
+        let Some(mut stage) = ctx.db.quest_stage_state().stage_key().find(key) else {
+            continue;
+        };
+        let Some(stage_def) = ctx.db.quest_stage_def().stage_id().find(stage.stage_id) else {
+            continue;
+        };
+        let mut changed = false;
+        for (i, cond) in stage_def.conditions.iter().enumerate() {
+            if cond.kind != kind || (cond.target_id != 0 && cond.target_id != target_id) {
+                continue;
+            }
+            let Some(progress) = stage.progress.get_mut(i) else {
+                continue;
+            };
+            if *progress < cond.quantity {
+                *progress = progress.saturating_add(amount).min(cond.quantity);
+                changed = true;
+            }
+        }
+        if !changed {
+            continue;
+        }
+        stage.updated_at = ctx.timestamp;
+        ctx.db.quest_stage_state().stage_key().update(stage);
+        if let Err(e) = try_advance(ctx, chain) {
+            log::info!("quest stage not advanced: identity={identity} reason={e}");
+        }
+    }
+}
+
+/// Collect and reach conditions are checked live; the rest use recorded progress.
+fn condition_met(ctx: &ReducerContext, identity: Identity, cond: &QuestCondition, progress: u32) -> bool {
+    match cond.kind {
+        COND_COLLECT => inventory::count_items(ctx, identity, cond.target_id) >= cond.quantity,
+        COND_REACH => {
+            let in_region = cond.target_id == 0
+                || ctx
+                    .db
+                    .session_state()
+                    .identity()
+                    .find(identity)
+                    .is_some_and(|s| s.region_id == cond.target_id);
+            in_region
+                && ctx.db.transform_state().entity_id().find(identity).is_some_and(|tf| {
+                    let dx = tf.position[0] - cond.pos_x;
+                    let dz = tf.position[2] - cond.pos_z;
+                    dx * dx + dz * dz <= cond.radius * cond.radius
+                })
+        }
+        _ => progress >= cond.quantity,
+    }
+}
+
+/// Completes the chain's current stage if all its conditions hold, opening
+/// the next stage or finishing the chain with its rewards. Returns `false`
+/// without touching any row when the stage is not done yet.
+pub(crate) fn try_advance(ctx: &ReducerContext, mut chain: QuestChainState) -> Result<bool, String> {
+    let key = stage_key(&chain.chain_key, chain.current_stage_index);
+    let mut stage = ctx
+        .db
+        .quest_stage_state()
+        .stage_key()
+        .find(key)
+        .ok_or("quest stage state missing".to_string())?;
+    let stage_def = ctx
+        .db
+        .quest_stage_def()
+        .stage_id()
+        .find(stage.stage_id)
+        .ok_or("quest_stage_def missing".to_string())?;
+    let all_met = stage_def.conditions.iter().enumerate().all(|(i, cond)| {
+        condition_met(ctx, chain.identity, cond, stage.progress.get(i).copied().unwrap_or(0))
+    });
+    if !all_met {
+        return Ok(false);
+    }
+
+    let chain_def = ctx
+        .db
+        .quest_chain_def()
+        .chain_id()
+        .find(chain.chain_id)
+        .ok_or("quest_chain_def missing".to_string())?;
+    let next_index = chain.current_stage_index + 1;
+    let next_stage = chain_def.stage_ids.get(next_index as usize).copied();
+    let reward_items = reward_items(&chain_def);
+    if next_stage.is_none() && !inventory::can_fit_all(ctx, chain.identity, &reward_items)? {
+        return Err("no inventory space for quest rewards".to_string());
+    }
+
+    for cond in stage_def.conditions.iter().filter(|c| c.kind == COND_COLLECT && c.consume) {
+        inventory::consume_items(ctx, chain.identity, cond.target_id, cond.quantity)?;
+    }
+    stage.status = STAGE_STATUS_COMPLETED;
+    stage.updated_at = ctx.timestamp;
+    ctx.db.quest_stage_state().stage_key().update(stage);
+
+    chain.updated_at = ctx.timestamp;
+    match next_stage {
+        Some(stage_id) => {
+            chain.current_stage_index = next_index;
+            let chain_key = chain.chain_key.clone();
+            ctx.db.quest_chain_state().chain_key().update(chain);
+            open_stage(ctx, &chain_key, next_index, stage_id)?;
+        }
+        None => {
+            let identity = chain.identity;
+            chain.status = CHAIN_STATUS_COMPLETED;
+            ctx.db.quest_chain_state().chain_key().update(chain);
+            inventory::add_all(ctx, identity, &reward_items)?;
+            for reward in chain_def.rewards.iter().filter(|r| r.kind == REWARD_XP) {
+                skills::award_xp(ctx, identity, reward.target_id as u32, reward.amount);
+            }
+            log::info!("quest chain completed: identity={identity} chain_id={}", chain_def.chain_id);
+            achievements::evaluate(ctx, identity);
+        }
+    }
+    Ok(true)
+}
+
+fn reward_items(def: &QuestChainDef) -> Vec<(u64, u32)> {
+    def.rewards
+        .iter()
+        .filter(|r| r.kind == REWARD_ITEM)
+        .map(|r| (r.target_id, r.amount as u32))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tables::QuestReward;
+
+    #[test]
+    fn reward_items_keeps_only_item_rewards_in_order() {
+        let def = QuestChainDef {
+            chain_id: 1,
+            name: "test".to_string(),
+            prerequisite_chain_ids: Vec::new(),
+            skill_id: 0,
+            skill_level: 0,
+            stage_ids: vec![10],
+            rewards: vec![
+                QuestReward { kind: REWARD_ITEM, target_id: 7, amount: 3 },
+                QuestReward { kind: REWARD_XP, target_id: 2, amount: 500 },
+                QuestReward { kind: REWARD_ITEM, target_id: 9, amount: 1 },
+            ],
+        };
+        assert_eq!(reward_items(&def), vec![(7, 3), (9, 1)]);
+    }
+
+    #[test]
+    fn keys_nest_stage_under_chain() {
+        let chain = chain_key(Identity::ZERO, 4);
+        assert_eq!(stage_key(&chain, 2), format!("{chain}:2"));
+        assert!(chain.ends_with(":4"));
+    }
+}
@@ -0,0 +1,169 @@
+use std::collections::HashMap;
+
+use spacetimedb::{Identity, ReducerContext, Table};
+
+use crate::services::inventory;
+use crate::services::world_gen::{GeneratedWorld, WATER_NONE, WATER_SWAMP, ZONE_SPAWN};
+use crate::tables::{ResourceDef, ResourceNode, ToolDef};
+use crate::tables::item_def::tool_def;
+use crate::tables::resource_node::{resource_def, resource_node};
+use crate::utils::hex::{ChunkCoordinates, HexCoordinates};
+use crate::utils::rng::SeededRng;
+
+const SPAWN_SEED_SALT: u64 = 0x7265_736F_7572_6365;
+
+pub fn is_depleted(node: &ResourceNode) -> bool {
+    node.health == 0
+}
+
+/// Deterministic node placement for a generated world: one roll per eligible
+/// cell and matching def, capped per chunk.
+pub fn plan_world_nodes(world: &GeneratedWorld, defs: &[ResourceDef]) -> Vec<(u64, HexCoordinates)> {
+    let mut defs: Vec<&ResourceDef> = defs.iter().collect();
+    defs.sort_by_key(|d| d.resource_def_id);
+
+    let mut rng = SeededRng::new(world.seed ^ SPAWN_SEED_SALT);
+    let mut per_chunk: HashMap<(i64, u64), u32> = HashMap::new();
+    let mut planned = Vec::new();
+    for cell in &world.cells {
+        if cell.zoning_type == ZONE_SPAWN {
+            continue;
+        }
+        if cell.water_body_type != WATER_NONE && cell.water_body_type != WATER_SWAMP {
+            continue;
+        }
+        let hex = HexCoordinates::new(cell.hex_x, cell.hex_z);
+        let chunk_id = ChunkCoordinates::from_hex(&hex, 0).to_index();
+        for def in defs.iter().filter(|d| d.biome_id == cell.biome_id) {
+            let roll = rng.next_u32() % 1000;
+            if roll >= def.spawn_per_mille as u32 {
+                continue;
+            }
+            let count = per_chunk.entry((chunk_id, def.resource_def_id)).or_insert(0);
+            if *count >= def.max_per_chunk {
+                continue;
+            }
+            *count += 1;
+            planned.push((def.resource_def_id, hex));
+            break;
+        }
+    }
+    planned
+}
+
+/// Replaces every node in `dimension_id` with the plan for `world`.
+pub(crate) fn spawn_world_nodes(ctx: &ReducerContext, world: &GeneratedWorld, dimension_id: u32) -> u32 {
+    let stale: Vec<u64> = ctx
+        .db
+        .resource_node()
+        .iter()
+        .filter(|n| n.dimension_id == dimension_id)
+        .map(|n| n.entity_id)
+        .collect();
+    for entity_id in stale {
+        ctx.db.resource_node().entity_id().delete(entity_id);
+    }
+
+    let defs: Vec<ResourceDef> = ctx.db.resource_def().iter().collect();
+    let max_health: HashMap<u64, u32> = defs.iter().map(|d| (d.resource_def_id, d.max_health)).collect();
+    let planned = plan_world_nodes(world, &defs);
+    for (resource_def_id, hex) in &planned {
+        ctx.db.resource_node().insert(ResourceNode {
+            entity_id: 0,
+            resource_def_id: *resource_def_id,
+            dimension_id,
+            chunk_id: ChunkCoordinates::from_hex(hex, dimension_id).to_index(),
+            hex_x: hex.x,
+            hex_z: hex.z,
+            health: max_health.get(resource_def_id).copied().unwrap_or(1).max(1),
+            depleted_at: None,
+            respawn_at: None,
+        });
+    }
+    planned.len() as u32
+}
+
+/// Best tool of the required type and tier in the owner's main inventory.
+/// Defs that need no tool return `Ok(None)`.
+pub(crate) fn find_tool(
+    ctx: &ReducerContext,
+    owner: Identity,
+    def: &ResourceDef,
+) -> Result<Option<ToolDef>, String> {
+    if def.tool_type == 0 {
+        return Ok(None);
+    }
+    inventory::main_inventory_stacks(ctx, owner)
+        .into_iter()
+        .filter_map(|(item_def_id, _)| ctx.db.tool_def().item_def_id().find(item_def_id))
+        .filter(|tool| tool.tool_type == def.tool_type && tool.tier >= def.tool_tier)
+        .max_by_key(|tool| (tool.power, tool.item_def_id))
+        .map(Some)
+        .ok_or_else(|| "required tool missing".to_string())
+}
+
+pub(crate) fn active_nodes_in_chunk(ctx: &ReducerContext, chunk_id: i64, resource_def_id: u64) -> u32 {
+    ctx.db
+        .resource_node()
+        .iter()
+        .filter(|n| n.chunk_id == chunk_id && n.resource_def_id == resource_def_id && !is_depleted(n))
+        .count() as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::world_gen::{self, BIOME_FOREST, BIOME_GRASSLAND};
+
+    fn def(resource_def_id: u64, biome_id: u16, spawn_per_mille: u16, max_per_chunk: u32) -> ResourceDef {
+        ResourceDef {
+            resource_def_id,
+            name: format!("node {resource_def_id}"),
+            biome_id,
+            tool_type: 0,
+            tool_tier: 0,
+            skill_id: 0,
+            skill_level: 0,
+            max_health: 10,
+            gather_duration_ms: 1000,
+            item_list_id: 0,
+            respawn_secs: 60,
+            spawn_per_mille,
+            max_per_chunk,
+        }
+    }
+
+    #[test]
+    fn plan_is_deterministic_and_ignores_def_order() {
+        let world = world_gen::generate(42, 2);
+        let defs = [def(1, BIOME_FOREST, 200, 50), def(2, BIOME_GRASSLAND, 200, 50)];
+        let reversed = [def(2, BIOME_GRASSLAND, 200, 50), def(1, BIOME_FOREST, 200, 50)];
+        let plan = plan_world_nodes(&world, &defs);
+        assert!(!plan.is_empty());
+        assert_eq!(plan, plan_world_nodes(&world, &reversed));
+    }
+
+    #[test]
+    fn nodes_land_only_on_dry_matching_cells_outside_spawn() {
+        let world = world_gen::generate(42, 2);
+        let plan = plan_world_nodes(&world, &[def(1, BIOME_FOREST, 1000, 1000)]);
+        for (_, hex) in &plan {
+            let cell = &world.cells[(hex.z * world.width + hex.x) as usize];
+            assert_eq!(cell.biome_id, BIOME_FOREST);
+            assert_ne!(cell.zoning_type, ZONE_SPAWN);
+            assert!(cell.water_body_type == WATER_NONE || cell.water_body_type == WATER_SWAMP);
+        }
+    }
+
+    #[test]
+    fn chunks_hold_at_most_max_per_chunk_nodes_per_def() {
+        let world = world_gen::generate(42, 2);
+        let plan = plan_world_nodes(&world, &[def(1, BIOME_FOREST, 1000, 3)]);
+        let mut per_chunk: HashMap<i64, u32> = HashMap::new();
+        for (_, hex) in &plan {
+            *per_chunk.entry(ChunkCoordinates::from_hex(hex, 0).to_index()).or_insert(0) += 1;
+        }
+        assert!(!per_chunk.is_empty());
+        assert!(per_chunk.values().all(|&n| n <= 3));
+    }
+}
@@ -0,0 +1,185 @@
+use spacetimedb::{Identity, ReducerContext, Table};
+
+use crate::services::{abilities, achievements, balance, stats};
+use crate::tables::{SkillLevelEvent, SkillProgress};
+use crate::tables::skill::{skill_def, skill_level_event, skill_progress};
+
+pub const SKILL_FORESTRY: u32 = 1;
+pub const SKILL_MINING: u32 = 2;
+pub const SKILL_FORAGING: u32 = 3;
+pub const SKILL_CARPENTRY: u32 = 4;
+pub const SKILL_SMITHING: u32 = 5;
+pub const SKILL_CONSTRUCTION: u32 = 6;
+pub const SKILL_COMBAT: u32 = 7;
+
+pub fn progress_key(identity: Identity, skill_id: u32) -> String {
+    format!("{identity}:{skill_id}")
+}
+
+pub(crate) fn skill_level(ctx: &ReducerContext, identity: Identity, skill_id: u32) -> u32 {
+    ctx.db
+        .skill_progress()
+        .progress_key()
+        .find(progress_key(identity, skill_id))
+        .map(|p| p.level)
+        .unwrap_or(0)
+}
+
+/// Best level across all skills; stands in for overall player progress.
+pub(crate) fn highest_level(ctx: &ReducerContext, identity: Identity) -> u32 {
+    ctx.db
+        .skill_progress()
+        .iter()
+        .filter(|p| p.identity == identity)
+        .map(|p| p.level)
+        .max()
+        .unwrap_or(0)
+}
+
+/// `skill_id == 0` means no requirement.
+pub(crate) fn meets_requirement(ctx: &ReducerContext, identity: Identity, skill_id: u32, level: u32) -> bool {
+    skill_id == 0 || skill_level(ctx, identity, skill_id) >= level
+}
+
+/// Like `meets_requirement`, but fails with e.g. "requires Carpentry 10".
+pub(crate) fn require(
+    ctx: &ReducerContext,
+    identity: Identity,
+    skill_id: u32,
+    level: u32,
+) -> Result<(), String> {
+    if meets_requirement(ctx, identity, skill_id, level) {
+        return Ok(());
+    }
+    let name = ctx
+        .db
+        .skill_def()
+        .skill_id()
+        .find(skill_id)
+        .map(|d| d.name)
+        .unwrap_or_else(|| format!("skill {skill_id}"));
+    Err(format!("requires {name} {level}"))
+}
+
+/// Level curve read from balance params.
+pub(crate) struct XpCurve {
+    base: f32,
+    exponent: f32,
+}
+
+impl XpCurve {
+    pub(crate) fn load(ctx: &ReducerContext) -> Self {
+        Self {
+            base: balance::get_f32(ctx, balance::SKILL_XP_BASE, 100.0).max(1.0),
+            exponent: balance::get_f32(ctx, balance::SKILL_XP_EXPONENT, 2.0).max(1.0),
+        }
+    }
+
+    pub(crate) fn xp_for_level(&self, level: u32) -> u64 {
+        (self.base as f64 * (level as f64).powf(self.exponent as f64)).ceil() as u64
+    }
+
+    pub(crate) fn level_for_xp(&self, xp: u64, max_level: u32) -> u32 {
+        let mut level = 0;
+        while level < max_level && xp >= self.xp_for_level(level + 1) {
+            level += 1;
+        }
+        level
+    }
+}
+
+/// Adds xp to a skill, capped at the skill's max level, and records a
+/// `skill_level_event` on level-up. Returns the new level if it changed.
+pub(crate) fn award_xp(ctx: &ReducerContext, identity: Identity, skill_id: u32, amount: u64) -> Option<u32> {
+    if skill_id == 0 || amount == 0 {
+        return None;
+    }
+    let Some(def) = ctx.db.skill_def().skill_id().find(skill_id) else {
+        log::warn!("award_xp: unknown skill_id={skill_id}");
+        return None;
+    };
+
+    let curve = XpCurve::load(ctx);
+    let key = progress_key(identity, skill_id);
+    let existing = ctx.db.skill_progress().progress_key().find(key.clone());
+    let is_new = existing.is_none();
+    let mut progress = existing.unwrap_or(SkillProgress {
+        progress_key: key,
+        identity,
+        skill_id,
+        xp: 0,
+        level: 0,
+        updated_at: ctx.timestamp,
+    });
+
+    let old_level = progress.level;
+    progress.xp = progress.xp.saturating_add(amount).min(curve.xp_for_level(def.max_level));
+    progress.level = curve.level_for_xp(progress.xp, def.max_level);
+    progress.updated_at = ctx.timestamp;
+    let new_level = progress.level;
+
+    if is_new {
+        ctx.db.skill_progress().insert(progress);
+    } else {
+        ctx.db.skill_progress().progress_key().update(progress);
+    }
+
+    if new_level <= old_level {
+        return None;
+    }
+    ctx.db.skill_level_event().insert(SkillLevelEvent {
+        event_id: 0,
+        identity,
+        skill_id,
+        old_level,
+        new_level,
+        created_at: ctx.timestamp,
+    });
+    stats::recompute(ctx, identity);
+    abilities::refresh_loadout(ctx, identity);
+    achievements::evaluate(ctx, identity);
+    log::info!("skill level up: identity={identity} skill_id={skill_id} level={old_level}->{new_level}");
+    Some(new_level)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::XpCurve;
+
+    fn default_curve() -> XpCurve {
+        XpCurve { base: 100.0, exponent: 2.0 }
+    }
+
+    #[test]
+    fn xp_for_level_follows_base_times_level_power() {
+        let curve = default_curve();
+        assert_eq!(curve.xp_for_level(0), 0);
+        assert_eq!(curve.xp_for_level(1), 100);
+        assert_eq!(curve.xp_for_level(2), 400);
+        assert_eq!(curve.xp_for_level(10), 10_000);
+    }
+
+    #[test]
+    fn xp_for_level_rounds_fractional_curves_up() {
+        let curve = XpCurve { base: 10.0, exponent: 1.5 };
+        // 10 * 2^1.5 = 28.28..
+        assert_eq!(curve.xp_for_level(2), 29);
+    }
+
+    #[test]
+    fn level_for_xp_uses_thresholds_inclusively() {
+        let curve = default_curve();
+        assert_eq!(curve.level_for_xp(0, 50), 0);
+        assert_eq!(curve.level_for_xp(99, 50), 0);
+        assert_eq!(curve.level_for_xp(100, 50), 1);
+        assert_eq!(curve.level_for_xp(399, 50), 1);
+        assert_eq!(curve.level_for_xp(400, 50), 2);
+    }
+
+    #[test]
+    fn level_for_xp_stops_at_max_level() {
+        let curve = default_curve();
+        assert_eq!(curve.level_for_xp(u64::MAX, 5), 5);
+        assert_eq!(curve.level_for_xp(10_000, 0), 0);
+    }
+}
@@ -0,0 +1,190 @@
+use spacetimedb::{Identity, ReducerContext, Table, Timestamp};
+
+use crate::services::{buffs, equipment, health, movement, skills};
+use crate::tables::{CharacterStats, StatModifier};
+use crate::tables::buff::status_effect;
+use crate::tables::character_stats::character_stats;
+use crate::tables::equipment::equipment_def;
+use crate::tables::item_instance::item_instance;
+
+pub const STAT_MAX_HP: u8 = 1;
+pub const STAT_ARMOR: u8 = 2;
+pub const STAT_ATTACK_POWER: u8 = 3;
+pub const STAT_MOVE_SPEED: u8 = 4;
+pub const STAT_CARRY_VOLUME: u8 = 5;
+pub const STAT_COLD_RESIST: u8 = 6;
+pub const STAT_HEAT_RESIST: u8 = 7;
+pub const STAT_POISON_RESIST: u8 = 8;
+pub const STAT_MAX_STAMINA: u8 = 9;
+pub const STAT_MAX_SATIATION: u8 = 10;
+pub const STAT_HP_REGEN: u8 = 11;
+pub const STAT_STAMINA_REGEN: u8 = 12;
+const STAT_COUNT: usize = 13;
+
+pub const BASE_MAX_HP: f32 = 100.0;
+pub const BASE_ARMOR: f32 = 0.0;
+pub const BASE_ATTACK_POWER: f32 = 10.0;
+pub const BASE_CARRY_VOLUME: f32 = 2000.0;
+pub const BASE_MAX_STAMINA: f32 = 100.0;
+pub const BASE_MAX_SATIATION: f32 = 1000.0;
+pub const BASE_HP_REGEN: f32 = 1.0;
+pub const BASE_STAMINA_REGEN: f32 = 2.0;
+
+/// Flat bonus per skill level: (skill_id, stat, per_level).
+const SKILL_BONUSES: [(u32, u8, f32); 4] = [
+    (skills::SKILL_COMBAT, STAT_MAX_HP, 2.0),
+    (skills::SKILL_COMBAT, STAT_ATTACK_POWER, 0.5),
+    (skills::SKILL_MINING, STAT_CARRY_VOLUME, 10.0),
+    (skills::SKILL_CONSTRUCTION, STAT_CARRY_VOLUME, 10.0),
+];
+
+/// Flat and percentage bonuses per stat, applied as `(base + flat) * (1 + pct)`.
+#[derive(Default)]
+pub(crate) struct StatTotals {
+    flat: [f32; STAT_COUNT],
+    pct: [f32; STAT_COUNT],
+}
+
+impl StatTotals {
+    pub(crate) fn add(&mut self, modifier: &StatModifier) {
+        let idx = modifier.stat as usize;
+        if idx >= STAT_COUNT || !modifier.value.is_finite() {
+            return;
+        }
+        if modifier.is_percentage {
+            self.pct[idx] += modifier.value;
+        } else {
+            self.flat[idx] += modifier.value;
+        }
+    }
+
+    fn apply(&self, stat: u8, base: f32, min: f32, max: f32) -> f32 {
+        let idx = stat as usize;
+        ((base + self.flat[idx]) * (1.0 + self.pct[idx])).clamp(min, max)
+    }
+}
+
+/// Rebuilds the cached stats from base values, equipment, active buffs, speed
+/// modifiers and skill levels. Call whenever one of those sources changes.
+pub(crate) fn recompute(ctx: &ReducerContext, identity: Identity) -> CharacterStats {
+    let mut totals = StatTotals::default();
+    for slot in equipment::equipped(ctx, identity) {
+        let Some(instance) = ctx.db.item_instance().item_instance_id().find(slot.item_instance_id) else {
+            continue;
+        };
+        let Some(def) = ctx.db.equipment_def().item_def_id().find(instance.item_def_id) else {
+            continue;
+        };
+        for modifier in &def.modifiers {
+            totals.add(modifier);
+        }
+    }
+    for (skill_id, stat, per_level) in SKILL_BONUSES {
+        let level = skills::skill_level(ctx, identity, skill_id);
+        if level > 0 {
+            totals.add(&StatModifier {
+                stat,
+                value: per_level * level as f32,
+                is_percentage: false,
+            });
+        }
+    }
+    let mut valid_until: Option<Timestamp> = None;
+    for buff in buffs::active_buffs(ctx, identity) {
+        if let Some(expires_at) = buff.expires_at {
+            valid_until = Some(valid_until.map_or(expires_at, |t| t.min(expires_at)));
+        }
+        let Some(def) = ctx.db.status_effect().effect_id().find(buff.effect_id) else {
+            continue;
+        };
+        for modifier in &def.modifiers {
+            totals.add(&StatModifier {
+                value: modifier.value * buff.stacks as f32,
+                ..modifier.clone()
+            });
+        }
+    }
+
+    let stats = CharacterStats {
+        identity,
+        max_hp: totals.apply(STAT_MAX_HP, BASE_MAX_HP, 1.0, 10_000.0) as i32,
+        max_stamina: totals.apply(STAT_MAX_STAMINA, BASE_MAX_STAMINA, 1.0, 5_000.0) as i32,
+        max_satiation: totals.apply(STAT_MAX_SATIATION, BASE_MAX_SATIATION, 1.0, 10_000.0) as i32,
+        hp_regen: totals.apply(STAT_HP_REGEN, BASE_HP_REGEN, 0.0, 100.0) as i32,
+        stamina_regen: totals.apply(STAT_STAMINA_REGEN, BASE_STAMINA_REGEN, 0.0, 100.0) as i32,
+        armor: totals.apply(STAT_ARMOR, BASE_ARMOR, 0.0, 1_000.0) as i32,
+        attack_power: totals.apply(STAT_ATTACK_POWER, BASE_ATTACK_POWER, 1.0, 1_000.0) as i32,
+        move_speed: totals.apply(STAT_MOVE_SPEED, movement::BASE_MOVE_SPEED, 1.0, 30.0),
+        carry_volume: totals.apply(STAT_CARRY_VOLUME, BASE_CARRY_VOLUME, 0.0, 100_000.0) as i32,
+        cold_resist: totals.apply(STAT_COLD_RESIST, 0.0, 0.0, 1_000.0) as i32,
+        heat_resist: totals.apply(STAT_HEAT_RESIST, 0.0, 0.0, 1_000.0) as i32,
+        poison_resist: totals.apply(STAT_POISON_RESIST, 0.0, 0.0, 1_000.0) as i32,
+        valid_until,
+        updated_at: ctx.timestamp,
+    };
+
+    health::clamp_to_stats(ctx, &stats);
+
+    if ctx.db.character_stats().identity().find(identity).is_some() {
+        ctx.db.character_stats().identity().update(stats)
+    } else {
+        ctx.db.character_stats().insert(stats)
+    }
+}
+
+/// Value of a single stat from a stats row; unknown stats read as 0.
+pub fn stat_value(stats: &CharacterStats, stat: u8) -> f32 {
+    match stat {
+        STAT_MAX_HP => stats.max_hp as f32,
+        STAT_ARMOR => stats.armor as f32,
+        STAT_ATTACK_POWER => stats.attack_power as f32,
+        STAT_MOVE_SPEED => stats.move_speed,
+        STAT_CARRY_VOLUME => stats.carry_volume as f32,
+        STAT_COLD_RESIST => stats.cold_resist as f32,
+        STAT_HEAT_RESIST => stats.heat_resist as f32,
+        STAT_POISON_RESIST => stats.poison_resist as f32,
+        STAT_MAX_STAMINA => stats.max_stamina as f32,
+        STAT_MAX_SATIATION => stats.max_satiation as f32,
+        STAT_HP_REGEN => stats.hp_regen as f32,
+        STAT_STAMINA_REGEN => stats.stamina_regen as f32,
+        _ => 0.0,
+    }
+}
+
+/// Cached stats, recomputed first if missing or past a timed source's expiry.
+pub(crate) fn get(ctx: &ReducerContext, identity: Identity) -> CharacterStats {
+    match ctx.db.character_stats().identity().find(identity) {
+        Some(stats) if stats.valid_until.is_none_or(|t| t > ctx.timestamp) => stats,
+        _ => recompute(ctx, identity),
+    }
+}
+
+/// Armor reduces damage by `armor / (armor + 100)`; every hit deals at least 1.
+pub fn mitigated_damage(attack_power: i32, armor: i32) -> i32 {
+    let armor = armor.max(0) as f32;
+    ((attack_power.max(0) as f32) * 100.0 / (100.0 + armor)).round().max(1.0) as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::mitigated_damage;
+
+    #[test]
+    fn armor_scales_damage_by_100_over_100_plus_armor() {
+        assert_eq!(mitigated_damage(50, 0), 50);
+        assert_eq!(mitigated_damage(50, 100), 25);
+        assert_eq!(mitigated_damage(30, 50), 20);
+    }
+
+    #[test]
+    fn every_hit_deals_at_least_one_damage() {
+        assert_eq!(mitigated_damage(1, 10_000), 1);
+        assert_eq!(mitigated_damage(0, 0), 1);
+        assert_eq!(mitigated_damage(-20, 0), 1);
+    }
+
+    #[test]
+    fn negative_armor_counts_as_none() {
+        assert_eq!(mitigated_damage(40, -50), 40);
+    }
+}
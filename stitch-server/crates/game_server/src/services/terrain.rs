@@ -0,0 +1,171 @@
+use std::collections::HashSet;
+
+use spacetimedb::{ReducerContext, Table};
+
+use crate::services::housing;
+use crate::services::world_gen::{OVERWORLD_DIMENSION, WATER_LAKE, WATER_RIVER, WATER_SEA, WATER_SWAMP};
+use crate::tables::{TerrainCell, TerrainChunk};
+use crate::tables::building_state::building_state;
+use crate::tables::navigation::{nav_cell_cost, nav_obstacle};
+use crate::tables::terrain_chunk::{terrain_chunk, world_gen_params};
+use crate::utils::hex::{ChunkCoordinates, HexCoordinates};
+
+/// Largest upward elevation step between neighbouring cells a walker can take.
+pub const MAX_CLIMB_PER_STEP: i16 = 3;
+
+/// Interiors have no generated terrain; every other region walks the overworld.
+pub fn dimension_for_region(region_id: u64) -> Option<u32> {
+    match housing::dimension_of_region(region_id) {
+        Some(_) => None,
+        None => Some(OVERWORLD_DIMENSION),
+    }
+}
+
+/// Positions map 1:1 onto hex coordinates (x → hex_x, z → hex_z).
+pub fn hex_at(x: f32, z: f32) -> HexCoordinates {
+    HexCoordinates::round(x, z)
+}
+
+pub fn is_walkable(cell: &TerrainCell) -> bool {
+    cell.water_body_type != WATER_SEA && cell.water_body_type != WATER_LAKE
+}
+
+/// Height a walker stands at: shallow water is waded, not swum.
+pub fn surface_height(cell: &TerrainCell) -> f32 {
+    match cell.water_body_type {
+        WATER_RIVER | WATER_SWAMP => cell.elevation.max(cell.water_level) as f32,
+        _ => cell.elevation as f32,
+    }
+}
+
+/// Cell lookups for one region, caching the last chunk read.
+pub(crate) struct TerrainSampler<'a> {
+    ctx: &'a ReducerContext,
+    dimension: Option<u32>,
+    chunk: Option<TerrainChunk>,
+}
+
+impl<'a> TerrainSampler<'a> {
+    pub(crate) fn for_region(ctx: &'a ReducerContext, region_id: u64) -> Self {
+        TerrainSampler {
+            ctx,
+            dimension: dimension_for_region(region_id),
+            chunk: None,
+        }
+    }
+
+    /// `None` when the region has no terrain or the cell was never generated.
+    pub(crate) fn cell(&mut self, hex: HexCoordinates) -> Option<TerrainCell> {
+        let dimension = self.dimension?;
+        let coords = ChunkCoordinates::from_hex(&hex, dimension);
+        if self.chunk.as_ref().map(|c| (c.chunk_x, c.chunk_z)) != Some((coords.x, coords.z)) {
+            self.chunk = self.ctx.db.terrain_chunk().chunk_id().find(coords.to_index());
+        }
+        let chunk = self.chunk.as_ref()?;
+        chunk
+            .cells
+            .get(ChunkCoordinates::cell_index(&hex))
+            .filter(|c| c.hex_x == hex.x && c.hex_z == hex.z)
+            .cloned()
+    }
+}
+
+/// Cells among `hexes` covered by a standing building, a blocking obstacle or a
+/// blocked nav cell.
+pub(crate) fn blocked_cells(
+    ctx: &ReducerContext,
+    region_id: u64,
+    hexes: &[HexCoordinates],
+) -> HashSet<(i32, i32)> {
+    let wanted: HashSet<(i32, i32)> = hexes.iter().map(|h| (h.x, h.z)).collect();
+    let mut blocked = HashSet::new();
+    for building in ctx.db.building_state().iter() {
+        if building.region_id == region_id
+            && building.state != 2
+            && wanted.contains(&(building.hex_x, building.hex_z))
+        {
+            blocked.insert((building.hex_x, building.hex_z));
+        }
+    }
+    for obstacle in ctx.db.nav_obstacle().iter() {
+        if obstacle.region_id == region_id
+            && obstacle.blocked
+            && wanted.contains(&(obstacle.hex_x, obstacle.hex_z))
+        {
+            blocked.insert((obstacle.hex_x, obstacle.hex_z));
+        }
+    }
+    for cost in ctx.db.nav_cell_cost().iter() {
+        if cost.region_id == region_id && cost.blocked && wanted.contains(&(cost.hex_x, cost.hex_z)) {
+            blocked.insert((cost.hex_x, cost.hex_z));
+        }
+    }
+    blocked
+}
+
+/// Where new characters appear: the generated spawn cell, or the origin before
+/// any world exists.
+pub(crate) fn spawn_position(ctx: &ReducerContext, region_id: u64) -> Vec<f32> {
+    let Some(dimension) = dimension_for_region(region_id) else {
+        return vec![0.0, 0.0, 0.0];
+    };
+    let Some(params) = ctx.db.world_gen_params().dimension_id().find(dimension) else {
+        return vec![0.0, 0.0, 0.0];
+    };
+    let spawn = HexCoordinates::new(params.spawn_x, params.spawn_z);
+    let y = TerrainSampler::for_region(ctx, region_id)
+        .cell(spawn)
+        .map(|cell| surface_height(&cell))
+        .unwrap_or(0.0);
+    vec![spawn.x as f32, y, spawn.z as f32]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::world_gen::WATER_NONE;
+
+    fn cell(water_body_type: u8, elevation: i16, water_level: i16) -> TerrainCell {
+        TerrainCell {
+            hex_x: 0,
+            hex_z: 0,
+            elevation,
+            water_level,
+            water_body_type,
+            biome_id: 1,
+            biome_blend: 0,
+            vegetation_density: 0,
+            zoning_type: 0,
+            original_elevation: elevation,
+            distance_to_water: 0,
+            distance_to_sea: 0,
+        }
+    }
+
+    #[test]
+    fn sea_and_lakes_block_walking() {
+        assert!(is_walkable(&cell(WATER_NONE, 2, 0)));
+        assert!(is_walkable(&cell(WATER_RIVER, 1, 2)));
+        assert!(is_walkable(&cell(WATER_SWAMP, 1, 2)));
+        assert!(!is_walkable(&cell(WATER_SEA, -5, 0)));
+        assert!(!is_walkable(&cell(WATER_LAKE, 0, 2)));
+    }
+
+    #[test]
+    fn shallow_water_is_waded_at_the_water_surface() {
+        assert_eq!(surface_height(&cell(WATER_NONE, 4, 9)), 4.0);
+        assert_eq!(surface_height(&cell(WATER_RIVER, 1, 3)), 3.0);
+        assert_eq!(surface_height(&cell(WATER_SWAMP, 5, 3)), 5.0);
+    }
+
+    #[test]
+    fn interiors_have_no_terrain_dimension() {
+        assert_eq!(dimension_for_region(1), Some(OVERWORLD_DIMENSION));
+        assert_eq!(dimension_for_region(housing::interior_region_id(3)), None);
+    }
+
+    #[test]
+    fn positions_map_onto_the_nearest_hex() {
+        assert_eq!(hex_at(2.2, -0.9), HexCoordinates::new(2, -1));
+    }
+}
@@ -0,0 +1,95 @@
+use spacetimedb::{Identity, ReducerContext, Table};
+
+use crate::tables::{CurrencyFlowDaily, CurrencyTxn, Wallet};
+use crate::tables::wallet::{currency_flow_daily, currency_txn, wallet};
+use crate::utils::time::day_of;
+
+pub const REASON_NPC_PURCHASE: u8 = 1;
+pub const REASON_NPC_SALE: u8 = 2;
+
+pub fn flow_key(day: u32, reason: u8) -> String {
+    format!("{day}:{reason}")
+}
+
+pub(crate) fn balance(ctx: &ReducerContext, identity: Identity) -> u64 {
+    ctx.db.wallet().identity().find(identity).map(|w| w.balance).unwrap_or(0)
+}
+
+fn set_balance(ctx: &ReducerContext, identity: Identity, balance: u64) {
+    match ctx.db.wallet().identity().find(identity) {
+        Some(mut wallet) => {
+            wallet.balance = balance;
+            wallet.updated_at = ctx.timestamp;
+            ctx.db.wallet().identity().update(wallet);
+        }
+        None => {
+            ctx.db.wallet().insert(Wallet {
+                identity,
+                balance,
+                updated_at: ctx.timestamp,
+            });
+        }
+    }
+}
+
+/// Sink/source tally for the economy health metrics.
+fn record_flow(ctx: &ReducerContext, reason: u8, sourced: u64, sunk: u64) {
+    let day = day_of(ctx.timestamp);
+    match ctx.db.currency_flow_daily().flow_key().find(flow_key(day, reason)) {
+        Some(mut flow) => {
+            flow.sourced = flow.sourced.saturating_add(sourced);
+            flow.sunk = flow.sunk.saturating_add(sunk);
+            flow.updated_at = ctx.timestamp;
+            ctx.db.currency_flow_daily().flow_key().update(flow);
+        }
+        None => {
+            ctx.db.currency_flow_daily().insert(CurrencyFlowDaily {
+                flow_key: flow_key(day, reason),
+                day,
+                reason,
+                sourced,
+                sunk,
+                updated_at: ctx.timestamp,
+            });
+        }
+    }
+}
+
+fn record_txn(ctx: &ReducerContext, identity: Identity, amount: i64, reason: u8, balance_after: u64) {
+    ctx.db.currency_txn().insert(CurrencyTxn {
+        txn_id: 0,
+        identity,
+        amount,
+        reason,
+        balance_after,
+        created_at: ctx.timestamp,
+    });
+}
+
+/// Mints coins into a wallet; counted as a currency source.
+pub(crate) fn credit(ctx: &ReducerContext, identity: Identity, amount: u64, reason: u8) {
+    if amount == 0 {
+        return;
+    }
+    let after = balance(ctx, identity).saturating_add(amount);
+    set_balance(ctx, identity, after);
+    record_txn(ctx, identity, amount as i64, reason, after);
+    record_flow(ctx, reason, amount, 0);
+}
+
+/// Removes coins from a wallet; counted as a currency sink. Fails without
+/// touching any row when the balance is too low.
+pub(crate) fn debit(ctx: &ReducerContext, identity: Identity, amount: u64, reason: u8) -> Result<(), String> {
+    if amount == 0 {
+        return Ok(());
+    }
+    let current = balance(ctx, identity);
+    if current < amount {
+        return Err("not enough coins".to_string());
+    }
+    let after = current - amount;
+    set_balance(ctx, identity, after);
+    record_txn(ctx, identity, -(amount as i64), reason, after);
+    record_flow(ctx, reason, 0, amount);
+    Ok(())
+}
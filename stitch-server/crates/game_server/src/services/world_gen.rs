@@ -0,0 +1,535 @@
+//! Deterministic procedural world generation.
+//!
+//! The pipeline is pure (no database access) so the same `(seed, size)` always
+//! produces the same cells; `world_generate` persists the result as
+//! `terrain_chunk` rows. Stages: land mask with edge falloff, distance fields,
+//! biome map, elevation, lakes, rivers (MST over lakes, A* carved), swamps,
+//! vegetation and the spawn zone.
+
+use std::cmp::Reverse;
+use std::collections::VecDeque;
+
+use crate::tables::{TerrainCell, TerrainChunk};
+use crate::utils::hex::{ChunkCoordinates, HexCoordinates, CHUNK_HEIGHT, CHUNK_SIZE, CHUNK_WIDTH};
+use crate::utils::noise::{Fbm, NoiseSpecs};
+use crate::utils::pathfinding::Pathfinder;
+
+pub const OVERWORLD_DIMENSION: u32 = 0;
+pub const MAX_WORLD_CHUNKS: u32 = 8;
+pub const SEA_LEVEL: i16 = 0;
+
+pub const WATER_NONE: u8 = 0;
+pub const WATER_SEA: u8 = 1;
+pub const WATER_LAKE: u8 = 2;
+pub const WATER_RIVER: u8 = 3;
+pub const WATER_SWAMP: u8 = 4;
+
+pub const BIOME_OCEAN: u16 = 0;
+pub const BIOME_GRASSLAND: u16 = 1;
+pub const BIOME_FOREST: u16 = 2;
+pub const BIOME_DESERT: u16 = 3;
+pub const BIOME_SWAMP: u16 = 4;
+pub const BIOME_TUNDRA: u16 = 5;
+pub const BIOME_MOUNTAIN: u16 = 6;
+pub const BIOME_COUNT: usize = 7;
+
+pub const ZONE_WILD: u8 = 0;
+pub const ZONE_SPAWN: u8 = 1;
+
+const LAND_THRESHOLD: f32 = 0.3;
+const EDGE_FALLOFF: f32 = 0.45;
+const COAST_RAMP_CELLS: f32 = 6.0;
+const LAKE_MIN_SEA_DISTANCE: i16 = 4;
+const LAKE_MIN_CELLS: usize = 4;
+const MAX_RIVER_LAKES: usize = 10;
+const RIVER_MAX_EXPANSIONS: usize = 20_000;
+const SPAWN_RADIUS: i32 = 6;
+
+struct BiomeParams {
+    base_height: f32,
+    height_range: f32,
+    terrace: i16,
+    /// Lake noise must exceed this; values above 1.0 disable lakes.
+    lake_threshold: f32,
+    lake_depth: i16,
+    rivers: bool,
+    vegetation: u8,
+}
+
+fn biome_params(biome_id: u16) -> BiomeParams {
+    let (base_height, height_range, terrace, lake_threshold, lake_depth, rivers, vegetation) = match biome_id {
+        BIOME_GRASSLAND => (2.0, 6.0, 0, 0.64, 2, true, 120),
+        BIOME_FOREST => (3.0, 8.0, 0, 0.66, 2, true, 220),
+        BIOME_DESERT => (2.0, 5.0, 0, 2.0, 0, false, 20),
+        BIOME_SWAMP => (1.0, 2.0, 0, 0.6, 1, true, 160),
+        BIOME_TUNDRA => (3.0, 8.0, 0, 0.68, 3, false, 60),
+        BIOME_MOUNTAIN => (10.0, 30.0, 4, 2.0, 0, false, 40),
+        _ => (0.0, 0.0, 0, 2.0, 0, false, 0),
+    };
+    BiomeParams {
+        base_height,
+        height_range,
+        terrace,
+        lake_threshold,
+        lake_depth,
+        rivers,
+        vegetation,
+    }
+}
+
+pub struct GeneratedWorld {
+    pub seed: u64,
+    pub size_chunks: u32,
+    pub width: i32,
+    pub cells: Vec<TerrainCell>,
+    pub land_cells: u32,
+    pub lake_count: u32,
+    pub river_cells: u32,
+    pub spawn: HexCoordinates,
+}
+
+struct Grid {
+    width: i32,
+}
+
+impl Grid {
+    fn len(&self) -> usize {
+        (self.width * self.width) as usize
+    }
+
+    fn index(&self, x: i32, z: i32) -> Option<usize> {
+        if x < 0 || z < 0 || x >= self.width || z >= self.width {
+            return None;
+        }
+        Some((z * self.width + x) as usize)
+    }
+
+    fn coords(&self, index: usize) -> HexCoordinates {
+        HexCoordinates::new(index as i32 % self.width, index as i32 / self.width)
+    }
+
+    fn neighbors(&self, index: usize) -> impl Iterator<Item = usize> + '_ {
+        self.coords(index)
+            .neighbors()
+            .into_iter()
+            .filter_map(|n| self.index(n.x, n.z))
+    }
+}
+
+fn noise(seed: u64, salt: u64, scale: f32, octaves: u32) -> Fbm {
+    Fbm::new(NoiseSpecs {
+        seed: (seed ^ salt.wrapping_mul(0x9E37_79B9_7F4A_7C15)) as i64,
+        scale,
+        octaves,
+        persistence: 0.5,
+        lacunarity: 2.0,
+        offset: (0.0, 0.0),
+    })
+}
+
+fn sample(fbm: &Fbm, hex: HexCoordinates) -> f32 {
+    let (wx, wz) = hex.to_world_position();
+    fbm.sample(wx, wz)
+}
+
+/// Multi-source BFS over hex neighbours; cells unreachable from any source get `i16::MAX`.
+fn distance_field(grid: &Grid, is_source: impl Fn(usize) -> bool) -> Vec<i16> {
+    let mut distance = vec![i16::MAX; grid.len()];
+    let mut queue = VecDeque::new();
+    for (i, d) in distance.iter_mut().enumerate() {
+        if is_source(i) {
+            *d = 0;
+            queue.push_back(i);
+        }
+    }
+    while let Some(i) = queue.pop_front() {
+        let next = distance[i].saturating_add(1);
+        for n in grid.neighbors(i) {
+            if distance[n] > next {
+                distance[n] = next;
+                queue.push_back(n);
+            }
+        }
+    }
+    distance
+}
+
+fn pick_biome(dist_sea: i16, temperature: f32, moisture: f32, ridge: f32) -> u16 {
+    if dist_sea >= 6 && ridge > 0.6 {
+        BIOME_MOUNTAIN
+    } else if temperature < 0.38 {
+        BIOME_TUNDRA
+    } else if temperature > 0.6 && moisture < 0.45 {
+        BIOME_DESERT
+    } else if moisture > 0.6 {
+        BIOME_SWAMP
+    } else if moisture > 0.5 {
+        BIOME_FOREST
+    } else {
+        BIOME_GRASSLAND
+    }
+}
+
+pub fn generate(seed: u64, size_chunks: u32) -> GeneratedWorld {
+    let width = size_chunks as i32 * CHUNK_WIDTH;
+    let grid = Grid { width };
+    let center = HexCoordinates::new(width / 2, width / 2);
+    let radius = (width / 2 - 2).max(1) as f32;
+
+    let land_noise = noise(seed, 1, 1.0 / 48.0, 5);
+    let temperature_noise = noise(seed, 2, 1.0 / 96.0, 3);
+    let moisture_noise = noise(seed, 3, 1.0 / 80.0, 3);
+    let ridge_noise = noise(seed, 4, 1.0 / 40.0, 4);
+    let height_noise = noise(seed, 5, 1.0 / 24.0, 4);
+    let lake_noise = noise(seed, 6, 1.0 / 20.0, 3);
+    let vegetation_noise = noise(seed, 7, 1.0 / 12.0, 2);
+
+    // Land mask: fBm minus a radial falloff so the world is an island.
+    let land: Vec<bool> = (0..grid.len())
+        .map(|i| {
+            let hex = grid.coords(i);
+            let d = hex.distance_to(&center) as f32;
+            if d >= radius {
+                return false;
+            }
+            sample(&land_noise, hex) - EDGE_FALLOFF * (d * d) / (radius * radius) > LAND_THRESHOLD
+        })
+        .collect();
+
+    let dist_sea = distance_field(&grid, |i| !land[i]);
+    let dist_land = distance_field(&grid, |i| land[i]);
+
+    let biomes: Vec<u16> = (0..grid.len())
+        .map(|i| {
+            if !land[i] {
+                return BIOME_OCEAN;
+            }
+            let hex = grid.coords(i);
+            let latitude = 1.0 - hex.z as f32 / width as f32;
+            let temperature = sample(&temperature_noise, hex) * 0.6 + latitude * 0.4;
+            pick_biome(
+                dist_sea[i],
+                temperature,
+                sample(&moisture_noise, hex),
+                sample(&ridge_noise, hex),
+            )
+        })
+        .collect();
+
+    let mut cells: Vec<TerrainCell> = (0..grid.len())
+        .map(|i| {
+            let hex = grid.coords(i);
+            let (elevation, water_level, water_body_type) = if land[i] {
+                let params = biome_params(biomes[i]);
+                let ramp = (dist_sea[i] as f32 / COAST_RAMP_CELLS).min(1.0);
+                let raw = (params.base_height + sample(&height_noise, hex) * params.height_range) * ramp;
+                let mut elevation = SEA_LEVEL + 1 + raw as i16;
+                if params.terrace > 0 {
+                    elevation -= elevation.rem_euclid(params.terrace);
+                    elevation = elevation.max(SEA_LEVEL + 1);
+                }
+                (elevation, SEA_LEVEL, WATER_NONE)
+            } else {
+                let depth = (2 + dist_land[i].min(19) * 2).min(40);
+                (SEA_LEVEL - depth, SEA_LEVEL, WATER_SEA)
+            };
+
+            let same = grid.neighbors(i).filter(|&n| biomes[n] == biomes[i]).count()
+                + (6 - grid.neighbors(i).count());
+            TerrainCell {
+                hex_x: hex.x,
+                hex_z: hex.z,
+                elevation,
+                water_level,
+                water_body_type,
+                biome_id: biomes[i],
+                biome_blend: (same * 255 / 6) as u8,
+                vegetation_density: 0,
+                zoning_type: ZONE_WILD,
+                original_elevation: elevation,
+                distance_to_water: 0,
+                distance_to_sea: dist_sea[i],
+            }
+        })
+        .collect();
+
+    let lakes = carve_lakes(&grid, &mut cells, &dist_sea, &lake_noise);
+    let river_cells = carve_rivers(&grid, &mut cells, &lakes);
+
+    for cell in cells.iter_mut() {
+        if cell.biome_id == BIOME_SWAMP && cell.water_body_type == WATER_NONE && cell.elevation <= SEA_LEVEL + 2 {
+            cell.water_body_type = WATER_SWAMP;
+            cell.water_level = cell.elevation + 1;
+        }
+    }
+
+    let dist_water = distance_field(&grid, |i| cells[i].water_body_type != WATER_NONE);
+    for (i, cell) in cells.iter_mut().enumerate() {
+        cell.distance_to_water = dist_water[i];
+        if cell.water_body_type != WATER_NONE && cell.water_body_type != WATER_SWAMP {
+            continue;
+        }
+        let base = biome_params(cell.biome_id).vegetation as f32;
+        let mut density = base * (0.5 + sample(&vegetation_noise, grid.coords(i)));
+        if dist_water[i] <= 2 {
+            density += 30.0;
+        }
+        cell.vegetation_density = density.clamp(0.0, 255.0) as u8;
+    }
+
+    let spawn = (0..grid.len())
+        .filter(|&i| cells[i].water_body_type == WATER_NONE && cells[i].biome_id != BIOME_MOUNTAIN)
+        .min_by_key(|&i| (grid.coords(i).distance_to(&center), i))
+        .map(|i| grid.coords(i))
+        .unwrap_or(center);
+    for (i, cell) in cells.iter_mut().enumerate() {
+        if grid.coords(i).distance_to(&spawn) <= SPAWN_RADIUS && cell.water_body_type != WATER_SEA {
+            cell.zoning_type = ZONE_SPAWN;
+        }
+    }
+
+    GeneratedWorld {
+        seed,
+        size_chunks,
+        width,
+        land_cells: land.iter().filter(|l| **l).count() as u32,
+        lake_count: lakes.len() as u32,
+        river_cells,
+        spawn,
+        cells,
+    }
+}
+
+struct Lake {
+    cells: Vec<usize>,
+    surface: i16,
+}
+
+/// Flood-fills lake-noise blobs that stay clear of the coast and flattens them.
+fn carve_lakes(grid: &Grid, cells: &mut [TerrainCell], dist_sea: &[i16], lake_noise: &Fbm) -> Vec<Lake> {
+    let candidate: Vec<bool> = (0..grid.len())
+        .map(|i| {
+            let cell = &cells[i];
+            cell.water_body_type == WATER_NONE
+                && dist_sea[i] >= LAKE_MIN_SEA_DISTANCE
+                && sample(lake_noise, grid.coords(i)) > biome_params(cell.biome_id).lake_threshold
+        })
+        .collect();
+
+    let mut visited = vec![false; grid.len()];
+    let mut lakes = Vec::new();
+    for start in 0..grid.len() {
+        if !candidate[start] || visited[start] {
+            continue;
+        }
+        let mut component = vec![start];
+        let mut queue = VecDeque::from([start]);
+        visited[start] = true;
+        while let Some(i) = queue.pop_front() {
+            for n in grid.neighbors(i) {
+                if candidate[n] && !visited[n] {
+                    visited[n] = true;
+                    component.push(n);
+                    queue.push_back(n);
+                }
+            }
+        }
+        if component.len() < LAKE_MIN_CELLS {
+            continue;
+        }
+
+        let surface = component.iter().map(|&i| cells[i].elevation).min().unwrap_or(SEA_LEVEL + 1);
+        for &i in &component {
+            let depth = biome_params(cells[i].biome_id).lake_depth.max(1);
+            cells[i].elevation = surface - depth;
+            cells[i].water_level = surface;
+            cells[i].water_body_type = WATER_LAKE;
+        }
+        lakes.push(Lake {
+            cells: component,
+            surface,
+        });
+    }
+    lakes
+}
+
+/// Connects the largest river-enabled lakes with a minimum spanning tree of
+/// A* paths and carves each path into a river channel.
+fn carve_rivers(grid: &Grid, cells: &mut [TerrainCell], lakes: &[Lake]) -> u32 {
+    let mut sources: Vec<&Lake> = lakes
+        .iter()
+        .filter(|lake| biome_params(cells[lake.cells[0]].biome_id).rivers)
+        .collect();
+    sources.sort_by_key(|lake| (Reverse(lake.cells.len()), lake.cells[0]));
+    sources.truncate(MAX_RIVER_LAKES);
+
+    let mut edges = Vec::new();
+    for a in 0..sources.len() {
+        for b in (a + 1)..sources.len() {
+            let from = sources[a].cells[sources[a].cells.len() / 2];
+            let to = sources[b].cells[sources[b].cells.len() / 2];
+            if let Some((cost, path)) = river_path(grid, cells, from, to) {
+                edges.push((cost, a, b, path));
+            }
+        }
+    }
+    edges.sort_by_key(|(cost, a, b, _)| (*cost, *a, *b));
+
+    let mut parent: Vec<usize> = (0..sources.len()).collect();
+    fn find(parent: &mut [usize], mut i: usize) -> usize {
+        while parent[i] != i {
+            parent[i] = parent[parent[i]];
+            i = parent[i];
+        }
+        i
+    }
+
+    let mut river_cells = 0u32;
+    for (_, a, b, path) in edges {
+        let (ra, rb) = (find(&mut parent, a), find(&mut parent, b));
+        if ra == rb {
+            continue;
+        }
+        parent[ra] = rb;
+
+        let surface = sources[a].surface.min(sources[b].surface);
+        for i in path {
+            let cell = &mut cells[i];
+            if cell.water_body_type != WATER_NONE {
+                continue;
+            }
+            cell.elevation = (cell.elevation - 1).min(surface).max(SEA_LEVEL);
+            cell.water_level = cell.elevation + 1;
+            cell.water_body_type = WATER_RIVER;
+            river_cells += 1;
+        }
+    }
+    river_cells
+}
+
+/// Land-only A* where climbing is expensive, so rivers follow valleys.
+fn river_path(grid: &Grid, cells: &[TerrainCell], from: usize, to: usize) -> Option<(u32, Vec<usize>)> {
+    let goal = grid.coords(to);
+    let mut pathfinder = Pathfinder::with_capacity(RIVER_MAX_EXPANSIONS);
+    let result = pathfinder.find_path(
+        from,
+        to,
+        RIVER_MAX_EXPANSIONS,
+        |i| grid.coords(i).distance_to(&goal) as u32 * 10,
+        |current, edges| {
+            for n in grid.neighbors(current) {
+                if cells[n].water_body_type == WATER_SEA {
+                    continue;
+                }
+                let climb = (cells[n].elevation - cells[current].elevation).unsigned_abs() as u32;
+                edges.push((n, 10 + climb * 4));
+            }
+        },
+    )?;
+    Some((result.cost, result.path))
+}
+
+impl GeneratedWorld {
+    /// FNV-1a over every cell in grid order.
+    pub fn checksum(&self) -> u64 {
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        let mut feed = |bytes: &[u8]| {
+            for b in bytes {
+                hash ^= *b as u64;
+                hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+            }
+        };
+        for cell in &self.cells {
+            feed(&cell.hex_x.to_le_bytes());
+            feed(&cell.hex_z.to_le_bytes());
+            feed(&cell.elevation.to_le_bytes());
+            feed(&cell.water_level.to_le_bytes());
+            feed(&[cell.water_body_type, cell.biome_blend, cell.vegetation_density, cell.zoning_type]);
+            feed(&cell.biome_id.to_le_bytes());
+            feed(&cell.original_elevation.to_le_bytes());
+            feed(&cell.distance_to_water.to_le_bytes());
+            feed(&cell.distance_to_sea.to_le_bytes());
+        }
+        hash
+    }
+
+    pub fn into_chunks(self, dimension_id: u32) -> Vec<TerrainChunk> {
+        let chunks_per_side = self.size_chunks as i32;
+        let mut chunks = Vec::with_capacity((chunks_per_side * chunks_per_side) as usize);
+        for chunk_z in 0..chunks_per_side {
+            for chunk_x in 0..chunks_per_side {
+                let mut cells = Vec::with_capacity(CHUNK_SIZE);
+                let mut biome_distribution = vec![0u16; BIOME_COUNT];
+                for local_z in 0..CHUNK_HEIGHT {
+                    for local_x in 0..CHUNK_WIDTH {
+                        let x = chunk_x * CHUNK_WIDTH + local_x;
+                        let z = chunk_z * CHUNK_HEIGHT + local_z;
+                        let cell = self.cells[(z * self.width + x) as usize].clone();
+                        if let Some(count) = biome_distribution.get_mut(cell.biome_id as usize) {
+                            *count += 1;
+                        }
+                        cells.push(cell);
+                    }
+                }
+                let coords = ChunkCoordinates {
+                    x: chunk_x,
+                    z: chunk_z,
+                    dimension: dimension_id,
+                };
+                chunks.push(TerrainChunk {
+                    chunk_id: coords.to_index(),
+                    dimension_id,
+                    chunk_x,
+                    chunk_z,
+                    cells,
+                    is_generated: true,
+                    generation_seed: self.seed,
+                    biome_distribution,
+                });
+            }
+        }
+        chunks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Pinned output of `generate(42, 2)`. A change here means existing worlds would regenerate
+    /// differently; update the values only on purpose.
+    const GOLDEN_CHECKSUM: u64 = 0x80aa_4e97_426b_16cd;
+
+    fn cell(world: &GeneratedWorld, x: i32, z: i32) -> &TerrainCell {
+        &world.cells[(z * world.width + x) as usize]
+    }
+
+    #[test]
+    fn checksum_matches_golden_value() {
+        assert_eq!(generate(42, 2).checksum(), GOLDEN_CHECKSUM);
+        assert_ne!(generate(43, 2).checksum(), GOLDEN_CHECKSUM);
+    }
+
+    #[test]
+    fn spot_cells_match_golden_values() {
+        let world = generate(42, 2);
+        assert_eq!(world.width, 64);
+        assert_eq!((world.land_cells, world.lake_count, world.river_cells), (959, 4, 22));
+        assert_eq!((world.spawn.x, world.spawn.z), (31, 33));
+
+        // (x, z, biome_id, elevation, water_body_type)
+        let expected: [(i32, i32, u16, i16, u8); 6] = [
+            (0, 0, BIOME_OCEAN, -40, 1),
+            (40, 15, BIOME_SWAMP, 2, 4),
+            (16, 31, BIOME_FOREST, 2, 0),
+            (31, 33, BIOME_SWAMP, 3, 0),
+            (45, 21, BIOME_MOUNTAIN, 20, 0),
+            (8, 38, BIOME_GRASSLAND, 1, 0),
+        ];
+        for (x, z, biome_id, elevation, water_body_type) in expected {
+            let c = cell(&world, x, z);
+            assert_eq!((c.hex_x, c.hex_z), (x, z));
+            assert_eq!((c.biome_id, c.elevation, c.water_body_type), (biome_id, elevation, water_body_type));
+        }
+    }
+}
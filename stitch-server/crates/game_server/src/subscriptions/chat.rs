@@ -0,0 +1,27 @@
+use spacetimedb::ViewContext;
+
+use crate::services::chat;
+use crate::tables::ChatMessage;
+use crate::tables::chat::{chat_channel__view, chat_message__view};
+
+/// Chat messages the caller may read: system and region chat, local chat in
+/// range, party chat, guild chat of their empire, and whispers they sent or
+/// received.
+#[spacetimedb::view(name = visible_chat_message, public)]
+pub fn visible_chat_message(ctx: &ViewContext) -> Vec<ChatMessage> {
+    let mut messages = Vec::new();
+    for (channel_type, scope_id) in chat::member_scopes(ctx, ctx.sender) {
+        let key = chat::channel_key(channel_type, scope_id);
+        let Some(channel) = ctx.db.chat_channel().channel_key().find(key) else {
+            continue;
+        };
+        messages.extend(
+            ctx.db
+                .chat_message()
+                .channel_id()
+                .filter(channel.channel_id)
+                .filter(|m| chat::can_see(ctx, ctx.sender, &channel, m)),
+        );
+    }
+    messages
+}
@@ -1 +1,4 @@
 //! Subscription query helpers for AOI and stream paths.
+
+pub mod chat;
+pub mod moderation;
@@ -0,0 +1,16 @@
+use spacetimedb::ViewContext;
+
+use crate::services::moderation;
+use crate::tables::ReportQueue;
+use crate::tables::moderation::report_queue__view;
+
+/// The whole report queue for admins and moderators; empty for everyone else.
+#[spacetimedb::view(name = visible_report_queue, public)]
+pub fn visible_report_queue(ctx: &ViewContext) -> Vec<ReportQueue> {
+    if !moderation::is_staff(ctx, ctx.sender) {
+        return Vec::new();
+    }
+    (moderation::REPORT_OPEN..=moderation::REPORT_DISMISSED)
+        .flat_map(|status| ctx.db.report_queue().status().filter(status))
+        .collect()
+}
@@ -0,0 +1,34 @@
+use spacetimedb::{Identity, Timestamp};
+
+#[spacetimedb::table(name = ability_def, public)]
+pub struct AbilityDef {
+    #[primary_key]
+    pub ability_def_id: u32,
+    pub name: String,
+    pub kind: u8, // 1=attack, 2=heal, 3=buff
+    pub cooldown_ms: u64,
+    pub stamina_cost: i32,
+    /// Max target distance; 0 means self only.
+    pub range: f32,
+    /// Bonus damage for attacks, hp restored for heals.
+    pub power: i32,
+    /// Status effect applied to the target, 0 = none.
+    pub effect_id: u32,
+    pub skill_id: u32,
+    pub skill_level: u32,
+    /// Main-hand weapon type required; 0 = innate.
+    pub weapon_type: u8,
+}
+
+/// An ability a player has had in their loadout, with its server-time cooldown.
+/// Rows outlive unequipping (`available = false`) so swapping gear cannot reset cooldowns.
+#[spacetimedb::table(name = ability_state, public)]
+pub struct AbilityState {
+    #[primary_key]
+    pub ability_key: String,
+    pub identity: Identity,
+    pub ability_def_id: u32,
+    pub available: bool,
+    pub cooldown_until: Timestamp,
+    pub use_count: u32,
+}
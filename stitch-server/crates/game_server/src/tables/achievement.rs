@@ -0,0 +1,60 @@
+use spacetimedb::{Identity, SpacetimeType, Timestamp};
+
+#[derive(SpacetimeType, Clone, Debug, PartialEq)]
+pub struct AchievementRequirement {
+    pub kind: u8,       // 1=item,2=recipe,3=biome,4=npc (knowledge kinds),5=quest chain completed
+    pub target_id: u64, // 0 = any of the kind, counted against `count`
+    pub count: u32,
+}
+
+#[derive(SpacetimeType, Clone, Debug, PartialEq)]
+pub struct AchievementReward {
+    pub kind: u8, // 1=title,2=cosmetic
+    pub reward_id: u64,
+    pub label: String,
+}
+
+#[spacetimedb::table(name = achievement_def, public)]
+pub struct AchievementDef {
+    #[primary_key]
+    pub achievement_id: u64,
+    pub name: String,
+    pub prerequisite_ids: Vec<u64>, // must be acquired before this one is discovered
+    pub skill_id: u32,              // 0 = no skill requirement
+    pub skill_level: u32,
+    pub requirements: Vec<AchievementRequirement>,
+    pub rewards: Vec<AchievementReward>,
+}
+
+#[spacetimedb::table(name = achievement_state, public)]
+pub struct AchievementState {
+    #[primary_key]
+    pub achievement_key: String, // "identity:achievement_id"
+    pub identity: Identity,
+    pub achievement_id: u64,
+    pub status: u8, // 0=discovered,1=acquired
+    pub discovered_at: Timestamp,
+    pub acquired_at: Option<Timestamp>,
+}
+
+#[spacetimedb::table(name = knowledge_state, public)]
+pub struct KnowledgeState {
+    #[primary_key]
+    pub knowledge_key: String, // "identity:kind:target_id"
+    pub identity: Identity,
+    pub kind: u8, // 1=item,2=recipe,3=biome,4=npc
+    pub target_id: u64,
+    pub discovered_at: Timestamp,
+}
+
+#[spacetimedb::table(name = player_unlock, public)]
+pub struct PlayerUnlock {
+    #[primary_key]
+    pub unlock_key: String, // "identity:kind:reward_id"
+    pub identity: Identity,
+    pub kind: u8, // 1=title,2=cosmetic
+    pub reward_id: u64,
+    pub label: String,
+    pub achievement_id: u64,
+    pub unlocked_at: Timestamp,
+}
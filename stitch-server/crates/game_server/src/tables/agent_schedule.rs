@@ -0,0 +1,137 @@
+use spacetimedb::ScheduleAt;
+
+use crate::agents::buff_expiry_agent::buff_expiry_agent;
+use crate::agents::chat_cleanup_agent::chat_cleanup_agent;
+use crate::agents::claim_ownership_agent::claim_ownership_agent;
+use crate::agents::claim_upkeep_agent::claim_upkeep_agent;
+use crate::agents::day_night_agent::day_night_agent;
+use crate::agents::empire_upkeep_agent::empire_upkeep_agent;
+use crate::agents::environment_effect_agent::environment_effect_agent;
+use crate::agents::housing_collapse_agent::housing_collapse_agent;
+use crate::agents::npc_ai_agent::npc_ai_agent;
+use crate::agents::npc_conversation_agent::npc_conversation_agent;
+use crate::agents::npc_lease_agent::npc_lease_agent;
+use crate::agents::npc_mover_agent::npc_mover_agent;
+use crate::agents::npc_restock_agent::npc_restock_agent;
+use crate::agents::player_regen_agent::player_regen_agent;
+use crate::agents::resource_regen_agent::resource_regen_agent;
+
+#[spacetimedb::table(name = buff_expiry_agent_schedule, scheduled(buff_expiry_agent))]
+pub struct BuffExpiryAgentSchedule {
+    #[primary_key]
+    #[auto_inc]
+    pub scheduled_id: u64,
+    pub scheduled_at: ScheduleAt,
+}
+
+#[spacetimedb::table(name = chat_cleanup_agent_schedule, scheduled(chat_cleanup_agent))]
+pub struct ChatCleanupAgentSchedule {
+    #[primary_key]
+    #[auto_inc]
+    pub scheduled_id: u64,
+    pub scheduled_at: ScheduleAt,
+}
+
+#[spacetimedb::table(name = claim_ownership_agent_schedule, scheduled(claim_ownership_agent))]
+pub struct ClaimOwnershipAgentSchedule {
+    #[primary_key]
+    #[auto_inc]
+    pub scheduled_id: u64,
+    pub scheduled_at: ScheduleAt,
+}
+
+#[spacetimedb::table(name = claim_upkeep_agent_schedule, scheduled(claim_upkeep_agent))]
+pub struct ClaimUpkeepAgentSchedule {
+    #[primary_key]
+    #[auto_inc]
+    pub scheduled_id: u64,
+    pub scheduled_at: ScheduleAt,
+}
+
+#[spacetimedb::table(name = day_night_agent_schedule, scheduled(day_night_agent))]
+pub struct DayNightAgentSchedule {
+    #[primary_key]
+    #[auto_inc]
+    pub scheduled_id: u64,
+    pub scheduled_at: ScheduleAt,
+}
+
+#[spacetimedb::table(name = empire_upkeep_agent_schedule, scheduled(empire_upkeep_agent))]
+pub struct EmpireUpkeepAgentSchedule {
+    #[primary_key]
+    #[auto_inc]
+    pub scheduled_id: u64,
+    pub scheduled_at: ScheduleAt,
+}
+
+#[spacetimedb::table(name = environment_effect_agent_schedule, scheduled(environment_effect_agent))]
+pub struct EnvironmentEffectAgentSchedule {
+    #[primary_key]
+    #[auto_inc]
+    pub scheduled_id: u64,
+    pub scheduled_at: ScheduleAt,
+}
+
+#[spacetimedb::table(name = housing_collapse_agent_schedule, scheduled(housing_collapse_agent))]
+pub struct HousingCollapseAgentSchedule {
+    #[primary_key]
+    #[auto_inc]
+    pub scheduled_id: u64,
+    pub scheduled_at: ScheduleAt,
+}
+
+#[spacetimedb::table(name = npc_ai_agent_schedule, scheduled(npc_ai_agent))]
+pub struct NpcAiAgentSchedule {
+    #[primary_key]
+    #[auto_inc]
+    pub scheduled_id: u64,
+    pub scheduled_at: ScheduleAt,
+}
+
+#[spacetimedb::table(name = npc_conversation_agent_schedule, scheduled(npc_conversation_agent))]
+pub struct NpcConversationAgentSchedule {
+    #[primary_key]
+    #[auto_inc]
+    pub scheduled_id: u64,
+    pub scheduled_at: ScheduleAt,
+}
+
+#[spacetimedb::table(name = npc_lease_agent_schedule, scheduled(npc_lease_agent))]
+pub struct NpcLeaseAgentSchedule {
+    #[primary_key]
+    #[auto_inc]
+    pub scheduled_id: u64,
+    pub scheduled_at: ScheduleAt,
+}
+
+#[spacetimedb::table(name = npc_mover_agent_schedule, scheduled(npc_mover_agent))]
+pub struct NpcMoverAgentSchedule {
+    #[primary_key]
+    #[auto_inc]
+    pub scheduled_id: u64,
+    pub scheduled_at: ScheduleAt,
+}
+
+#[spacetimedb::table(name = npc_restock_agent_schedule, scheduled(npc_restock_agent))]
+pub struct NpcRestockAgentSchedule {
+    #[primary_key]
+    #[auto_inc]
+    pub scheduled_id: u64,
+    pub scheduled_at: ScheduleAt,
+}
+
+#[spacetimedb::table(name = player_regen_agent_schedule, scheduled(player_regen_agent))]
+pub struct PlayerRegenAgentSchedule {
+    #[primary_key]
+    #[auto_inc]
+    pub scheduled_id: u64,
+    pub scheduled_at: ScheduleAt,
+}
+
+#[spacetimedb::table(name = resource_regen_agent_schedule, scheduled(resource_regen_agent))]
+pub struct ResourceRegenAgentSchedule {
+    #[primary_key]
+    #[auto_inc]
+    pub scheduled_id: u64,
+    pub scheduled_at: ScheduleAt,
+}
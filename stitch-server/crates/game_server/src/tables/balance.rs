@@ -0,0 +1,11 @@
+use spacetimedb::Timestamp;
+
+/// Live-tunable balance knobs. Values are stored as strings and parsed by
+/// `services::balance` with a hardcoded fallback.
+#[spacetimedb::table(name = balance_params, private)]
+pub struct BalanceParams {
+    #[primary_key]
+    pub key: String,
+    pub value: String,
+    pub updated_at: Timestamp,
+}
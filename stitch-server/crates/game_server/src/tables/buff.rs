@@ -0,0 +1,48 @@
+use spacetimedb::{Identity, Timestamp};
+
+use crate::tables::StatModifier;
+
+/// Static definition of a buff or debuff.
+#[spacetimedb::table(name = status_effect, public)]
+pub struct StatusEffect {
+    #[primary_key]
+    pub effect_id: u32,
+    pub name: String,
+    /// Effects sharing a non-zero category conflict; the higher priority wins.
+    pub category: u16,
+    pub priority: i32,
+    pub duration_ms: u64, // 0 = until removed
+    pub max_stacks: u32,
+    pub is_debuff: bool,
+    /// Applied once per stack.
+    pub modifiers: Vec<StatModifier>,
+}
+
+#[spacetimedb::table(name = buff_state, public)]
+pub struct BuffState {
+    #[primary_key]
+    pub buff_key: String,
+    pub identity: Identity,
+    pub effect_id: u32,
+    pub category: u16,
+    pub priority: i32,
+    pub stacks: u32,
+    pub source: u8, // 1=food, 2=ability, 3=environment, 4=hunger
+    pub applied_at: Timestamp,
+    pub expires_at: Option<Timestamp>,
+}
+
+/// A biome hazard: while a player stands in a biome carrying `hazard_tag` and
+/// their resistance stat is below the threshold, `effect_id` is kept applied
+/// and `damage_per_tick` is dealt every environment tick.
+#[spacetimedb::table(name = environment_effect_def, public)]
+pub struct EnvironmentEffectDef {
+    #[primary_key]
+    pub env_effect_id: u32,
+    pub name: String,
+    pub hazard_tag: u8,
+    pub effect_id: u32,
+    pub damage_per_tick: i32,
+    pub resistance_stat: u8,
+    pub resistance_threshold: i32,
+}
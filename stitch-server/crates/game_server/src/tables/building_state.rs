@@ -5,6 +5,7 @@ pub struct BuildingState {
     #[primary_key]
     pub entity_id: u64,
     pub owner_identity: Identity,
+    pub building_type: u32,
     pub region_id: u64,
     pub hex_x: i32,
     pub hex_z: i32,
@@ -16,3 +17,17 @@ pub struct BuildingState {
     pub created_at: Timestamp,
     pub updated_at: Timestamp,
 }
+
+/// Static per-type data: skill gate, material cost and construction steps. Only types with a def
+/// can be placed.
+#[spacetimedb::table(name = building_def, public)]
+pub struct BuildingDef {
+    #[primary_key]
+    pub building_type: u32,
+    pub name: String,
+    pub skill_id: u32,
+    pub skill_level: u32,
+    pub required_item_def_id: u64,
+    pub required_item_qty: u32,
+    pub build_required: u32,
+}
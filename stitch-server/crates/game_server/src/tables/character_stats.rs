@@ -0,0 +1,24 @@
+use spacetimedb::{Identity, Timestamp};
+
+/// Cached result of `services::stats::recompute`.
+#[spacetimedb::table(name = character_stats, public)]
+pub struct CharacterStats {
+    #[primary_key]
+    pub identity: Identity,
+    pub max_hp: i32,
+    pub max_stamina: i32,
+    pub max_satiation: i32,
+    /// Vitals restored every regen tick, before passive bonuses.
+    pub hp_regen: i32,
+    pub stamina_regen: i32,
+    pub armor: i32,
+    pub attack_power: i32,
+    pub move_speed: f32,
+    pub carry_volume: i32,
+    pub cold_resist: i32,
+    pub heat_resist: i32,
+    pub poison_resist: i32,
+    /// Earliest expiry among timed sources; the row is stale from then on.
+    pub valid_until: Option<Timestamp>,
+    pub updated_at: Timestamp,
+}
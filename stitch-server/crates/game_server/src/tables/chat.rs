@@ -0,0 +1,52 @@
+use spacetimedb::{Identity, Timestamp};
+
+/// Channels are created on first use, one per type and scope.
+#[spacetimedb::table(name = chat_channel, public)]
+pub struct ChatChannel {
+    #[primary_key]
+    #[auto_inc]
+    pub channel_id: u64,
+    #[unique]
+    pub channel_key: String, // "channel_type:scope_id"
+    pub channel_type: u8,    // 1=region,2=local,3=party,4=guild,5=whisper,6=system
+    pub scope_id: u64,       // region_id, party_id or empire_id; 0 for whisper/system
+    pub created_at: Timestamp,
+}
+
+/// Private; players read their channels through the `visible_chat_message` view.
+#[spacetimedb::table(name = chat_message, private)]
+pub struct ChatMessage {
+    #[primary_key]
+    #[auto_inc]
+    pub message_id: u64,
+    #[index(btree)]
+    pub channel_id: u64,
+    pub sender_id: Identity,
+    pub recipient_id: Option<Identity>, // whisper target
+    pub pos_x: f32,                     // sender position, for local range
+    pub pos_z: f32,
+    pub text: String,
+    pub sent_at: Timestamp,
+}
+
+#[spacetimedb::table(name = chat_mute, private)]
+pub struct ChatMute {
+    #[primary_key]
+    pub identity: Identity,
+    pub muted_until: Timestamp,
+    pub reason: String,
+    pub muted_by: Identity,
+    pub created_at: Timestamp,
+}
+
+/// Original text of messages the chat filter changed.
+#[spacetimedb::table(name = chat_filter_log, private)]
+pub struct ChatFilterLog {
+    #[primary_key]
+    #[auto_inc]
+    pub log_id: u64,
+    pub identity: Identity,
+    pub channel_id: u64,
+    pub original: String,
+    pub created_at: Timestamp,
+}
@@ -0,0 +1,13 @@
+use spacetimedb::Timestamp;
+
+#[spacetimedb::table(name = claim_local_state, public)]
+pub struct ClaimLocalState {
+    #[primary_key]
+    pub claim_id: u64,
+    pub supplies: u32,
+    pub population: u32,
+    pub num_tiles: u32,
+    pub protected: bool,
+    pub last_upkeep_at: Timestamp,
+    pub updated_at: Timestamp,
+}
@@ -0,0 +1,15 @@
+use spacetimedb::{Identity, Timestamp};
+
+#[spacetimedb::table(name = claim_member_state, public)]
+pub struct ClaimMemberState {
+    #[primary_key]
+    pub member_key: String,
+    pub claim_id: u64,
+    pub member_identity: Identity,
+    pub rank: u8,   // 0=guest,1=member,2=officer,3=co_owner,4=owner
+    pub status: u8, // 0=invited,1=active
+    pub invited_by: Identity,
+    pub last_active_at: Timestamp,
+    pub joined_at: Timestamp,
+    pub updated_at: Timestamp,
+}
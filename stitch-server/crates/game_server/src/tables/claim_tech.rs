@@ -0,0 +1,21 @@
+use spacetimedb::Timestamp;
+
+#[spacetimedb::table(name = claim_tech_def, public)]
+pub struct ClaimTechDef {
+    #[primary_key]
+    pub tech_id: u64,
+    pub tier: u32,
+    pub required_tech_id: u64, // 0 = none
+    pub supply_cost: u32,
+    pub max_tiles: u32,
+    pub unlocks_building_type: u32, // 0 = none
+}
+
+#[spacetimedb::table(name = claim_tech_state, public)]
+pub struct ClaimTechState {
+    #[primary_key]
+    pub tech_key: String,
+    pub claim_id: u64,
+    pub tech_id: u64,
+    pub researched_at: Timestamp,
+}
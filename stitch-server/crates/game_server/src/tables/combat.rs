@@ -6,6 +6,8 @@ pub struct CombatState {
     pub identity: Identity,
     pub region_id: u64,
     pub in_combat: bool,
+    /// Deprecated and unused: hit points live in `resource_state.hp`. Kept so the module still
+    /// publishes over existing data.
     pub current_hp: i32,
     pub last_attack_client_ts_ms: u64,
     pub updated_at: Timestamp,
@@ -0,0 +1,46 @@
+use spacetimedb::{Identity, ScheduleAt, SpacetimeType, Timestamp};
+
+use crate::reducers::craft::craft_complete::craft_complete;
+
+#[derive(SpacetimeType, Clone, Debug, PartialEq)]
+pub struct RecipeItem {
+    pub item_def_id: u64,
+    pub quantity: u32,
+}
+
+#[spacetimedb::table(name = recipe_def, public)]
+pub struct RecipeDef {
+    #[primary_key]
+    pub recipe_id: u64,
+    pub name: String,
+    pub inputs: Vec<RecipeItem>,
+    pub outputs: Vec<RecipeItem>,
+    pub station_building_type: u32, // 0 = hand craft
+    pub skill_id: u32,
+    pub skill_level: u32,
+    pub duration_ms: u64,
+    pub xp_reward: u64,
+}
+
+#[spacetimedb::table(name = craft_job, public)]
+pub struct CraftJob {
+    #[primary_key]
+    #[auto_inc]
+    pub job_id: u64,
+    pub owner_identity: Identity,
+    pub recipe_id: u64,
+    pub building_id: u64, // 0 = hand craft
+    pub status: u8,       // 0=processing,1=ready (awaiting collect)
+    pub started_at: Timestamp,
+    pub completes_at: Timestamp,
+}
+
+#[spacetimedb::table(name = craft_timer, scheduled(craft_complete))]
+pub struct CraftTimer {
+    #[primary_key]
+    #[auto_inc]
+    pub scheduled_id: u64,
+    pub scheduled_at: ScheduleAt,
+    #[unique]
+    pub job_id: u64,
+}
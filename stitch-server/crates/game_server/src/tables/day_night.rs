@@ -0,0 +1,13 @@
+use spacetimedb::Timestamp;
+
+/// World clock, advanced by `day_night_agent`. Single row with `id = 0`.
+#[spacetimedb::table(name = day_night_state, public)]
+pub struct DayNightState {
+    #[primary_key]
+    pub id: u32,
+    pub is_day: bool,
+    pub hour: u8, // 0-23; day runs 6-18
+    pub cycle_number: u64,
+    pub phase_started_at: Timestamp,
+    pub updated_at: Timestamp,
+}
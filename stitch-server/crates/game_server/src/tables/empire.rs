@@ -0,0 +1,95 @@
+use spacetimedb::{Identity, ScheduleAt, Timestamp};
+
+use crate::agents::empire_siege_agent::empire_siege_tick;
+
+#[spacetimedb::table(name = empire_state, public)]
+pub struct EmpireState {
+    #[primary_key]
+    pub empire_id: u64,
+    pub name: String,
+    pub emperor_identity: Identity,
+    pub capital_claim_id: u64,
+    pub treasury: u64,
+    pub num_claims: u32,
+    pub created_at: Timestamp,
+    pub updated_at: Timestamp,
+}
+
+#[spacetimedb::table(name = empire_rank_state, public)]
+pub struct EmpireRankState {
+    #[primary_key]
+    pub rank_key: String,
+    pub empire_id: u64,
+    pub rank: u8, // 0=emperor (highest) .. 4=citizen
+    pub title: String,
+    pub permissions: u32,
+}
+
+#[spacetimedb::table(name = empire_member_state, public)]
+pub struct EmpireMemberState {
+    #[primary_key]
+    pub member_key: String,
+    pub empire_id: u64,
+    #[index(btree)]
+    pub member_identity: Identity,
+    pub rank: u8,
+    pub joined_at: Timestamp,
+    pub updated_at: Timestamp,
+}
+
+#[spacetimedb::table(name = empire_claim_state, public)]
+pub struct EmpireClaimState {
+    #[primary_key]
+    pub claim_id: u64,
+    pub empire_id: u64,
+    pub joined_at: Timestamp,
+}
+
+/// An empire's standing offer for a claim to join; the claim owner accepts with `empire_claim_join`.
+#[spacetimedb::table(name = empire_claim_invitation, public)]
+pub struct EmpireClaimInvitation {
+    #[primary_key]
+    pub invite_key: String,
+    pub empire_id: u64,
+    #[index(btree)]
+    pub claim_id: u64,
+    pub invited_by: Identity,
+    pub created_at: Timestamp,
+}
+
+#[spacetimedb::table(name = empire_node_state, public)]
+pub struct EmpireNodeState {
+    #[primary_key]
+    pub node_id: u64, // watchtower building entity_id
+    pub empire_id: u64,
+    pub region_id: u64,
+    pub hex_x: i32,
+    pub hex_z: i32,
+    pub energy: u32,
+    pub upkeep: u32,
+    pub influence_radius: u32,
+    pub active: bool,
+    pub updated_at: Timestamp,
+}
+
+#[spacetimedb::table(name = empire_node_siege_state, public)]
+pub struct EmpireNodeSiegeState {
+    #[primary_key]
+    pub siege_id: String,
+    pub node_id: u64,
+    pub attacker_empire_id: u64,
+    pub defender_empire_id: u64,
+    pub attacker_supplies: u32,
+    pub phase: u8, // 0=active,1=attacker_won,2=defender_won
+    pub started_at: Timestamp,
+    pub updated_at: Timestamp,
+}
+
+#[spacetimedb::table(name = empire_siege_timer, scheduled(empire_siege_tick))]
+pub struct EmpireSiegeTimer {
+    #[primary_key]
+    #[auto_inc]
+    pub scheduled_id: u64,
+    pub scheduled_at: ScheduleAt,
+    pub siege_id: String,
+}
@@ -0,0 +1,29 @@
+use spacetimedb::{Identity, SpacetimeType};
+
+#[derive(SpacetimeType, Clone, Debug, PartialEq)]
+pub struct StatModifier {
+    pub stat: u8,
+    pub value: f32,
+    pub is_percentage: bool,
+}
+
+#[spacetimedb::table(name = equipment_def, public)]
+pub struct EquipmentDef {
+    #[primary_key]
+    pub item_def_id: u64,
+    pub slot: u8,
+    pub weapon_type: u8, // 0 = not a weapon
+    pub skill_id: u32,
+    pub skill_level: u32,
+    pub modifiers: Vec<StatModifier>,
+}
+
+/// An equipped item. The item instance leaves the inventory slot while equipped.
+#[spacetimedb::table(name = equipment_slot, public)]
+pub struct EquipmentSlot {
+    #[primary_key]
+    pub slot_key: String,
+    pub identity: Identity,
+    pub slot: u8,
+    pub item_instance_id: u64,
+}
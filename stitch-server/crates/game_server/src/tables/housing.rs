@@ -0,0 +1,64 @@
+use spacetimedb::{Identity, ScheduleAt, Timestamp};
+
+use crate::agents::housing_collapse_agent::housing_regen;
+
+#[spacetimedb::table(name = housing_state, public)]
+pub struct HousingState {
+    #[primary_key]
+    pub owner_identity: Identity,
+    pub entrance_building_id: u64,
+    pub network_id: u64,
+    pub dimension_id: u64,
+    pub locked_until: Timestamp,
+    pub is_empty: bool,
+    pub last_visited_at: Timestamp,
+    pub created_at: Timestamp,
+    pub updated_at: Timestamp,
+}
+
+#[spacetimedb::table(name = dimension_network, public)]
+pub struct DimensionNetwork {
+    #[primary_key]
+    pub network_id: u64,
+    pub building_id: u64,
+    pub owner_identity: Identity,
+    pub collapse_respawn_at: Option<Timestamp>,
+}
+
+#[spacetimedb::table(name = dimension_desc, public)]
+pub struct DimensionDesc {
+    #[primary_key]
+    pub dimension_id: u64,
+    pub network_id: u64,
+    pub spawn_x: f32,
+    pub spawn_z: f32,
+    pub collapse_at: Option<Timestamp>,
+}
+
+#[spacetimedb::table(name = housing_moving_cost, public)]
+pub struct HousingMovingCost {
+    #[primary_key]
+    pub owner_identity: Identity,
+    pub moving_time_cost_minutes: u32,
+    pub computed_at: Timestamp,
+}
+
+/// Where a player entered an interior from, so `housing_exit` can put them back.
+#[spacetimedb::table(name = housing_visit_state, private)]
+pub struct HousingVisitState {
+    #[primary_key]
+    pub identity: Identity,
+    pub dimension_id: u64,
+    pub return_region_id: u64,
+    pub return_position: Vec<f32>,
+    pub entered_at: Timestamp,
+}
+
+#[spacetimedb::table(name = housing_regen_timer, scheduled(housing_regen))]
+pub struct HousingRegenTimer {
+    #[primary_key]
+    #[auto_inc]
+    pub scheduled_id: u64,
+    pub scheduled_at: ScheduleAt,
+    pub network_id: u64,
+}
@@ -7,3 +7,24 @@ pub struct ItemDef {
     pub max_stack: u32,
     pub volume: i32,
 }
+
+#[spacetimedb::table(name = tool_def, public)]
+pub struct ToolDef {
+    #[primary_key]
+    pub item_def_id: u64,
+    pub tool_type: u8, // 1=axe, 2=pickaxe, 3=sickle
+    pub tier: u8,
+    /// Node health removed per completed gather.
+    pub power: u32,
+}
+
+#[spacetimedb::table(name = food_def, public)]
+pub struct FoodDef {
+    #[primary_key]
+    pub item_def_id: u64,
+    pub hp_restore: i32,
+    pub stamina_restore: i32,
+    pub satiation_restore: i32,
+    pub consumable_in_combat: bool,
+    pub effect_id: u32, // status effect granted on eating, 0 = none
+}
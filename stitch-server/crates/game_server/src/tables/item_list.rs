@@ -0,0 +1,17 @@
+use spacetimedb::SpacetimeType;
+
+#[derive(SpacetimeType, Clone, Debug, PartialEq)]
+pub struct ItemListEntry {
+    pub item_def_id: u64,
+    pub min_qty: u32,
+    pub max_qty: u32,
+    /// Independent chance per roll, 0.0..=1.0.
+    pub probability: f32,
+}
+
+#[spacetimedb::table(name = item_list_def, public)]
+pub struct ItemListDef {
+    #[primary_key]
+    pub item_list_id: u64,
+    pub entries: Vec<ItemListEntry>,
+}
@@ -1,37 +1,117 @@
+pub mod ability;
+pub mod achievement;
 pub mod account;
+pub mod agent_schedule;
+pub mod balance;
+pub mod buff;
 pub mod building_state;
+pub mod character_stats;
+pub mod chat;
+pub mod claim_local_state;
+pub mod claim_member_state;
 pub mod claim_state;
+pub mod claim_tech;
 pub mod combat;
+pub mod crafting;
+pub mod day_night;
+pub mod empire;
+pub mod equipment;
+pub mod housing;
 pub mod inventory_container;
 pub mod inventory_lock;
 pub mod inventory_slot;
 pub mod item_def;
 pub mod item_instance;
+pub mod item_list;
 pub mod item_stack;
+pub mod moderation;
 pub mod movement;
+pub mod navigation;
+pub mod npc_action;
+pub mod npc_conversation;
+pub mod npc_cost;
+pub mod npc_policy;
 pub mod npc_quest;
+pub mod npc_schedule;
+pub mod npc_shop;
+pub mod party;
 pub mod permission_state;
 pub mod player_state;
+pub mod rate_limit;
+pub mod resource_node;
+pub mod resource_state;
 pub mod session_state;
+pub mod skill;
+pub mod terrain_chunk;
 pub mod transform_state;
 pub mod trade_market;
+pub mod wallet;
 
+pub use ability::{AbilityDef, AbilityState};
+pub use achievement::{
+    AchievementDef, AchievementRequirement, AchievementReward, AchievementState, KnowledgeState, PlayerUnlock,
+};
 pub use account::Account;
-pub use building_state::BuildingState;
+pub use agent_schedule::{
+    BuffExpiryAgentSchedule, ChatCleanupAgentSchedule, ClaimOwnershipAgentSchedule, ClaimUpkeepAgentSchedule,
+    DayNightAgentSchedule, EmpireUpkeepAgentSchedule, EnvironmentEffectAgentSchedule,
+    HousingCollapseAgentSchedule, NpcAiAgentSchedule, NpcConversationAgentSchedule, NpcLeaseAgentSchedule,
+    NpcMoverAgentSchedule, NpcRestockAgentSchedule, PlayerRegenAgentSchedule, ResourceRegenAgentSchedule,
+};
+pub use balance::BalanceParams;
+pub use buff::{BuffState, EnvironmentEffectDef, StatusEffect};
+pub use building_state::{BuildingDef, BuildingState};
+pub use character_stats::CharacterStats;
+pub use chat::{ChatChannel, ChatFilterLog, ChatMessage, ChatMute};
+pub use claim_local_state::ClaimLocalState;
+pub use claim_member_state::ClaimMemberState;
 pub use claim_state::ClaimState;
+pub use claim_tech::{ClaimTechDef, ClaimTechState};
 pub use combat::{AttackOutcome, AttackScheduled, CombatState, ThreatState};
+pub use crafting::{CraftJob, CraftTimer, RecipeDef, RecipeItem};
+pub use day_night::DayNightState;
+pub use empire::{
+    EmpireClaimInvitation, EmpireClaimState, EmpireMemberState, EmpireNodeSiegeState, EmpireNodeState,
+    EmpireRankState, EmpireSiegeTimer, EmpireState,
+};
+pub use equipment::{EquipmentDef, EquipmentSlot, StatModifier};
+pub use housing::{
+    DimensionDesc, DimensionNetwork, HousingMovingCost, HousingRegenTimer, HousingState,
+    HousingVisitState,
+};
 pub use inventory_container::InventoryContainer;
 pub use inventory_lock::InventoryLock;
 pub use inventory_slot::InventorySlot;
-pub use item_def::ItemDef;
+pub use item_def::{FoodDef, ItemDef, ToolDef};
 pub use item_instance::ItemInstance;
+pub use item_list::{ItemListDef, ItemListEntry};
 pub use item_stack::ItemStack;
+pub use moderation::{BanList, ModerationAction, ReportQueue};
 pub use movement::{MovementActorState, MovementRequestLog, MovementViolation};
+pub use navigation::{NavCellCost, NavObstacle, NavPath, NavWaypoint};
+pub use npc_action::{
+    NpcActionLease, NpcActionProposal, NpcActionRequest, NpcActionResult, NpcActionUsage, NpcWorker,
+};
+pub use npc_conversation::{
+    NpcConversationSession, NpcConversationTurn, NpcMemoryLong, NpcMemoryShort, NpcRelation,
+};
+pub use npc_cost::{NpcCostMetrics, NpcRegionBudget, NpcResponseCache};
+pub use npc_policy::{NpcPolicyLedger, NpcPolicyViolation, NpcPriceState, NpcRoleItemRule};
 pub use npc_quest::{
-    AgentRequest, AgentResult, NpcInteractionLog, NpcState, QuestChainState, QuestStageState,
+    NpcInteractionLog, NpcState, QuestChainDef, QuestChainState, QuestCondition, QuestReward, QuestStageDef,
+    QuestStageState,
 };
+pub use npc_schedule::NpcActionSchedule;
+pub use npc_shop::NpcShopStock;
+pub use party::{PartyInvitation, PartyMember, PartyPermission, PartyState};
 pub use permission_state::PermissionState;
 pub use player_state::PlayerState;
+pub use rate_limit::RateLimitBucket;
+pub use resource_node::{GatherTimer, ResourceDef, ResourceNode};
+pub use resource_state::ResourceState;
 pub use session_state::SessionState;
+pub use skill::{SkillDef, SkillLevelEvent, SkillProgress};
+pub use terrain_chunk::{TerrainCell, TerrainChunk, WorldGenParams};
 pub use transform_state::TransformState;
-pub use trade_market::{MarketFill, MarketOrder, TradeOffer, TradeSession};
+pub use trade_market::{MarketFill, MarketOrder, PriceIndex, TradeOffer, TradeSession};
+pub use wallet::{CurrencyFlowDaily, CurrencyTxn, Wallet};
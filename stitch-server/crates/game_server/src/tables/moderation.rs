@@ -0,0 +1,47 @@
+use spacetimedb::{Identity, Timestamp};
+
+/// Player reports with the context captured when they were filed.
+#[spacetimedb::table(name = report_queue, private)]
+pub struct ReportQueue {
+    #[primary_key]
+    #[auto_inc]
+    pub report_id: u64,
+    pub reporter_id: Identity,
+    pub target_id: Identity,
+    pub category: u8, // 1=chat,2=cheating,3=trade,4=other
+    pub details: String,
+    pub chat_snapshot: String, // target's recent lines the reporter could see
+    pub movement_violations: u32,
+    pub trade_snapshot: String, // target's recent trades
+    #[index(btree)]
+    pub status: u8,   // 0=open,1=triaged,2=resolved,3=dismissed
+    pub priority: u8, // 1=low .. 3=high
+    pub assigned_to: Option<Identity>,
+    pub action_id: u64, // linked moderation_action, 0 = none
+    pub resolution: String,
+    pub created_at: Timestamp,
+    pub updated_at: Timestamp,
+}
+
+#[spacetimedb::table(name = moderation_action, private)]
+pub struct ModerationAction {
+    #[primary_key]
+    #[auto_inc]
+    pub action_id: u64,
+    pub target_id: Identity,
+    pub action: u8, // 1=warn,2=mute,3=ban,4=restore
+    pub reason: String,
+    pub duration_secs: u64, // mute/ban length, 0 = permanent ban
+    pub moderator_id: Identity,
+    pub created_at: Timestamp,
+}
+
+#[spacetimedb::table(name = ban_list, private)]
+pub struct BanList {
+    #[primary_key]
+    pub identity: Identity,
+    pub banned_until: Option<Timestamp>, // None = permanent
+    pub reason: String,
+    pub action_id: u64,
+    pub created_at: Timestamp,
+}
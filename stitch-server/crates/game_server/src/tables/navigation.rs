@@ -0,0 +1,53 @@
+use spacetimedb::Timestamp;
+
+#[spacetimedb::table(name = nav_obstacle, public)]
+pub struct NavObstacle {
+    #[primary_key]
+    #[auto_inc]
+    pub obstacle_id: u64,
+    pub region_id: u64,
+    pub hex_x: i32,
+    pub hex_z: i32,
+    pub blocked: bool,
+    pub created_at: Timestamp,
+}
+
+#[spacetimedb::table(name = nav_cell_cost, public)]
+pub struct NavCellCost {
+    #[primary_key]
+    pub cell_key: String,
+    pub region_id: u64,
+    pub hex_x: i32,
+    pub hex_z: i32,
+    /// Extra cost on top of the base step cost of 1.0.
+    pub terrain_cost: f32,
+    pub blocked: bool,
+}
+
+#[spacetimedb::table(name = nav_path, public)]
+pub struct NavPath {
+    #[primary_key]
+    #[auto_inc]
+    pub path_id: u64,
+    pub npc_id: u64,
+    pub region_id: u64,
+    pub target_x: i32,
+    pub target_z: i32,
+    pub next_seq: u32,
+    pub waypoint_count: u32,
+    pub status: u8, // 0=moving,1=arrived,2=failed
+    pub created_at: Timestamp,
+    pub updated_at: Timestamp,
+}
+
+#[spacetimedb::table(name = nav_waypoint, public)]
+pub struct NavWaypoint {
+    #[primary_key]
+    #[auto_inc]
+    pub waypoint_id: u64,
+    #[index(btree)]
+    pub path_id: u64,
+    pub seq: u32,
+    pub hex_x: i32,
+    pub hex_z: i32,
+}
@@ -0,0 +1,81 @@
+use spacetimedb::{Identity, SpacetimeType, Timestamp};
+
+/// Structured result a worker submits for a leased request.
+#[derive(SpacetimeType, Clone, Debug, PartialEq)]
+pub struct NpcActionProposal {
+    pub action_type: u8, // 1-4 as requested, 5=give item,6=set price; checked by npc_policy
+    pub reply: String,   // dialog shown to the player
+    pub target_x: i32,   // move only: destination hex
+    pub target_z: i32,
+    pub quest_chain_id: u64, // quest only: chain offered to the player, 0 = none
+    pub item_def_id: u64,    // give item / set price
+    pub quantity: u32,       // give item only
+    pub unit_price: u64,     // set price only
+    pub memory_summary: String, // replaces the short-term memory when not empty
+    pub memory_tag: String,     // appended to the long-term memory when not empty
+    pub affinity_delta: i32,
+}
+
+/// Model usage a worker reports alongside its proposal.
+#[derive(SpacetimeType, Clone, Debug, PartialEq)]
+pub struct NpcActionUsage {
+    pub tokens_in: u32,
+    pub tokens_out: u32,
+    pub latency_ms: u32,
+}
+
+#[spacetimedb::table(name = npc_worker, private)]
+pub struct NpcWorker {
+    #[primary_key]
+    pub identity: Identity,
+    pub label: String,
+    pub registered_at: Timestamp,
+    pub last_seen_at: Timestamp,
+}
+
+#[spacetimedb::table(name = npc_action_request, private)]
+pub struct NpcActionRequest {
+    #[primary_key]
+    #[auto_inc]
+    pub request_id: u64,
+    pub npc_id: u64,
+    pub action_type: u8, // 1=move,2=talk,3=trade,4=quest
+    pub requested_by: Identity,
+    pub region_id: u64,
+    pub session_id: u64,   // conversation session, 0 = none
+    pub context: String,   // snapshot taken at enqueue time
+    pub cache_key: String, // npc_response_cache key, empty = not cacheable
+    #[index(btree)]
+    pub status: u8, // 0=pending,1=leased,2=done,3=failed
+    pub attempts: u32,
+    pub lease_owner: Option<Identity>,
+    pub lease_expires_at: Option<Timestamp>,
+    pub created_at: Timestamp,
+    pub updated_at: Timestamp,
+}
+
+/// Visible copy of a request while a worker holds its lease; workers
+/// subscribe to their own rows.
+#[spacetimedb::table(name = npc_action_lease, public)]
+pub struct NpcActionLease {
+    #[primary_key]
+    pub request_id: u64,
+    pub worker_identity: Identity,
+    pub npc_id: u64,
+    pub action_type: u8,
+    pub context: String,
+    pub expires_at: Timestamp,
+}
+
+#[spacetimedb::table(name = npc_action_result, public)]
+pub struct NpcActionResult {
+    #[primary_key]
+    pub request_id: u64,
+    pub npc_id: u64,
+    pub requested_by: Identity,
+    pub action_type: u8,
+    pub status: u8, // 1=applied,2=rejected,3=failed
+    pub reply: String,
+    pub detail: String,
+    pub applied_at: Timestamp,
+}
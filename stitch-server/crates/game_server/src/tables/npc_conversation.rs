@@ -0,0 +1,61 @@
+use spacetimedb::{Identity, Timestamp};
+
+#[spacetimedb::table(name = npc_conversation_session, public)]
+pub struct NpcConversationSession {
+    #[primary_key]
+    #[auto_inc]
+    pub session_id: u64,
+    pub npc_id: u64,
+    pub identity: Identity,
+    pub status: u8, // 1=active,2=closed,3=timed_out
+    pub turn_count: u32,
+    pub opened_at: Timestamp,
+    pub last_active_at: Timestamp,
+    pub closed_at: Option<Timestamp>,
+}
+
+#[spacetimedb::table(name = npc_conversation_turn, private)]
+pub struct NpcConversationTurn {
+    #[primary_key]
+    pub turn_key: String, // "session_id:turn_index"
+    pub session_id: u64,
+    pub turn_index: u32,
+    pub request_id: u64,
+    pub input_summary: String,
+    pub output_summary: String,
+    pub created_at: Timestamp,
+}
+
+/// Rolling summary of recent turns between one npc and one player.
+#[spacetimedb::table(name = npc_memory_short, private)]
+pub struct NpcMemoryShort {
+    #[primary_key]
+    pub memory_key: String, // "npc_id:identity"
+    pub npc_id: u64,
+    pub identity: Identity,
+    pub summary: String,
+    pub updated_at: Timestamp,
+}
+
+/// Durable event tags (promises, quests given, grudges) kept across sessions.
+#[spacetimedb::table(name = npc_memory_long, private)]
+pub struct NpcMemoryLong {
+    #[primary_key]
+    pub memory_key: String, // "npc_id:identity"
+    pub npc_id: u64,
+    pub identity: Identity,
+    pub summary: String,
+    pub updated_at: Timestamp,
+}
+
+#[spacetimedb::table(name = npc_relation, public)]
+pub struct NpcRelation {
+    #[primary_key]
+    pub relation_key: String, // "npc_id:identity"
+    pub npc_id: u64,
+    pub identity: Identity,
+    pub affinity: i32, // -100..=100
+    pub trust: i32,    // -100..=100
+    pub interactions: u32,
+    pub updated_at: Timestamp,
+}
@@ -0,0 +1,40 @@
+use spacetimedb::Timestamp;
+
+/// Worker replies reused for identical talk prompts until `expires_at`.
+#[spacetimedb::table(name = npc_response_cache, private)]
+pub struct NpcResponseCache {
+    #[primary_key]
+    pub cache_key: String, // hash of npc_id, action type and normalized input
+    pub npc_id: u64,
+    pub reply: String,
+    pub hits: u32,
+    pub created_at: Timestamp,
+    pub expires_at: Timestamp,
+}
+
+/// Per npc per day usage, from enqueues and worker usage reports.
+#[spacetimedb::table(name = npc_cost_metrics, private)]
+pub struct NpcCostMetrics {
+    #[primary_key]
+    pub metric_key: String, // "npc_id:day"
+    pub npc_id: u64,
+    pub region_id: u64,
+    pub day: u32, // days since unix epoch
+    pub requests: u32,
+    pub cache_hits: u32,
+    pub fallbacks: u32, // answered with the scripted reply because of the budget
+    pub tokens_in: u64,
+    pub tokens_out: u64,
+    pub latency_ms_total: u64,
+    pub latency_ms_max: u32,
+    pub updated_at: Timestamp,
+}
+
+/// Daily token cap for a region; regions without a row use `npc.region_daily_tokens`.
+#[spacetimedb::table(name = npc_region_budget, private)]
+pub struct NpcRegionBudget {
+    #[primary_key]
+    pub region_id: u64,
+    pub daily_token_cap: u64, // 0 = unlimited
+    pub updated_at: Timestamp,
+}
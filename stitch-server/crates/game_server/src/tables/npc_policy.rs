@@ -0,0 +1,49 @@
+use spacetimedb::{Identity, Timestamp};
+
+/// Items an npc of a given role may hand out, and how many per grant.
+#[spacetimedb::table(name = npc_role_item_rule, public)]
+pub struct NpcRoleItemRule {
+    #[primary_key]
+    pub rule_key: String, // "role:item_def_id"
+    pub role: u8,
+    pub item_def_id: u64,
+    pub max_quantity: u32,
+}
+
+/// Per player per day totals of what workers granted through npcs.
+#[spacetimedb::table(name = npc_policy_ledger, private)]
+pub struct NpcPolicyLedger {
+    #[primary_key]
+    pub ledger_key: String, // "identity:day"
+    pub identity: Identity,
+    pub day: u32, // days since unix epoch
+    pub items_granted: u32,
+    pub quests_offered: u32,
+    pub updated_at: Timestamp,
+}
+
+/// Price an npc asks for an item, set through worker price changes.
+#[spacetimedb::table(name = npc_price_state, public)]
+pub struct NpcPriceState {
+    #[primary_key]
+    pub price_key: String, // "npc_id:item_def_id"
+    pub npc_id: u64,
+    pub item_def_id: u64,
+    pub unit_price: u64,
+    pub changed_at: Timestamp,
+}
+
+#[spacetimedb::table(name = npc_policy_violation, private)]
+pub struct NpcPolicyViolation {
+    #[primary_key]
+    #[auto_inc]
+    pub violation_id: u64,
+    pub request_id: u64,
+    pub session_id: u64,
+    pub npc_id: u64,
+    pub identity: Identity,
+    pub action_type: u8,
+    pub reason: String,
+    pub severity: u8, // 1=low,2=medium,3=high
+    pub created_at: Timestamp,
+}
@@ -1,4 +1,4 @@
-use spacetimedb::{Identity, Timestamp};
+use spacetimedb::{Identity, SpacetimeType, Timestamp};
 
 #[spacetimedb::table(name = npc_state, public)]
 pub struct NpcState {
@@ -7,7 +7,10 @@ pub struct NpcState {
     pub region_id: u64,
     pub pos_x: f32,
     pub pos_z: f32,
-    pub schedule_kind: u8,
+    pub role: u8,             // 1=villager,2=merchant,3=quest giver,4=guard
+    pub schedule_kind: u8,    // 0=static,1=follows npc_action_schedule
+    pub activity: u8,         // 0=idle, else the current npc_action_schedule activity
+    pub schedule_key: String, // active npc_action_schedule entry, empty = none
     pub updated_at: Timestamp,
 }
 
@@ -24,6 +27,45 @@ pub struct NpcInteractionLog {
     pub updated_at: Timestamp,
 }
 
+#[derive(SpacetimeType, Clone, Debug, PartialEq)]
+pub struct QuestCondition {
+    pub kind: u8,       // 1=collect items,2=craft,3=reach location,4=talk to npc,5=kill
+    pub target_id: u64, // item_def_id / recipe_id / region_id / npc_id / 0 (any player)
+    pub quantity: u32,
+    pub consume: bool, // collect only: items are taken when the stage completes
+    pub pos_x: f32,    // reach only
+    pub pos_z: f32,
+    pub radius: f32,
+}
+
+#[derive(SpacetimeType, Clone, Debug, PartialEq)]
+pub struct QuestReward {
+    pub kind: u8,       // 1=item,2=skill xp
+    pub target_id: u64, // item_def_id / skill_id
+    pub amount: u64,
+}
+
+#[spacetimedb::table(name = quest_chain_def, public)]
+pub struct QuestChainDef {
+    #[primary_key]
+    pub chain_id: u64,
+    pub name: String,
+    pub prerequisite_chain_ids: Vec<u64>,
+    pub skill_id: u32, // 0 = no skill requirement
+    pub skill_level: u32,
+    pub stage_ids: Vec<u64>, // in completion order
+    pub rewards: Vec<QuestReward>,
+}
+
+#[spacetimedb::table(name = quest_stage_def, public)]
+pub struct QuestStageDef {
+    #[primary_key]
+    pub stage_id: u64,
+    pub chain_id: u64,
+    pub name: String,
+    pub conditions: Vec<QuestCondition>,
+}
+
 #[spacetimedb::table(name = quest_chain_state, public)]
 pub struct QuestChainState {
     #[primary_key]
@@ -31,6 +73,7 @@ pub struct QuestChainState {
     pub identity: Identity,
     pub chain_id: u64,
     pub status: u8, // 0=started,1=completed
+    pub current_stage_index: u32,
     pub started_at: Timestamp,
     pub updated_at: Timestamp,
 }
@@ -41,29 +84,8 @@ pub struct QuestStageState {
     pub stage_key: String,
     pub chain_key: String,
     pub stage_index: u32,
-    pub status: u8, // 0=in_progress,1=completed
+    pub stage_id: u64,
+    pub status: u8,         // 0=in_progress,1=completed
+    pub progress: Vec<u32>, // per condition, for event-driven kinds
     pub updated_at: Timestamp,
 }
-
-#[spacetimedb::table(name = agent_request, private)]
-pub struct AgentRequest {
-    #[primary_key]
-    pub request_id: String,
-    pub agent_kind: u8,
-    pub requested_by: Identity,
-    pub region_id: u64,
-    pub status: u8, // 0=requested,1=running,2=done,3=failed
-    pub payload: String,
-    pub created_at: Timestamp,
-    pub updated_at: Timestamp,
-}
-
-#[spacetimedb::table(name = agent_result, public)]
-pub struct AgentResult {
-    #[primary_key]
-    pub result_id: String,
-    pub request_id: String,
-    pub status: u8, // 1=success,2=failed
-    pub summary: String,
-    pub created_at: Timestamp,
-}
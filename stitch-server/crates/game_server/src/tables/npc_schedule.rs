@@ -0,0 +1,12 @@
+/// One step of an npc's daily routine; active from `start_hour` until the
+/// next entry's hour.
+#[spacetimedb::table(name = npc_action_schedule, public)]
+pub struct NpcActionSchedule {
+    #[primary_key]
+    pub schedule_key: String, // "npc_id:start_hour"
+    pub npc_id: u64,
+    pub start_hour: u8,
+    pub activity: u8,  // 1=home,2=work,3=market,4=wander,5=sleep
+    pub target_x: i32, // hex the npc walks to
+    pub target_z: i32,
+}
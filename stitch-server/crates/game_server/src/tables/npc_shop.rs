@@ -0,0 +1,15 @@
+use spacetimedb::Timestamp;
+
+/// An item an npc vendor sells and buys back, up to `max_quantity` on hand.
+#[spacetimedb::table(name = npc_shop_stock, public)]
+pub struct NpcShopStock {
+    #[primary_key]
+    pub stock_key: String, // "npc_id:item_def_id"
+    pub npc_id: u64,
+    pub item_def_id: u64,
+    pub base_price: u64,
+    pub quantity: u32,
+    pub max_quantity: u32,
+    pub restock_amount: u32, // added per restock tick, up to max_quantity
+    pub updated_at: Timestamp,
+}
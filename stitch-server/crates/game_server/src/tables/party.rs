@@ -0,0 +1,45 @@
+use spacetimedb::{Identity, Timestamp};
+
+#[spacetimedb::table(name = party_state, public)]
+pub struct PartyState {
+    #[primary_key]
+    #[auto_inc]
+    pub party_id: u64,
+    pub leader_identity: Identity,
+    pub loot_rule: u8,    // 0=free_for_all,1=round_robin
+    pub loot_cursor: u32, // next round-robin pick
+    pub created_at: Timestamp,
+    pub updated_at: Timestamp,
+}
+
+/// A player belongs to at most one party.
+#[spacetimedb::table(name = party_member, public)]
+pub struct PartyMember {
+    #[primary_key]
+    pub member_identity: Identity,
+    #[index(btree)]
+    pub party_id: u64,
+    pub joined_at: Timestamp,
+}
+
+#[spacetimedb::table(name = party_invitation, public)]
+pub struct PartyInvitation {
+    #[primary_key]
+    pub invite_key: String, // "party_id:invitee"
+    pub party_id: u64,
+    pub invitee_identity: Identity,
+    pub inviter_identity: Identity,
+    pub expires_at: Timestamp,
+}
+
+/// Access a claim, building or container owner granted to a whole party.
+#[spacetimedb::table(name = party_permission, private)]
+pub struct PartyPermission {
+    #[primary_key]
+    pub permission_key: String, // "target_kind:target_id:party_id"
+    pub target_kind: u8,        // 1=claim,2=building,4=container
+    pub target_id: u64,
+    #[index(btree)]
+    pub party_id: u64,
+    pub flags: u32,
+}
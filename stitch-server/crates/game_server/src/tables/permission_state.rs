@@ -4,7 +4,7 @@ use spacetimedb::Identity;
 pub struct PermissionState {
     #[primary_key]
     pub permission_key: String,
-    pub target_kind: u8, // 1=claim, 2=building
+    pub target_kind: u8, // 0=global, 1=claim, 2=building, 3=dimension, 4=container
     pub target_id: u64,
     pub subject_identity: Identity,
     pub flags: u32,
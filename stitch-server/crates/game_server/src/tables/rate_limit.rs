@@ -0,0 +1,12 @@
+use spacetimedb::{Identity, Timestamp};
+
+/// Token bucket per identity and rate-limited action.
+#[spacetimedb::table(name = rate_limit_bucket, private)]
+pub struct RateLimitBucket {
+    #[primary_key]
+    pub bucket_key: String, // "identity:action_type"
+    pub identity: Identity,
+    pub action_type: u8, // 1=chat
+    pub tokens: u32,
+    pub refilled_at: Timestamp,
+}
@@ -0,0 +1,49 @@
+use spacetimedb::{Identity, ScheduleAt, Timestamp};
+
+use crate::reducers::resource::gather_complete::gather_complete;
+
+#[spacetimedb::table(name = resource_def, public)]
+pub struct ResourceDef {
+    #[primary_key]
+    pub resource_def_id: u64,
+    pub name: String,
+    pub biome_id: u16,
+    pub tool_type: u8, // 0=bare hands
+    pub tool_tier: u8,
+    pub skill_id: u32,
+    pub skill_level: u32,
+    pub max_health: u32,
+    pub gather_duration_ms: u64,
+    pub item_list_id: u64,
+    pub respawn_secs: u64,
+    /// Chance per eligible biome cell at world generation, in 1/1000.
+    pub spawn_per_mille: u16,
+    pub max_per_chunk: u32,
+}
+
+#[spacetimedb::table(name = resource_node, public)]
+pub struct ResourceNode {
+    #[primary_key]
+    #[auto_inc]
+    pub entity_id: u64,
+    pub resource_def_id: u64,
+    pub dimension_id: u32,
+    pub chunk_id: i64,
+    pub hex_x: i32,
+    pub hex_z: i32,
+    pub health: u32,
+    pub depleted_at: Option<Timestamp>,
+    pub respawn_at: Option<Timestamp>,
+}
+
+#[spacetimedb::table(name = gather_timer, scheduled(gather_complete))]
+pub struct GatherTimer {
+    #[primary_key]
+    #[auto_inc]
+    pub scheduled_id: u64,
+    pub scheduled_at: ScheduleAt,
+    pub gatherer_identity: Identity,
+    pub node_id: u64,
+    pub tool_item_def_id: u64, // 0 = bare hands
+    pub started_at: Timestamp,
+}
@@ -0,0 +1,15 @@
+use spacetimedb::{Identity, Timestamp};
+
+/// Player vitals. `hp == 0` means downed: no regeneration and no actions
+/// until `player_respawn` or the regen agent brings the player back.
+#[spacetimedb::table(name = resource_state, public)]
+pub struct ResourceState {
+    #[primary_key]
+    pub identity: Identity,
+    pub hp: i32,
+    pub stamina: i32,
+    pub satiation: i32,
+    pub last_damage_at: Timestamp,
+    pub last_stamina_use_at: Timestamp,
+    pub updated_at: Timestamp,
+}
@@ -0,0 +1,33 @@
+use spacetimedb::{Identity, Timestamp};
+
+#[spacetimedb::table(name = skill_def, public)]
+pub struct SkillDef {
+    #[primary_key]
+    pub skill_id: u32,
+    pub name: String,
+    pub category: String, // "gathering", "crafting", "building", "combat"
+    pub max_level: u32,
+}
+
+#[spacetimedb::table(name = skill_progress, public)]
+pub struct SkillProgress {
+    #[primary_key]
+    pub progress_key: String,
+    pub identity: Identity,
+    pub skill_id: u32,
+    pub xp: u64,
+    pub level: u32,
+    pub updated_at: Timestamp,
+}
+
+#[spacetimedb::table(name = skill_level_event, public)]
+pub struct SkillLevelEvent {
+    #[primary_key]
+    #[auto_inc]
+    pub event_id: u64,
+    pub identity: Identity,
+    pub skill_id: u32,
+    pub old_level: u32,
+    pub new_level: u32,
+    pub created_at: Timestamp,
+}
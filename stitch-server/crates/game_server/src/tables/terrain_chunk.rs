@@ -0,0 +1,49 @@
+use spacetimedb::{SpacetimeType, Timestamp};
+
+#[derive(SpacetimeType, Clone, Debug, PartialEq)]
+pub struct TerrainCell {
+    pub hex_x: i32,
+    pub hex_z: i32,
+    pub elevation: i16,
+    pub water_level: i16,
+    /// 0=none, 1=sea, 2=lake, 3=river, 4=swamp
+    pub water_body_type: u8,
+    pub biome_id: u16,
+    pub biome_blend: u8,
+    pub vegetation_density: u8,
+    pub zoning_type: u8,
+    pub original_elevation: i16,
+    pub distance_to_water: i16,
+    pub distance_to_sea: i16,
+}
+
+#[spacetimedb::table(name = terrain_chunk, public)]
+pub struct TerrainChunk {
+    #[primary_key]
+    pub chunk_id: i64,
+    pub dimension_id: u32,
+    pub chunk_x: i32,
+    pub chunk_z: i32,
+    pub cells: Vec<TerrainCell>,
+    pub is_generated: bool,
+    pub generation_seed: u64,
+    /// Cell count per biome id, indexed by biome id.
+    pub biome_distribution: Vec<u16>,
+}
+
+#[spacetimedb::table(name = world_gen_params, public)]
+pub struct WorldGenParams {
+    #[primary_key]
+    pub dimension_id: u32,
+    pub seed: u64,
+    pub size_chunks: u32,
+    pub sea_level: i16,
+    pub land_cells: u32,
+    pub lake_count: u32,
+    pub river_cells: u32,
+    pub spawn_x: i32,
+    pub spawn_z: i32,
+    /// FNV-1a hash over every generated cell, for determinism snapshots.
+    pub checksum: u64,
+    pub generated_at: Timestamp,
+}
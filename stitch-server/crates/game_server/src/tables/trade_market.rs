@@ -54,3 +54,15 @@ pub struct MarketFill {
     pub seller_identity: Identity,
     pub created_at: Timestamp,
 }
+
+/// Regional reference price, a volume-weighted average of market fills.
+#[spacetimedb::table(name = price_index, public)]
+pub struct PriceIndex {
+    #[primary_key]
+    pub index_key: String, // "region_id:item_def_id"
+    pub region_id: u64,
+    pub item_def_id: u64,
+    pub price_avg: u64,
+    pub volume: u64,
+    pub updated_at: Timestamp,
+}
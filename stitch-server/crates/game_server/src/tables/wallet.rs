@@ -0,0 +1,33 @@
+use spacetimedb::{Identity, Timestamp};
+
+#[spacetimedb::table(name = wallet, public)]
+pub struct Wallet {
+    #[primary_key]
+    pub identity: Identity,
+    pub balance: u64, // coins
+    pub updated_at: Timestamp,
+}
+
+#[spacetimedb::table(name = currency_txn, private)]
+pub struct CurrencyTxn {
+    #[primary_key]
+    #[auto_inc]
+    pub txn_id: u64,
+    pub identity: Identity,
+    pub amount: i64, // > 0 credited, < 0 debited
+    pub reason: u8,  // 1=npc shop purchase,2=npc shop sale
+    pub balance_after: u64,
+    pub created_at: Timestamp,
+}
+
+/// Daily coins created (sources) and destroyed (sinks) per txn reason.
+#[spacetimedb::table(name = currency_flow_daily, private)]
+pub struct CurrencyFlowDaily {
+    #[primary_key]
+    pub flow_key: String, // "day:reason"
+    pub day: u32,         // days since unix epoch
+    pub reason: u8,
+    pub sourced: u64,
+    pub sunk: u64,
+    pub updated_at: Timestamp,
+}
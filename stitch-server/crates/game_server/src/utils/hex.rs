@@ -0,0 +1,229 @@
+//! Flat-top axial hex coordinates and chunk addressing.
+
+/// World units per hex outer radius.
+pub const HEX_OUTER_RADIUS: f32 = 1.0;
+pub const HEX_INNER_RADIUS: f32 = HEX_OUTER_RADIUS * 0.866_025_4;
+
+pub const CHUNK_WIDTH: i32 = 32;
+pub const CHUNK_HEIGHT: i32 = 32;
+pub const CHUNK_SIZE: usize = (CHUNK_WIDTH * CHUNK_HEIGHT) as usize;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct HexCoordinates {
+    pub x: i32,
+    pub z: i32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HexDirection {
+    East,
+    NorthEast,
+    NorthWest,
+    West,
+    SouthWest,
+    SouthEast,
+}
+
+impl HexDirection {
+    pub const ALL: [HexDirection; 6] = [
+        HexDirection::East,
+        HexDirection::NorthEast,
+        HexDirection::NorthWest,
+        HexDirection::West,
+        HexDirection::SouthWest,
+        HexDirection::SouthEast,
+    ];
+
+    pub fn to_vector(self) -> (i32, i32) {
+        match self {
+            HexDirection::East => (1, 0),
+            HexDirection::NorthEast => (1, -1),
+            HexDirection::NorthWest => (0, -1),
+            HexDirection::West => (-1, 0),
+            HexDirection::SouthWest => (-1, 1),
+            HexDirection::SouthEast => (0, 1),
+        }
+    }
+
+    pub fn rotate_clockwise(self) -> HexDirection {
+        match self {
+            HexDirection::East => HexDirection::SouthEast,
+            HexDirection::SouthEast => HexDirection::SouthWest,
+            HexDirection::SouthWest => HexDirection::West,
+            HexDirection::West => HexDirection::NorthWest,
+            HexDirection::NorthWest => HexDirection::NorthEast,
+            HexDirection::NorthEast => HexDirection::East,
+        }
+    }
+}
+
+impl HexCoordinates {
+    pub fn new(x: i32, z: i32) -> Self {
+        HexCoordinates { x, z }
+    }
+
+    pub fn y(&self) -> i32 {
+        -self.x - self.z
+    }
+
+    pub fn distance_to(&self, other: &HexCoordinates) -> i32 {
+        let dx = (other.x - self.x).abs();
+        let dy = (other.y() - self.y()).abs();
+        let dz = (other.z - self.z).abs();
+        (dx + dy + dz) / 2
+    }
+
+    pub fn neighbor(&self, direction: HexDirection) -> HexCoordinates {
+        let (dx, dz) = direction.to_vector();
+        HexCoordinates {
+            x: self.x + dx,
+            z: self.z + dz,
+        }
+    }
+
+    pub fn neighbors(&self) -> [HexCoordinates; 6] {
+        HexDirection::ALL.map(|d| self.neighbor(d))
+    }
+
+    pub fn to_world_position(&self) -> (f32, f32) {
+        let x = HEX_OUTER_RADIUS * 1.5 * self.x as f32;
+        let z = HEX_INNER_RADIUS * 2.0 * (self.z as f32 + self.x as f32 * 0.5);
+        (x, z)
+    }
+
+    pub fn from_world_position(world_x: f32, world_z: f32) -> HexCoordinates {
+        let x = world_x / (HEX_OUTER_RADIUS * 1.5);
+        let z = (world_z / (HEX_INNER_RADIUS * 2.0)) - x * 0.5;
+        Self::round(x, z)
+    }
+
+    /// Rounds fractional axial coordinates to the containing hex.
+    pub fn round(x: f32, z: f32) -> HexCoordinates {
+        let y = -x - z;
+
+        let mut cube_x = x.round();
+        let cube_y = y.round();
+        let mut cube_z = z.round();
+
+        let dx = (cube_x - x).abs();
+        let dy = (cube_y - y).abs();
+        let dz = (cube_z - z).abs();
+        if dx > dy && dx > dz {
+            cube_x = -cube_y - cube_z;
+        } else if dz > dy {
+            cube_z = -cube_x - cube_y;
+        }
+
+        HexCoordinates {
+            x: cube_x as i32,
+            z: cube_z as i32,
+        }
+    }
+
+    /// Hexes crossed by a straight line to `other`, both ends included.
+    pub fn line_to(&self, other: &HexCoordinates) -> Vec<HexCoordinates> {
+        let steps = self.distance_to(other);
+        if steps == 0 {
+            return vec![*self];
+        }
+        (0..=steps)
+            .map(|i| {
+                let t = i as f32 / steps as f32;
+                // Nudge off hex edges so ties always round the same way.
+                let x = self.x as f32 + (other.x - self.x) as f32 * t + 1e-4;
+                let z = self.z as f32 + (other.z - self.z) as f32 * t + 1e-4;
+                Self::round(x, z)
+            })
+            .collect()
+    }
+}
+
+/// Odd-r offset layout (row = z).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OffsetCoordinates {
+    pub row: i32,
+    pub col: i32,
+}
+
+impl From<HexCoordinates> for OffsetCoordinates {
+    fn from(hex: HexCoordinates) -> Self {
+        let col = hex.x + (hex.z - (hex.z & 1)) / 2;
+        OffsetCoordinates { row: hex.z, col }
+    }
+}
+
+impl From<OffsetCoordinates> for HexCoordinates {
+    fn from(offset: OffsetCoordinates) -> Self {
+        let x = offset.col - (offset.row - (offset.row & 1)) / 2;
+        HexCoordinates { x, z: offset.row }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ChunkCoordinates {
+    pub x: i32,
+    pub z: i32,
+    pub dimension: u32,
+}
+
+impl ChunkCoordinates {
+    pub fn to_index(&self) -> i64 {
+        (self.dimension as i64) * 1_000_000 + (self.z as i64) * 1000 + (self.x as i64) + 1
+    }
+
+    pub fn from_hex(hex: &HexCoordinates, dimension: u32) -> Self {
+        ChunkCoordinates {
+            x: hex.x.div_euclid(CHUNK_WIDTH),
+            z: hex.z.div_euclid(CHUNK_HEIGHT),
+            dimension,
+        }
+    }
+
+    /// Index of a hex within its chunk's cell vector.
+    pub fn cell_index(hex: &HexCoordinates) -> usize {
+        (hex.z.rem_euclid(CHUNK_HEIGHT) * CHUNK_WIDTH + hex.x.rem_euclid(CHUNK_WIDTH)) as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn neighbors_are_one_step_away() {
+        let center = HexCoordinates::new(3, -2);
+        for neighbor in center.neighbors() {
+            assert_eq!(center.distance_to(&neighbor), 1);
+        }
+    }
+
+    #[test]
+    fn distance_is_symmetric_and_counts_hex_steps() {
+        let a = HexCoordinates::new(0, 0);
+        let b = HexCoordinates::new(3, -1);
+        assert_eq!(a.distance_to(&b), 3);
+        assert_eq!(b.distance_to(&a), 3);
+        assert_eq!(a.distance_to(&HexCoordinates::new(2, 2)), 4);
+    }
+
+    #[test]
+    fn world_position_round_trips() {
+        for (x, z) in [(0, 0), (5, -3), (-7, 11), (40, 40)] {
+            let hex = HexCoordinates::new(x, z);
+            let (wx, wz) = hex.to_world_position();
+            assert_eq!(HexCoordinates::from_world_position(wx, wz), hex);
+        }
+    }
+
+    #[test]
+    fn line_to_is_contiguous_and_includes_both_ends() {
+        let from = HexCoordinates::new(-2, 1);
+        let to = HexCoordinates::new(4, -3);
+        let line = from.line_to(&to);
+        assert_eq!(line.len() as i32, from.distance_to(&to) + 1);
+        assert_eq!((line[0], line[line.len() - 1]), (from, to));
+        for pair in line.windows(2) {
+            assert_eq!(pair[0].distance_to(&pair[1]), 1);
+        }
+    }
+}
@@ -1 +1,7 @@
 //! Utility helpers shared across reducers and services.
+
+pub mod hex;
+pub mod noise;
+pub mod pathfinding;
+pub mod rng;
+pub mod time;
@@ -0,0 +1,183 @@
+//! Deterministic OpenSimplex 2D noise and fBm helpers.
+
+const STRETCH_2D: f64 = -0.211_324_865_405_187;
+const SQUISH_2D: f64 = 0.366_025_403_784_439;
+const NORM_2D: f64 = 47.0;
+
+const GRADIENTS_2D: [i8; 16] = [5, 2, 2, 5, -5, 2, -2, 5, 5, -2, 2, -5, -5, -2, -2, -5];
+
+pub struct OpenSimplexNoise {
+    perm: [u8; 256],
+}
+
+impl OpenSimplexNoise {
+    pub fn new(seed: i64) -> Self {
+        let mut perm = [0u8; 256];
+        let mut source = [0u8; 256];
+        for (i, s) in source.iter_mut().enumerate() {
+            *s = i as u8;
+        }
+
+        let step = |s: i64| s.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1_442_695_040_888_963_407);
+        let mut seed = step(step(step(seed)));
+        for i in (0..256i64).rev() {
+            seed = step(seed);
+            let mut r = seed.wrapping_add(31) % (i + 1);
+            if r < 0 {
+                r += i + 1;
+            }
+            perm[i as usize] = source[r as usize];
+            source[r as usize] = source[i as usize];
+        }
+
+        OpenSimplexNoise { perm }
+    }
+
+    fn extrapolate(&self, xsb: i64, ysb: i64, dx: f64, dy: f64) -> f64 {
+        let a = self.perm[(xsb & 0xFF) as usize] as i64;
+        let index = (self.perm[((a + ysb) & 0xFF) as usize] & 0x0E) as usize;
+        GRADIENTS_2D[index] as f64 * dx + GRADIENTS_2D[index + 1] as f64 * dy
+    }
+
+    /// Samples 2D noise in roughly [-1, 1].
+    pub fn sample_2d(&self, x: f64, y: f64) -> f64 {
+        let stretch_offset = (x + y) * STRETCH_2D;
+        let xs = x + stretch_offset;
+        let ys = y + stretch_offset;
+
+        let mut xsb = xs.floor() as i64;
+        let mut ysb = ys.floor() as i64;
+
+        let squish_offset = (xsb + ysb) as f64 * SQUISH_2D;
+        let xb = xsb as f64 + squish_offset;
+        let yb = ysb as f64 + squish_offset;
+
+        let xins = xs - xsb as f64;
+        let yins = ys - ysb as f64;
+        let in_sum = xins + yins;
+
+        let mut dx0 = x - xb;
+        let mut dy0 = y - yb;
+        let mut value = 0.0;
+
+        let dx1 = dx0 - 1.0 - SQUISH_2D;
+        let dy1 = dy0 - SQUISH_2D;
+        let mut attn1 = 2.0 - dx1 * dx1 - dy1 * dy1;
+        if attn1 > 0.0 {
+            attn1 *= attn1;
+            value += attn1 * attn1 * self.extrapolate(xsb + 1, ysb, dx1, dy1);
+        }
+
+        let dx2 = dx0 - SQUISH_2D;
+        let dy2 = dy0 - 1.0 - SQUISH_2D;
+        let mut attn2 = 2.0 - dx2 * dx2 - dy2 * dy2;
+        if attn2 > 0.0 {
+            attn2 *= attn2;
+            value += attn2 * attn2 * self.extrapolate(xsb, ysb + 1, dx2, dy2);
+        }
+
+        let (xsv_ext, ysv_ext, dx_ext, dy_ext);
+        if in_sum <= 1.0 {
+            let zins = 1.0 - in_sum;
+            if zins > xins || zins > yins {
+                if xins > yins {
+                    xsv_ext = xsb + 1;
+                    ysv_ext = ysb - 1;
+                    dx_ext = dx0 - 1.0;
+                    dy_ext = dy0 + 1.0;
+                } else {
+                    xsv_ext = xsb - 1;
+                    ysv_ext = ysb + 1;
+                    dx_ext = dx0 + 1.0;
+                    dy_ext = dy0 - 1.0;
+                }
+            } else {
+                xsv_ext = xsb + 1;
+                ysv_ext = ysb + 1;
+                dx_ext = dx0 - 1.0 - 2.0 * SQUISH_2D;
+                dy_ext = dy0 - 1.0 - 2.0 * SQUISH_2D;
+            }
+        } else {
+            let zins = 2.0 - in_sum;
+            if zins < xins || zins < yins {
+                if xins > yins {
+                    xsv_ext = xsb + 2;
+                    ysv_ext = ysb;
+                    dx_ext = dx0 - 2.0 - 2.0 * SQUISH_2D;
+                    dy_ext = dy0 - 2.0 * SQUISH_2D;
+                } else {
+                    xsv_ext = xsb;
+                    ysv_ext = ysb + 2;
+                    dx_ext = dx0 - 2.0 * SQUISH_2D;
+                    dy_ext = dy0 - 2.0 - 2.0 * SQUISH_2D;
+                }
+            } else {
+                xsv_ext = xsb;
+                ysv_ext = ysb;
+                dx_ext = dx0;
+                dy_ext = dy0;
+            }
+            xsb += 1;
+            ysb += 1;
+            dx0 = dx0 - 1.0 - 2.0 * SQUISH_2D;
+            dy0 = dy0 - 1.0 - 2.0 * SQUISH_2D;
+        }
+
+        let mut attn0 = 2.0 - dx0 * dx0 - dy0 * dy0;
+        if attn0 > 0.0 {
+            attn0 *= attn0;
+            value += attn0 * attn0 * self.extrapolate(xsb, ysb, dx0, dy0);
+        }
+
+        let mut attn_ext = 2.0 - dx_ext * dx_ext - dy_ext * dy_ext;
+        if attn_ext > 0.0 {
+            attn_ext *= attn_ext;
+            value += attn_ext * attn_ext * self.extrapolate(xsv_ext, ysv_ext, dx_ext, dy_ext);
+        }
+
+        value / NORM_2D
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct NoiseSpecs {
+    pub seed: i64,
+    pub scale: f32,
+    pub octaves: u32,
+    pub persistence: f32,
+    pub lacunarity: f32,
+    pub offset: (f32, f32),
+}
+
+/// Multi-octave fBm sampler normalized to [0, 1].
+pub struct Fbm {
+    noise: OpenSimplexNoise,
+    specs: NoiseSpecs,
+}
+
+impl Fbm {
+    pub fn new(specs: NoiseSpecs) -> Self {
+        Fbm {
+            noise: OpenSimplexNoise::new(specs.seed),
+            specs,
+        }
+    }
+
+    pub fn sample(&self, x: f32, z: f32) -> f32 {
+        let mut total = 0.0f64;
+        let mut amplitude = 1.0f64;
+        let mut frequency = 1.0f64;
+        let mut max_value = 0.0f64;
+
+        for _ in 0..self.specs.octaves.max(1) {
+            let sx = (x + self.specs.offset.0) as f64 * self.specs.scale as f64 * frequency;
+            let sz = (z + self.specs.offset.1) as f64 * self.specs.scale as f64 * frequency;
+            total += self.noise.sample_2d(sx, sz) * amplitude;
+            max_value += amplitude;
+            amplitude *= self.specs.persistence as f64;
+            frequency *= self.specs.lacunarity as f64;
+        }
+
+        (((total / max_value) + 1.0) / 2.0).clamp(0.0, 1.0) as f32
+    }
+}
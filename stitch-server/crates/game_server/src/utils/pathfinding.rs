@@ -0,0 +1,196 @@
+//! Bounded A* search, generic over the node type.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::Hash;
+
+use crate::utils::hex::HexCoordinates;
+
+pub struct PathResult<T> {
+    /// Start and goal included.
+    pub path: Vec<T>,
+    pub cost: u32,
+    pub expanded: usize,
+}
+
+pub struct Pathfinder<T> {
+    g_costs: HashMap<T, u32>,
+    came_from: HashMap<T, T>,
+    // Duplicates are allowed instead of decrease-key; stale entries are skipped.
+    open: BinaryHeap<Reverse<(u32, T)>>,
+    edges: Vec<(T, u32)>,
+}
+
+impl<T: Copy + Eq + Hash + Ord> Pathfinder<T> {
+    pub fn with_capacity(capacity: usize) -> Self {
+        Pathfinder {
+            g_costs: HashMap::with_capacity(capacity),
+            came_from: HashMap::with_capacity(capacity),
+            open: BinaryHeap::with_capacity(capacity),
+            edges: Vec::with_capacity(12),
+        }
+    }
+
+    /// Returns `None` when the goal is unreachable or `node_limit` expansions
+    /// were spent first. `get_edges` pushes `(neighbour, step_cost)` pairs.
+    pub fn find_path(
+        &mut self,
+        start: T,
+        goal: T,
+        node_limit: usize,
+        get_h_cost: impl Fn(T) -> u32,
+        mut get_edges: impl FnMut(T, &mut Vec<(T, u32)>),
+    ) -> Option<PathResult<T>> {
+        self.g_costs.clear();
+        self.came_from.clear();
+        self.open.clear();
+
+        self.g_costs.insert(start, 0);
+        self.open.push(Reverse((get_h_cost(start), start)));
+
+        let mut expanded = 0;
+        while let Some(Reverse((f_cost, current))) = self.open.pop() {
+            let g_cost = self.g_costs[&current];
+            if f_cost > g_cost.saturating_add(get_h_cost(current)) {
+                continue;
+            }
+            if current == goal {
+                let mut path = vec![current];
+                let mut node = current;
+                while let Some(&prev) = self.came_from.get(&node) {
+                    path.push(prev);
+                    node = prev;
+                }
+                path.reverse();
+                return Some(PathResult {
+                    path,
+                    cost: g_cost,
+                    expanded,
+                });
+            }
+
+            expanded += 1;
+            if expanded > node_limit {
+                return None;
+            }
+
+            self.edges.clear();
+            get_edges(current, &mut self.edges);
+            for &(next, step_cost) in &self.edges {
+                let cost = g_cost.saturating_add(step_cost);
+                if self.g_costs.get(&next).is_some_and(|&known| known <= cost) {
+                    continue;
+                }
+                self.g_costs.insert(next, cost);
+                self.came_from.insert(next, current);
+                self.open.push(Reverse((cost.saturating_add(get_h_cost(next)), next)));
+            }
+        }
+        None
+    }
+}
+
+/// A* over hex neighbours. `step_cost(from, to)` returns `None` for impassable
+/// steps; the heuristic assumes every step costs at least `min_step_cost`.
+pub fn find_hex_path(
+    start: HexCoordinates,
+    goal: HexCoordinates,
+    node_limit: usize,
+    min_step_cost: u32,
+    mut step_cost: impl FnMut(HexCoordinates, HexCoordinates) -> Option<u32>,
+) -> Option<PathResult<HexCoordinates>> {
+    let mut pathfinder = Pathfinder::with_capacity(node_limit.min(4096));
+    pathfinder.find_path(
+        start,
+        goal,
+        node_limit,
+        |hex| hex.distance_to(&goal) as u32 * min_step_cost,
+        |hex, edges| {
+            for next in hex.neighbors() {
+                if let Some(cost) = step_cost(hex, next) {
+                    edges.push((next, cost));
+                }
+            }
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::find_hex_path;
+    use crate::utils::hex::HexCoordinates;
+
+    const STEP: u32 = 10;
+
+    fn hex(x: i32, z: i32) -> HexCoordinates {
+        HexCoordinates::new(x, z)
+    }
+
+    /// Open terrain inside a `bound`-hex radius around the origin, minus `blocked`.
+    fn open_grid(
+        bound: i32,
+        blocked: &[HexCoordinates],
+    ) -> impl FnMut(HexCoordinates, HexCoordinates) -> Option<u32> + '_ {
+        move |_, to| {
+            if to.distance_to(&hex(0, 0)) > bound || blocked.contains(&to) {
+                return None;
+            }
+            Some(STEP)
+        }
+    }
+
+    fn assert_contiguous(path: &[HexCoordinates]) {
+        for pair in path.windows(2) {
+            assert_eq!(pair[0].distance_to(&pair[1]), 1, "{:?} -> {:?}", pair[0], pair[1]);
+        }
+    }
+
+    #[test]
+    fn start_equal_to_goal_is_a_zero_cost_path() {
+        let result = find_hex_path(hex(2, 1), hex(2, 1), 10, STEP, open_grid(8, &[])).unwrap();
+        assert_eq!(result.path, vec![hex(2, 1)]);
+        assert_eq!(result.cost, 0);
+    }
+
+    #[test]
+    fn open_terrain_yields_a_shortest_path() {
+        let result = find_hex_path(hex(0, 0), hex(4, -2), 1000, STEP, open_grid(8, &[])).unwrap();
+        assert_eq!(result.path.first(), Some(&hex(0, 0)));
+        assert_eq!(result.path.last(), Some(&hex(4, -2)));
+        assert_eq!(result.path.len(), 5);
+        assert_eq!(result.cost, 4 * STEP);
+        assert_contiguous(&result.path);
+    }
+
+    #[test]
+    fn walls_are_routed_around() {
+        let wall: Vec<HexCoordinates> = (-3..=3).map(|z| hex(1, z)).collect();
+        let result = find_hex_path(hex(0, 0), hex(2, 0), 1000, STEP, open_grid(8, &wall)).unwrap();
+        assert!(result.path.iter().all(|h| !wall.contains(h)));
+        assert!(result.cost > 2 * STEP);
+        assert_eq!(result.cost, (result.path.len() as u32 - 1) * STEP);
+        assert_contiguous(&result.path);
+    }
+
+    #[test]
+    fn enclosed_goal_is_unreachable() {
+        let ring = hex(3, 0).neighbors();
+        assert!(find_hex_path(hex(0, 0), hex(3, 0), 10_000, STEP, open_grid(6, &ring)).is_none());
+    }
+
+    #[test]
+    fn node_limit_bounds_the_search() {
+        assert!(find_hex_path(hex(0, 0), hex(6, 0), 3, STEP, open_grid(8, &[])).is_none());
+        let result = find_hex_path(hex(0, 0), hex(6, 0), 1000, STEP, open_grid(8, &[])).unwrap();
+        assert!(result.expanded <= 1000);
+    }
+
+    #[test]
+    fn cheaper_detours_beat_expensive_shortcuts() {
+        let swamp = hex(1, 0);
+        let cost = |_: HexCoordinates, to: HexCoordinates| Some(if to == swamp { 100 } else { STEP });
+        let result = find_hex_path(hex(0, 0), hex(2, 0), 1000, STEP, cost).unwrap();
+        assert!(!result.path.contains(&swamp));
+        assert_eq!(result.cost, 3 * STEP);
+    }
+}
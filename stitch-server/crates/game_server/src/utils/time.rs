@@ -0,0 +1,23 @@
+//! Calendar helpers on top of spacetimedb timestamps.
+
+use spacetimedb::Timestamp;
+
+const DAY_MICROS: i64 = 86_400_000_000;
+
+/// Days since the unix epoch; keys daily caps and tallies.
+pub fn day_of(ts: Timestamp) -> u32 {
+    (ts.to_micros_since_unix_epoch() / DAY_MICROS) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn days_roll_over_at_utc_midnight() {
+        assert_eq!(day_of(Timestamp::UNIX_EPOCH), 0);
+        assert_eq!(day_of(Timestamp::from_micros_since_unix_epoch(DAY_MICROS - 1)), 0);
+        assert_eq!(day_of(Timestamp::from_micros_since_unix_epoch(DAY_MICROS)), 1);
+        assert_eq!(day_of(Timestamp::from_micros_since_unix_epoch(DAY_MICROS * 20_000 + 5)), 20_000);
+    }
+}
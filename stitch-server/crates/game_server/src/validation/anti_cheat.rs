@@ -1,10 +1,16 @@
 use spacetimedb::{Identity, ReducerContext, Table, Timestamp};
 
+use crate::services::{movement, terrain};
 use crate::tables::{MovementActorState, MovementRequestLog, MovementViolation};
 use crate::tables::movement::movement_request_log;
 use crate::tables::movement::movement_violation;
 
-pub const MOVE_MAX_DISTANCE_PER_STEP: f32 = 8.0;
+/// Longest gap between requests that still earns travel distance.
+pub const MOVE_MAX_ELAPSED_MS: u64 = 1_000;
+/// Headroom over the exact speed budget for latency jitter.
+pub const MOVE_SPEED_TOLERANCE: f32 = 1.25;
+pub const MOVE_DISTANCE_SLACK: f32 = 1.0;
+pub const MOVE_HEIGHT_TOLERANCE: f32 = 2.0;
 
 pub(crate) fn validate_request_id(request_id: &str) -> Result<String, String> {
     let trimmed = request_id.trim();
@@ -27,19 +33,68 @@ pub(crate) fn is_duplicate_request(ctx: &ReducerContext, request_id: &str) -> bo
 }
 
 pub(crate) fn validate_actor_progression(
+    ctx: &ReducerContext,
+    region_id: u64,
     actor_state: Option<MovementActorState>,
     client_ts_ms: u64,
     next_position: &[f32],
 ) -> Result<(), &'static str> {
-    if let Some(existing) = actor_state {
-        if client_ts_ms <= existing.last_client_ts_ms {
-            return Err("non_monotonic_timestamp");
+    let mut sampler = terrain::TerrainSampler::for_region(ctx, region_id);
+    let next_hex = terrain::hex_at(next_position[0], next_position[2]);
+    if let Some(cell) = sampler.cell(next_hex) {
+        if !terrain::is_walkable(&cell) {
+            return Err("unwalkable_destination");
+        }
+        if (next_position[1] - terrain::surface_height(&cell)).abs() > MOVE_HEIGHT_TOLERANCE {
+            return Err("height_mismatch");
+        }
+    }
+
+    let Some(existing) = actor_state.filter(|a| a.region_id == region_id) else {
+        if !terrain::blocked_cells(ctx, region_id, &[next_hex]).is_empty() {
+            return Err("path_blocked");
         }
+        return Ok(());
+    };
 
-        let allowed_sq = MOVE_MAX_DISTANCE_PER_STEP * MOVE_MAX_DISTANCE_PER_STEP;
-        if distance_sq(&existing.last_position, next_position) > allowed_sq {
-            return Err("distance_exceeded");
+    if client_ts_ms <= existing.last_client_ts_ms {
+        return Err("non_monotonic_timestamp");
+    }
+
+    // the client clock only shortens the budget; server time since the last accepted move caps it
+    let server_elapsed_ms = ctx
+        .timestamp
+        .duration_since(existing.updated_at)
+        .map_or(0, |d| d.as_millis() as u64);
+    let elapsed_ms = (client_ts_ms - existing.last_client_ts_ms)
+        .min(server_elapsed_ms)
+        .min(MOVE_MAX_ELAPSED_MS);
+    let speed = movement::movement_speed(ctx, existing.identity);
+    let allowed = speed * (elapsed_ms as f32 / 1000.0) * MOVE_SPEED_TOLERANCE + MOVE_DISTANCE_SLACK;
+    if distance_sq(&existing.last_position, next_position) > allowed * allowed {
+        return Err("distance_exceeded");
+    }
+
+    let from_hex = terrain::hex_at(existing.last_position[0], existing.last_position[2]);
+    let path = from_hex.line_to(&next_hex);
+    let blocked = terrain::blocked_cells(ctx, region_id, &path[1..]);
+    let mut previous = sampler.cell(from_hex);
+    for hex in path.iter().skip(1) {
+        if blocked.contains(&(hex.x, hex.z)) {
+            return Err("path_blocked");
+        }
+        let cell = sampler.cell(*hex);
+        if let Some(cell) = &cell {
+            if !terrain::is_walkable(cell) {
+                return Err("unwalkable_path");
+            }
+            if let Some(prev) = &previous {
+                if cell.elevation - prev.elevation > terrain::MAX_CLIMB_PER_STEP {
+                    return Err("climb_exceeded");
+                }
+            }
         }
+        previous = cell;
     }
 
     Ok(())
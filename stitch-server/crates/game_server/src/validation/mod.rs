@@ -1 +1,2 @@
 pub mod anti_cheat;
+pub mod rate_limit;
@@ -0,0 +1,75 @@
+use std::time::Duration;
+
+use spacetimedb::{Identity, ReducerContext, Table};
+
+use crate::tables::RateLimitBucket;
+use crate::tables::rate_limit::rate_limit_bucket;
+
+pub const ACTION_CHAT: u8 = 1;
+
+pub fn bucket_key(identity: Identity, action_type: u8) -> String {
+    format!("{identity}:{action_type}")
+}
+
+/// Whole refill periods in `elapsed`, capped at a full bucket.
+pub fn regained(elapsed: Duration, refill: Duration, capacity: u32) -> u32 {
+    (elapsed.as_secs() / refill.as_secs().max(1)).min(capacity as u64) as u32
+}
+
+/// Takes one token from the caller's bucket for `action_type`. Buckets hold
+/// up to `capacity` tokens and regain one every `refill_secs`.
+pub(crate) fn take(
+    ctx: &ReducerContext,
+    identity: Identity,
+    action_type: u8,
+    capacity: u32,
+    refill_secs: u64,
+) -> Result<(), String> {
+    let key = bucket_key(identity, action_type);
+    let Some(mut bucket) = ctx.db.rate_limit_bucket().bucket_key().find(key.clone()) else {
+        ctx.db.rate_limit_bucket().insert(RateLimitBucket {
+            bucket_key: key,
+            identity,
+            action_type,
+            tokens: capacity.saturating_sub(1),
+            refilled_at: ctx.timestamp,
+        });
+        return Ok(());
+    };
+
+    let refill = Duration::from_secs(refill_secs.max(1));
+    let elapsed = ctx.timestamp.duration_since(bucket.refilled_at).unwrap_or_default();
+    let regained = regained(elapsed, refill, capacity);
+    if regained > 0 {
+        bucket.tokens = (bucket.tokens + regained).min(capacity);
+        bucket.refilled_at = if bucket.tokens == capacity {
+            ctx.timestamp
+        } else {
+            bucket.refilled_at + refill * regained
+        };
+    }
+    if bucket.tokens == 0 {
+        return Err("rate limited, slow down".to_string());
+    }
+    bucket.tokens -= 1;
+    ctx.db.rate_limit_bucket().bucket_key().update(bucket);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn regains_one_token_per_whole_period() {
+        let refill = Duration::from_secs(10);
+        assert_eq!(regained(Duration::from_secs(9), refill, 5), 0);
+        assert_eq!(regained(Duration::from_secs(10), refill, 5), 1);
+        assert_eq!(regained(Duration::from_secs(29), refill, 5), 2);
+    }
+
+    #[test]
+    fn long_idle_refills_at_most_a_full_bucket() {
+        assert_eq!(regained(Duration::from_secs(3_600), Duration::from_secs(1), 5), 5);
+    }
+}